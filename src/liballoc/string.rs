@@ -1420,7 +1420,8 @@ impl String {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn len(&self) -> usize {
+    #[rustc_const_unstable(feature = "const_collection_len")]
+    pub const fn len(&self) -> usize {
         self.vec.len()
     }
 
@@ -1439,7 +1440,8 @@ impl String {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn is_empty(&self) -> bool {
+    #[rustc_const_unstable(feature = "const_collection_len")]
+    pub const fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
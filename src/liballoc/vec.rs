@@ -1313,7 +1313,8 @@ impl<T> Vec<T> {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn len(&self) -> usize {
+    #[rustc_const_unstable(feature = "const_collection_len")]
+    pub const fn len(&self) -> usize {
         self.len
     }
 
@@ -1329,7 +1330,8 @@ impl<T> Vec<T> {
     /// assert!(!v.is_empty());
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn is_empty(&self) -> bool {
+    #[rustc_const_unstable(feature = "const_collection_len")]
+    pub const fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
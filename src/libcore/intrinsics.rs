@@ -1347,6 +1347,33 @@ extern "rust-intrinsic" {
     /// See documentation of `<*const T>::offset_from` for details.
     pub fn ptr_offset_from<T>(ptr: *const T, base: *const T) -> isize;
 
+    /// See documentation of `<*const T>::guaranteed_eq` for details.
+    #[cfg(not(bootstrap))]
+    pub fn ptr_guaranteed_eq<T>(ptr: *const T, other: *const T) -> bool;
+
+    /// See documentation of `<*const T>::guaranteed_ne` for details.
+    #[cfg(not(bootstrap))]
+    pub fn ptr_guaranteed_ne<T>(ptr: *const T, other: *const T) -> bool;
+
+    /// Calls `called_in_const` with the (tupled) arguments `arg` during const evaluation, and
+    /// `called_at_rt` in code that runs at runtime.
+    ///
+    /// This lets a `const fn` provide a const-compatible fallback for an implementation that uses
+    /// non-const machinery, like SIMD or system calls. Both functions must behave the same, as
+    /// it is unspecified which of them is called in a given situation; for example, optimizations
+    /// may evaluate a call at compile time even if it happens in code that runs at runtime.
+    ///
+    /// `called_in_const` must be a `const fn`.
+    #[cfg(not(bootstrap))]
+    pub fn const_eval_select<ARG, F, G, RET>(
+        arg: ARG,
+        called_in_const: F,
+        called_at_rt: G,
+    ) -> RET
+    where
+        F: FnOnce<ARG, Output = RET>,
+        G: FnOnce<ARG, Output = RET>;
+
     /// Internal hook used by Miri to implement unwinding.
     /// Compiles to a NOP during non-Miri codegen.
     ///
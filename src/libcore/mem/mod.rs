@@ -866,7 +866,8 @@ impl<T> fmt::Debug for Discriminant<T> {
 /// assert_ne!(mem::discriminant(&Foo::B(3)), mem::discriminant(&Foo::C(3)));
 /// ```
 #[stable(feature = "discriminant_value", since = "1.21.0")]
-pub fn discriminant<T>(v: &T) -> Discriminant<T> {
+#[rustc_const_unstable(feature = "const_discriminant")]
+pub const fn discriminant<T>(v: &T) -> Discriminant<T> {
     #[cfg(bootstrap)]
     // SAFETY: going away soon
     unsafe {
@@ -58,7 +58,9 @@ pub const MAX_10_EXP: i32 = 38;
 
 /// Not a Number (NaN).
 #[stable(feature = "rust1", since = "1.0.0")]
-pub const NAN: f32 = 0.0_f32 / 0.0_f32;
+// Spelled out as the bits of a positive quiet NaN without payload, since the sign and payload of
+// the NaN produced by `0.0 / 0.0` depend on the target.
+pub const NAN: f32 = unsafe { crate::mem::transmute(0x7fc0_0000_u32) };
 /// Infinity (∞).
 #[stable(feature = "rust1", since = "1.0.0")]
 pub const INFINITY: f32 = 1.0_f32 / 0.0_f32;
@@ -58,7 +58,9 @@ pub const MAX_10_EXP: i32 = 308;
 
 /// Not a Number (NaN).
 #[stable(feature = "rust1", since = "1.0.0")]
-pub const NAN: f64 = 0.0_f64 / 0.0_f64;
+// Spelled out as the bits of a positive quiet NaN without payload, since the sign and payload of
+// the NaN produced by `0.0 / 0.0` depend on the target.
+pub const NAN: f64 = unsafe { crate::mem::transmute(0x7ff8_0000_0000_0000_u64) };
 /// Infinity (∞).
 #[stable(feature = "rust1", since = "1.0.0")]
 pub const INFINITY: f64 = 1.0_f64 / 0.0_f64;
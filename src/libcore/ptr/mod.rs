@@ -1181,8 +1181,9 @@ impl<T: ?Sized> *const T {
     /// }
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[rustc_const_unstable(feature = "const_ptr_offset")]
     #[inline]
-    pub unsafe fn offset(self, count: isize) -> *const T where T: Sized {
+    pub const unsafe fn offset(self, count: isize) -> *const T where T: Sized {
         intrinsics::offset(self, count)
     }
 
@@ -1236,8 +1237,9 @@ impl<T: ?Sized> *const T {
     /// }
     /// ```
     #[stable(feature = "ptr_wrapping_offset", since = "1.16.0")]
+    #[rustc_const_unstable(feature = "const_ptr_offset")]
     #[inline]
-    pub fn wrapping_offset(self, count: isize) -> *const T where T: Sized {
+    pub const fn wrapping_offset(self, count: isize) -> *const T where T: Sized {
         unsafe {
             intrinsics::arith_offset(self, count)
         }
@@ -1317,6 +1319,58 @@ impl<T: ?Sized> *const T {
         intrinsics::ptr_offset_from(self, origin)
     }
 
+    /// Returns whether two pointers are guaranteed to be equal.
+    ///
+    /// At runtime this function behaves like `self == other`.
+    /// However, in some contexts (e.g., compile-time evaluation),
+    /// it is not always possible to determine equality of two pointers, so this function may
+    /// spuriously return `false` for pointers that later actually turn out to be equal.
+    /// But when it returns `true`, the pointers are guaranteed to be equal.
+    ///
+    /// This function is the mirror of [`guaranteed_ne`], but not its inverse. There are pointer
+    /// comparisons for which both functions return `false`.
+    ///
+    /// [`guaranteed_ne`]: #method.guaranteed_ne
+    ///
+    /// The return value may change depending on the compiler version and unsafe code may not
+    /// rely on the result of this function for soundness. It is suggested to only use this
+    /// function for performance optimizations where spurious `false` return values by this
+    /// function do not affect the outcome, but just the performance.
+    #[unstable(feature = "const_raw_ptr_comparison", issue = "53020")]
+    #[rustc_const_unstable(feature = "const_raw_ptr_comparison")]
+    #[cfg(not(bootstrap))]
+    #[inline]
+    pub const fn guaranteed_eq(self, other: *const T) -> bool where T: Sized {
+        // SAFETY: comparing two pointers has no preconditions.
+        unsafe { intrinsics::ptr_guaranteed_eq(self, other) }
+    }
+
+    /// Returns whether two pointers are guaranteed to be unequal.
+    ///
+    /// At runtime this function behaves like `self != other`.
+    /// However, in some contexts (e.g., compile-time evaluation),
+    /// it is not always possible to determine the inequality of two pointers, so this function may
+    /// spuriously return `false` for pointers that later actually turn out to be unequal.
+    /// But when it returns `true`, the pointers are guaranteed to be unequal.
+    ///
+    /// This function is the mirror of [`guaranteed_eq`], but not its inverse. There are pointer
+    /// comparisons for which both functions return `false`.
+    ///
+    /// [`guaranteed_eq`]: #method.guaranteed_eq
+    ///
+    /// The return value may change depending on the compiler version and unsafe code may not
+    /// rely on the result of this function for soundness. It is suggested to only use this
+    /// function for performance optimizations where spurious `false` return values by this
+    /// function do not affect the outcome, but just the performance.
+    #[unstable(feature = "const_raw_ptr_comparison", issue = "53020")]
+    #[rustc_const_unstable(feature = "const_raw_ptr_comparison")]
+    #[cfg(not(bootstrap))]
+    #[inline]
+    pub const fn guaranteed_ne(self, other: *const T) -> bool where T: Sized {
+        // SAFETY: comparing two pointers has no preconditions.
+        unsafe { intrinsics::ptr_guaranteed_ne(self, other) }
+    }
+
     /// Calculates the distance between two pointers. The returned value is in
     /// units of T: the distance in bytes is divided by `mem::size_of::<T>()`.
     ///
@@ -1864,8 +1918,9 @@ impl<T: ?Sized> *mut T {
     /// }
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[rustc_const_unstable(feature = "const_ptr_offset")]
     #[inline]
-    pub unsafe fn offset(self, count: isize) -> *mut T where T: Sized {
+    pub const unsafe fn offset(self, count: isize) -> *mut T where T: Sized {
         intrinsics::offset(self, count) as *mut T
     }
 
@@ -1918,8 +1973,9 @@ impl<T: ?Sized> *mut T {
     /// assert_eq!(&data, &[0, 2, 0, 4, 0]);
     /// ```
     #[stable(feature = "ptr_wrapping_offset", since = "1.16.0")]
+    #[rustc_const_unstable(feature = "const_ptr_offset")]
     #[inline]
-    pub fn wrapping_offset(self, count: isize) -> *mut T where T: Sized {
+    pub const fn wrapping_offset(self, count: isize) -> *mut T where T: Sized {
         unsafe {
             intrinsics::arith_offset(self, count) as *mut T
         }
@@ -2043,6 +2099,58 @@ impl<T: ?Sized> *mut T {
         (self as *const T).offset_from(origin)
     }
 
+    /// Returns whether two pointers are guaranteed to be equal.
+    ///
+    /// At runtime this function behaves like `self == other`.
+    /// However, in some contexts (e.g., compile-time evaluation),
+    /// it is not always possible to determine equality of two pointers, so this function may
+    /// spuriously return `false` for pointers that later actually turn out to be equal.
+    /// But when it returns `true`, the pointers are guaranteed to be equal.
+    ///
+    /// This function is the mirror of [`guaranteed_ne`], but not its inverse. There are pointer
+    /// comparisons for which both functions return `false`.
+    ///
+    /// [`guaranteed_ne`]: #method.guaranteed_ne
+    ///
+    /// The return value may change depending on the compiler version and unsafe code may not
+    /// rely on the result of this function for soundness. It is suggested to only use this
+    /// function for performance optimizations where spurious `false` return values by this
+    /// function do not affect the outcome, but just the performance.
+    #[unstable(feature = "const_raw_ptr_comparison", issue = "53020")]
+    #[rustc_const_unstable(feature = "const_raw_ptr_comparison")]
+    #[cfg(not(bootstrap))]
+    #[inline]
+    pub const fn guaranteed_eq(self, other: *mut T) -> bool where T: Sized {
+        // SAFETY: comparing two pointers has no preconditions.
+        unsafe { intrinsics::ptr_guaranteed_eq(self as *const _, other as *const _) }
+    }
+
+    /// Returns whether two pointers are guaranteed to be unequal.
+    ///
+    /// At runtime this function behaves like `self != other`.
+    /// However, in some contexts (e.g., compile-time evaluation),
+    /// it is not always possible to determine the inequality of two pointers, so this function may
+    /// spuriously return `false` for pointers that later actually turn out to be unequal.
+    /// But when it returns `true`, the pointers are guaranteed to be unequal.
+    ///
+    /// This function is the mirror of [`guaranteed_eq`], but not its inverse. There are pointer
+    /// comparisons for which both functions return `false`.
+    ///
+    /// [`guaranteed_eq`]: #method.guaranteed_eq
+    ///
+    /// The return value may change depending on the compiler version and unsafe code may not
+    /// rely on the result of this function for soundness. It is suggested to only use this
+    /// function for performance optimizations where spurious `false` return values by this
+    /// function do not affect the outcome, but just the performance.
+    #[unstable(feature = "const_raw_ptr_comparison", issue = "53020")]
+    #[rustc_const_unstable(feature = "const_raw_ptr_comparison")]
+    #[cfg(not(bootstrap))]
+    #[inline]
+    pub const fn guaranteed_ne(self, other: *mut T) -> bool where T: Sized {
+        // SAFETY: comparing two pointers has no preconditions.
+        unsafe { intrinsics::ptr_guaranteed_ne(self as *const _, other as *const _) }
+    }
+
     /// Calculates the distance between two pointers. The returned value is in
     /// units of T: the distance in bytes is divided by `mem::size_of::<T>()`.
     ///
@@ -26,6 +26,18 @@ declare_lint! {
     report_in_external_macro
 }
 
+declare_lint! {
+    pub LONG_RUNNING_CONST_EVAL,
+    Deny,
+    "constant evaluation that takes more steps than the const evaluation limit allows"
+}
+
+declare_lint! {
+    pub NONPORTABLE_CONST_FLOAT,
+    Warn,
+    "floating-point operations in constants whose result may differ from the one at runtime"
+}
+
 declare_lint! {
     pub UNUSED_IMPORTS,
     Warn,
@@ -466,6 +478,29 @@ declare_lint! {
     };
 }
 
+declare_lint! {
+    pub FAILING_PROMOTED_EXPRESSIONS,
+    Warn,
+    "detects expressions that are promoted to a constant but always fail to evaluate",
+    @future_incompatible = FutureIncompatibleInfo {
+        reference: "the promotion rules at \
+                    <https://github.com/rust-lang/const-eval/blob/master/promotion.md>",
+        edition: None,
+    };
+}
+
+declare_lint! {
+    pub POINTER_STRUCTURAL_MATCH,
+    Allow,
+    "constants containing function pointers or raw pointers used in patterns"
+}
+
+declare_lint! {
+    pub CONST_ITEM_MUTATION,
+    Warn,
+    "detects attempts to mutate a `const` item"
+}
+
 declare_lint_pass! {
     /// Does nothing as a lint pass, but registers some `Lint`s
     /// that are used by other parts of the compiler.
@@ -494,6 +529,8 @@ declare_lint_pass! {
         PUB_USE_OF_PRIVATE_EXTERN_CRATE,
         INVALID_TYPE_PARAM_DEFAULT,
         CONST_ERR,
+        LONG_RUNNING_CONST_EVAL,
+        NONPORTABLE_CONST_FLOAT,
         RENAMED_AND_REMOVED_LINTS,
         SAFE_PACKED_BORROWS,
         PATTERNS_IN_FNS_WITHOUT_BODY,
@@ -527,6 +564,9 @@ declare_lint_pass! {
         MUTABLE_BORROW_RESERVATION_CONFLICT,
         INDIRECT_STRUCTURAL_MATCH,
         SOFT_UNSTABLE,
+        FAILING_PROMOTED_EXPRESSIONS,
+        POINTER_STRUCTURAL_MATCH,
+        CONST_ITEM_MUTATION,
     ]
 }
 
@@ -14,6 +14,7 @@ use rustc_data_structures::sync::Once;
 pub fn update_limits(sess: &Session, krate: &ast::Crate) {
     update_limit(krate, &sess.recursion_limit, sym::recursion_limit, 128);
     update_limit(krate, &sess.type_length_limit, sym::type_length_limit, 1048576);
+    update_limit(krate, &sess.const_eval_limit, sym::const_eval_limit, 2_000_000);
 }
 
 fn update_limit(krate: &ast::Crate, limit: &Once<usize>, name: Symbol, default: usize) {
@@ -2,6 +2,8 @@ use super::{RawConst, Pointer, CheckInAllocMsg, ScalarMaybeUndef};
 
 use crate::hir;
 use crate::hir::map::definitions::DefPathData;
+use crate::lint::Lint;
+use crate::lint::builtin::CONST_ERR;
 use crate::mir;
 use crate::ty::{self, Ty, layout};
 use crate::ty::layout::{Size, Align, LayoutError};
@@ -104,11 +106,23 @@ impl<'tcx> ConstEvalErr<'tcx> {
         message: &str,
         lint_root: hir::HirId,
         span: Option<Span>,
+    ) -> ErrorHandled {
+        self.report_as_named_lint(tcx, CONST_ERR, message, lint_root, span)
+    }
+
+    /// Like `report_as_lint`, but reports the error as `lint` instead of `const_err`.
+    pub fn report_as_named_lint(
+        &self,
+        tcx: TyCtxtAt<'tcx>,
+        lint: &'static Lint,
+        message: &str,
+        lint_root: hir::HirId,
+        span: Option<Span>,
     ) -> ErrorHandled {
         let lint = self.struct_generic(
             tcx,
             message,
-            Some(lint_root),
+            Some((lint, lint_root)),
         );
         match lint {
             Ok(mut lint) => {
@@ -135,20 +149,29 @@ impl<'tcx> ConstEvalErr<'tcx> {
         &self,
         tcx: TyCtxtAt<'tcx>,
         message: &str,
-        lint_root: Option<hir::HirId>,
+        lint: Option<(&'static Lint, hir::HirId)>,
     ) -> Result<DiagnosticBuilder<'tcx>, ErrorHandled> {
         let must_error = match self.error {
             InterpError::MachineStop(_) => bug!("CTFE does not stop"),
             err_inval!(Layout(LayoutError::Unknown(_))) |
             err_inval!(TooGeneric) =>
                 return Err(ErrorHandled::TooGeneric),
-            err_inval!(TypeckError) =>
+            err_inval!(TypeckError) |
+            err_exhaust!(StepLimitReached) =>
                 return Err(ErrorHandled::Reported),
             err_inval!(Layout(LayoutError::SizeOverflow(_))) => true,
+            // Calling something that is not a function is always a hard error.
+            err_unsup!(InvalidFunctionPointer) => true,
             _ => false,
         };
         trace!("reporting const eval failure at {:?}", self.span);
-        let mut err = if let (Some(lint_root), false) = (lint_root, must_error) {
+        let (message, label) = match self.error {
+            _ if must_error => (self.error.to_string(), None),
+            // A panic carries the message the user wrote, which says more than `message` does.
+            err_panic!(Panic { .. }) => (self.error.to_string(), Some(message.to_string())),
+            _ => (message.to_string(), Some(self.error.to_string())),
+        };
+        let mut err = if let (Some((lint, lint_root)), false) = (lint, must_error) {
             let hir_id = self.stacktrace
                 .iter()
                 .rev()
@@ -156,25 +179,25 @@ impl<'tcx> ConstEvalErr<'tcx> {
                 .next()
                 .unwrap_or(lint_root);
             tcx.struct_span_lint_hir(
-                crate::rustc::lint::builtin::CONST_ERR,
+                lint,
                 hir_id,
                 tcx.span,
-                message,
+                &message,
             )
-        } else if must_error {
-            struct_error(tcx, &self.error.to_string())
         } else {
-            struct_error(tcx, message)
+            struct_error(tcx, &message)
         };
-        if !must_error {
-            err.span_label(self.span, self.error.to_string());
+        if let Some(label) = label {
+            err.span_label(self.span, label);
         }
+        // List the calls that led to the error, innermost first, so that errors deep inside
+        // nested `const fn` calls can be traced back to the constant.
         // Skip the last, which is just the environment of the constant.  The stacktrace
         // is sometimes empty because we create "fake" eval contexts in CTFE to do work
         // on constant values.
         if self.stacktrace.len() > 0 {
             for frame_info in &self.stacktrace[..self.stacktrace.len()-1] {
-                err.span_label(frame_info.call_site, frame_info.to_string());
+                err.note(&frame_info.to_string());
             }
         }
         Ok(err)
@@ -429,7 +452,6 @@ pub enum UnsupportedOpInfo<'tcx> {
     CalledClosureAsFunction,
     NoMirFor(String),
     DerefFunctionPointer,
-    ExecuteMemory,
     InvalidChar(u128),
     OutOfTls,
     TlsOutOfBounds,
@@ -503,7 +525,8 @@ impl fmt::Debug for UnsupportedOpInfo<'tcx> {
             DoubleFree =>
                 write!(f, "tried to deallocate dangling pointer"),
             InvalidFunctionPointer =>
-                write!(f, "tried to use a function pointer after offsetting it"),
+                write!(f, "tried to use a pointer that does not point to the start of a function \
+                    as a function pointer"),
             InvalidBool =>
                 write!(f, "invalid boolean value read"),
             InvalidNullPointerUsage =>
@@ -522,8 +545,6 @@ impl fmt::Debug for UnsupportedOpInfo<'tcx> {
                 write!(f, "tried to access a dead local variable"),
             DerefFunctionPointer =>
                 write!(f, "tried to dereference a function pointer"),
-            ExecuteMemory =>
-                write!(f, "tried to treat a memory pointer as a function pointer"),
             OutOfTls =>
                 write!(f, "reached the maximum number of representable TLS keys"),
             TlsOutOfBounds =>
@@ -574,6 +595,16 @@ pub enum ResourceExhaustionInfo {
     StackFrameLimitReached,
     /// The program ran into an infinite loop.
     InfiniteLoop,
+    /// The program ran for more steps than the const evaluation limit allows, and the
+    /// `long_running_const_eval` lint has already been reported as an error.
+    StepLimitReached,
+    /// An allocation would have made the memory used by the program exceed the const evaluation
+    /// memory limit.
+    MemoryLimitReached {
+        allocation_size: Size,
+        allocated: Size,
+        limit: Size,
+    },
 }
 
 impl fmt::Debug for ResourceExhaustionInfo {
@@ -585,6 +616,12 @@ impl fmt::Debug for ResourceExhaustionInfo {
             InfiniteLoop =>
                 write!(f, "duplicate interpreter state observed here, const evaluation will never \
                     terminate"),
+            StepLimitReached =>
+                write!(f, "exceeded the const evaluation step limit"),
+            MemoryLimitReached { allocation_size, allocated, limit } =>
+                write!(f, "tried to allocate {} bytes while {} bytes are in use, exceeding the \
+                    const evaluation memory limit of {} bytes",
+                    allocation_size.bytes(), allocated.bytes(), limit.bytes()),
         }
     }
 }
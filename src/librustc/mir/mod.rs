@@ -150,6 +150,9 @@ pub struct Body<'tcx> {
     /// to change the value of `x`: `let mut x = 42; false && { x = 55; true };`
     ///
     /// List of places where control flow was destroyed. Used for error reporting.
+    ///
+    /// This is always empty when `#![feature(const_if_match)]` or `#![feature(const_loop)]` is
+    /// enabled, since `&&` and `||` are then lowered to real control flow.
     pub control_flow_destroyed: Vec<(Span, String)>,
 
     /// A span representing this MIR, for error reporting.
@@ -480,6 +483,15 @@ impl From<Mutability> for hir::Mutability {
     }
 }
 
+impl From<hir::Mutability> for Mutability {
+    fn from(m: hir::Mutability) -> Self {
+        match m {
+            hir::Mutability::Mutable => Mutability::Mut,
+            hir::Mutability::Immutable => Mutability::Not,
+        }
+    }
+}
+
 #[derive(
     Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, RustcEncodable, RustcDecodable, HashStable,
 )]
@@ -2117,6 +2129,10 @@ pub enum Rvalue<'tcx> {
     /// &x or &mut x
     Ref(Region<'tcx>, BorrowKind, Place<'tcx>),
 
+    /// &raw const x or &raw mut x: a raw pointer to the place, created without going through a
+    /// reference.
+    AddressOf(Mutability, Place<'tcx>),
+
     /// length of a [X] or [X;n] value
     Len(Place<'tcx>),
 
@@ -2271,6 +2287,15 @@ impl<'tcx> Debug for Rvalue<'tcx> {
                 write!(fmt, "&{}{}{:?}", region, kind_str, place)
             }
 
+            AddressOf(mutability, ref place) => {
+                let kind_str = match mutability {
+                    Mutability::Mut => "mut",
+                    Mutability::Not => "const",
+                };
+
+                write!(fmt, "&raw {} {:?}", kind_str, place)
+            }
+
             Aggregate(ref kind, ref places) => {
                 fn fmt_tuple(fmt: &mut Formatter<'_>, places: &[Operand<'_>]) -> fmt::Result {
                     let mut tuple_fmt = fmt.debug_tuple("");
@@ -2776,6 +2801,10 @@ pub struct BorrowCheckResult<'tcx> {
 pub struct ConstQualifs {
     pub has_mut_interior: bool,
     pub needs_drop: bool,
+    pub needs_non_const_drop: bool,
+    pub custom_eq: bool,
+    pub contains_ptr: bool,
+    pub ref_to_static: bool,
 }
 
 /// After we borrow check a closure, we are left with various
@@ -3148,6 +3177,7 @@ impl<'tcx> TypeFoldable<'tcx> for Rvalue<'tcx> {
             Ref(region, bk, ref place) => {
                 Ref(region.fold_with(folder), bk, place.fold_with(folder))
             }
+            AddressOf(mutability, ref place) => AddressOf(mutability, place.fold_with(folder)),
             Len(ref place) => Len(place.fold_with(folder)),
             Cast(kind, ref op, ty) => Cast(kind, op.fold_with(folder), ty.fold_with(folder)),
             BinaryOp(op, ref rhs, ref lhs) => {
@@ -3188,6 +3218,7 @@ impl<'tcx> TypeFoldable<'tcx> for Rvalue<'tcx> {
             Use(ref op) => op.visit_with(visitor),
             Repeat(ref op, _) => op.visit_with(visitor),
             Ref(region, _, ref place) => region.visit_with(visitor) || place.visit_with(visitor),
+            AddressOf(_, ref place) => place.visit_with(visitor),
             Len(ref place) => place.visit_with(visitor),
             Cast(_, ref op, ty) => op.visit_with(visitor) || ty.visit_with(visitor),
             BinaryOp(_, ref rhs, ref lhs) | CheckedBinaryOp(_, ref rhs, ref lhs) => {
@@ -104,6 +104,13 @@ rustc_queries! {
             cache_on_disk_if { key.is_local() }
         }
 
+        /// Returns `true` if the body of the local, non-const `fn` `key` would pass const
+        /// checking if it were declared as a `const fn`. This checks a fresh copy of the body,
+        /// since the one from `mir_const` may already have been stolen.
+        query passes_as_const_fn(key: DefId) -> bool {
+            desc { |tcx| "checking whether `{}` could be a `const fn`", tcx.def_path_str(key) }
+        }
+
         /// Fetch the MIR for a given `DefId` right after it's built - this includes
         /// unreachable code.
         query mir_built(_: DefId) -> &'tcx Steal<mir::Body<'tcx>> {}
@@ -112,9 +119,12 @@ rustc_queries! {
         /// ready for const evaluation.
         ///
         /// See the README for the `mir` module for details.
-        query mir_const(_: DefId) -> &'tcx Steal<mir::Body<'tcx>> {
-            no_hash
-        }
+        ///
+        /// Unlike `mir_validated`, the result of this query is hashed. This is the
+        /// input to `mir_const_qualif`, so when it is recomputed but unchanged, the
+        /// cached qualifs can be reused instead of running const-checking dataflow
+        /// again.
+        query mir_const(_: DefId) -> &'tcx Steal<mir::Body<'tcx>> {}
 
         query mir_validated(_: DefId) ->
             (
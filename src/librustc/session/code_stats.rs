@@ -1,6 +1,7 @@
 use rustc_target::abi::{Align, Size};
 use rustc_data_structures::fx::{FxHashSet};
 use std::cmp::{self, Ordering};
+use std::time::Duration;
 use rustc_data_structures::sync::Lock;
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
@@ -45,9 +46,24 @@ pub struct TypeSizeInfo {
     pub variants: Vec<VariantInfo>,
 }
 
+/// How much work a generic dataflow analysis did to reach a fixpoint on a single body.
+#[derive(Debug)]
+pub struct DataflowStatsInfo {
+    /// The path of the item that owns the body.
+    pub body: String,
+    pub analysis: &'static str,
+    pub num_blocks: usize,
+    /// The number of times the fixpoint iteration started over from the beginning of the reverse
+    /// postorder, including the first.
+    pub num_passes: usize,
+    pub num_block_visits: usize,
+    pub time: Duration,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
+    dataflow_stats: Lock<Vec<DataflowStatsInfo>>,
 }
 
 impl CodeStats {
@@ -174,4 +190,25 @@ impl CodeStats {
             }
         }
     }
+
+    pub fn record_dataflow_stats(&self, info: DataflowStatsInfo) {
+        self.dataflow_stats.borrow_mut().push(info);
+    }
+
+    pub fn print_dataflow_stats(&self) {
+        let mut dataflow_stats = self.dataflow_stats.borrow_mut();
+
+        // Bodies are analyzed in query order, which is an implementation detail. Sort by body, then
+        // by analysis, so the output only changes when the work done does. This sort is stable, so
+        // an analysis that is run on the same body more than once keeps the order of its runs.
+        dataflow_stats.sort_by(|info1, info2| {
+            (&info1.body, info1.analysis).cmp(&(&info2.body, info2.analysis))
+        });
+
+        for info in dataflow_stats.iter() {
+            println!("dataflow-stats `{}` on `{}`: {} blocks, {} passes, {} block visits, {} us",
+                     info.analysis, info.body, info.num_blocks, info.num_passes,
+                     info.num_block_visits, info.time.as_micros());
+        }
+    }
 }
@@ -158,6 +158,42 @@ pub enum SymbolManglingVersion {
 
 impl_stable_hash_via_hash!(SymbolManglingVersion);
 
+/// The names of the const-checking operations that `-Zconst-check-level` accepts, and whether their
+/// errors may be downgraded to warnings. Only operations that miri can evaluate may be downgraded.
+///
+/// This must be kept in sync with the `NonConstOp`s in `rustc_mir::transform::check_consts::ops`.
+pub const CONST_CHECK_OPS: &[(&str, bool)] = &[
+    ("Downcast", true),
+    ("FloatingPointOp", true),
+    ("FnCallDynTrait", true),
+    ("FnCallIndirect", true),
+    ("FnCallNonConst", true),
+    ("FnCallOther", false),
+    ("FnCallTraitMethod", true),
+    ("FnCallUnstable", true),
+    ("ForLoopIterator", true),
+    ("HeapAllocation", false),
+    ("IfOrMatch", true),
+    ("InlineAsm", false),
+    ("LiveDrop", true),
+    ("Loop", true),
+    ("MutAddressOf", true),
+    ("MutBorrow", true),
+    ("MutDeref", true),
+    ("NonConstArgument", false),
+    ("NonCopyRepeat", true),
+    ("Panic", true),
+    ("RawPtrComparison", true),
+    ("RawPtrDeref", true),
+    ("RawPtrToIntCast", true),
+    ("RefToStatic", true),
+    ("SimdVector", true),
+    ("StaticAccess", true),
+    ("ThreadLocalAccess", false),
+    ("Transmute", true),
+    ("UnionAccess", true),
+];
+
 #[derive(Clone, Copy, PartialEq, Hash)]
 pub enum DebugInfo {
     None,
@@ -862,12 +898,15 @@ macro_rules! options {
             Some("one of: `disabled`, `trampolines`, or `aliases`");
         pub const parse_symbol_mangling_version: Option<&str> =
             Some("either `legacy` or `v0` (RFC 2603)");
+        pub const parse_const_check_level: Option<&str> =
+            Some("a comma-separated list of `op=level`, where `op` is a const-checking operation \
+                  and `level` is `deny`, or `warn` if miri can evaluate `op`");
     }
 
     #[allow(dead_code)]
     mod $mod_set {
         use super::{$struct_name, Passes, Sanitizer, LtoCli, LinkerPluginLto, SwitchWithOptPath,
-            SymbolManglingVersion};
+            SymbolManglingVersion, CONST_CHECK_OPS};
         use rustc_target::spec::{LinkerFlavor, MergeFunctions, PanicStrategy, RelroLevel};
         use std::path::PathBuf;
         use std::str::FromStr;
@@ -1101,6 +1140,34 @@ macro_rules! options {
             }
         }
 
+        fn parse_const_check_level(
+            slot: &mut Vec<(String, crate::lint::Level)>,
+            v: Option<&str>,
+        ) -> bool {
+            use crate::lint::Level;
+
+            let s = match v {
+                Some(s) => s,
+                None => return false,
+            };
+
+            for entry in s.split(',') {
+                let parts = entry.split('=').collect::<Vec<_>>();
+                if parts.len() != 2 || parts[0].is_empty() { return false; }
+                let can_warn = match CONST_CHECK_OPS.iter().find(|&&(op, _)| op == parts[0]) {
+                    Some(&(_, can_warn)) => can_warn,
+                    None => return false,
+                };
+                let level = match Level::from_str(parts[1]) {
+                    Some(level @ Level::Deny) => level,
+                    Some(level @ Level::Warn) if can_warn => level,
+                    _ => return false,
+                };
+                slot.push((parts[0].to_string(), level));
+            }
+            true
+        }
+
         fn parse_unpretty(slot: &mut Option<String>, v: Option<&str>) -> bool {
             match v {
                 None => false,
@@ -1305,6 +1372,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "verify LLVM IR"),
     borrowck_stats: bool = (false, parse_bool, [UNTRACKED],
         "gather borrowck statistics"),
+    dataflow_stats: bool = (false, parse_bool, [UNTRACKED],
+        "print the number of passes, block visits and time each generic dataflow analysis needs \
+        to reach a fixpoint on each body"),
     no_landing_pads: bool = (false, parse_bool, [TRACKED],
         "omit landing pads for unwinding"),
     fewer_names: bool = (false, parse_bool, [TRACKED],
@@ -1411,6 +1481,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "the directory the MIR is dumped into"),
     dump_mir_graphviz: bool = (false, parse_bool, [UNTRACKED],
         "in addition to `.mir` files, create graphviz `.dot` files"),
+    dump_mir_dataflow: bool = (false, parse_bool, [UNTRACKED],
+        "in addition to `.mir` files, create graphviz `.dot` files with the results of generic \
+        dataflow analyses whose name is matched by `-Z dump-mir`"),
     dump_mir_exclude_pass_number: bool = (false, parse_bool, [UNTRACKED],
         "if set, exclude the pass number when dumping MIR (used in tests)"),
     mir_emit_retag: bool = (false, parse_bool, [TRACKED],
@@ -1427,6 +1500,10 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "describes how to render the `rendered` field of json diagnostics"),
     unleash_the_miri_inside_of_you: bool = (false, parse_bool, [TRACKED],
         "take the breaks off const evaluation. NOTE: this is unsound"),
+    const_check_level: Vec<(String, lint::Level)> = (vec![], parse_const_check_level, [TRACKED],
+        "report the given const-checking operations (e.g. `MutBorrow=warn`) at the given level"),
+    const_eval_memory_limit: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "the maximum number of bytes that the memory of a single constant evaluation may use"),
     osx_rpath_install_name: bool = (false, parse_bool, [TRACKED],
         "pass `-install_name @rpath/...` to the macOS linker"),
     sanitizer: Option<Sanitizer> = (None, parse_sanitizer, [TRACKED],
@@ -15,7 +15,7 @@ use crate::traits::*;
 
 use std::borrow::Cow;
 
-use syntax::{source_map::Span, symbol::Symbol};
+use syntax::{source_map::Span, symbol::{sym, Symbol}};
 
 use super::{FunctionCx, LocalRef};
 use super::place::PlaceRef;
@@ -457,6 +457,22 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         cleanup: Option<mir::BasicBlock>,
     ) {
         let span = terminator.source_info.span;
+
+        // At runtime, `const_eval_select` is just a call to its runtime variant with the
+        // tupled arguments spread out.
+        let is_const_eval_select = match self.monomorphize(&func.ty(self.mir, bx.tcx())).kind {
+            ty::FnDef(def_id, _) => {
+                bx.tcx().fn_sig(def_id).abi() == Abi::RustIntrinsic &&
+                    bx.tcx().item_name(def_id) == sym::const_eval_select
+            }
+            _ => false,
+        };
+        let (func, args) = if is_const_eval_select {
+            (&args[2], &args[..1])
+        } else {
+            (func, &args[..])
+        };
+
         // Create the callee. This is a fn ptr or zero-sized and hence a kind of scalar.
         let callee = self.codegen_operand(&mut bx, func);
 
@@ -508,7 +524,11 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             return;
         }
 
-        let extra_args = &args[sig.inputs().len()..];
+        let extra_args: &[_] = if is_const_eval_select {
+            &[]
+        } else {
+            &args[sig.inputs().len()..]
+        };
         let extra_args = extra_args.iter().map(|op_arg| {
             let op_ty = op_arg.ty(self.mir, bx.tcx());
             self.monomorphize(&op_ty)
@@ -693,7 +713,9 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         }
 
         // Split the rust-call tupled arguments off.
-        let (first_args, untuple) = if abi == Abi::RustCall && !args.is_empty() {
+        let (first_args, untuple) = if (abi == Abi::RustCall || is_const_eval_select) &&
+            !args.is_empty()
+        {
             let (tup, args) = args.split_last().unwrap();
             (args, Some(tup))
         } else {
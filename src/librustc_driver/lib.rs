@@ -392,7 +392,15 @@ pub fn run_compiler(
                 mem::drop(queries.expansion()?.take());
             }
 
-            queries.global_ctxt()?.peek_mut().enter(|tcx| tcx.analysis(LOCAL_CRATE))?;
+            let result = queries.global_ctxt()?.peek_mut().enter(|tcx| tcx.analysis(LOCAL_CRATE));
+
+            // Dataflow analyses only run during analysis. Print their stats even if it failed,
+            // since that is often when they are most interesting.
+            if sess.dataflow_stats() {
+                sess.code_stats.print_dataflow_stats();
+            }
+
+            result?;
 
             if callbacks.after_analysis(compiler, queries) == Compilation::Stop {
                 return early_exit();
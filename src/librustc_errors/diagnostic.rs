@@ -22,6 +22,11 @@ pub struct Diagnostic {
     /// as a sort key to sort a buffer of diagnostics.  By default, it is the primary span of
     /// `span` if there is one.  Otherwise, it is `DUMMY_SP`.
     pub sort_span: Span,
+
+    /// Key-value pairs for tools consuming machine-readable output, which are not rendered for
+    /// humans. Keys are namespaced by the part of the compiler that sets them, e.g.
+    /// `const_checking.op`.
+    pub metadata: Vec<(String, String)>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, RustcEncodable, RustcDecodable)]
@@ -100,6 +105,7 @@ impl Diagnostic {
             children: vec![],
             suggestions: vec![],
             sort_span: DUMMY_SP,
+            metadata: vec![],
         }
     }
 
@@ -498,6 +504,15 @@ impl Diagnostic {
         self
     }
 
+    /// Sets the metadata entry `key` to `value`, replacing any previous value.
+    pub fn set_metadata(&mut self, key: &str, value: &str) -> &mut Self {
+        match self.metadata.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value.to_owned(),
+            None => self.metadata.push((key.to_owned(), value.to_owned())),
+        }
+        self
+    }
+
     pub fn get_code(&self) -> Option<DiagnosticId> {
         self.code.clone()
     }
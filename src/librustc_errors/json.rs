@@ -24,6 +24,7 @@ use std::vec;
 use std::sync::{Arc, Mutex};
 
 use rustc_serialize::json::{as_json, as_pretty_json};
+use rustc_serialize::{Encodable, Encoder};
 
 #[cfg(test)]
 mod tests;
@@ -130,11 +131,12 @@ impl Emitter for JsonEmitter {
 
 // The following data types are provided just for serialisation.
 
-#[derive(RustcEncodable)]
 struct Diagnostic {
     /// The primary error message.
     message: String,
     code: Option<DiagnosticCode>,
+    /// Machine-readable key-value pairs, see `Diagnostic::metadata`. Omitted when empty.
+    metadata: Vec<(String, String)>,
     /// "error: internal compiler error", "error", "warning", "note", "help".
     level: &'static str,
     spans: Vec<DiagnosticSpan>,
@@ -144,6 +146,33 @@ struct Diagnostic {
     rendered: Option<String>,
 }
 
+// Not derived, so that `metadata` can be left out of the output of most diagnostics, which have
+// none, and is emitted as an object rather than as a list of pairs.
+impl Encodable for Diagnostic {
+    fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        let skipped = if self.metadata.is_empty() { 1 } else { 0 };
+        s.emit_struct("Diagnostic", 7 - skipped, |s| {
+            s.emit_struct_field("message", 0, |s| self.message.encode(s))?;
+            s.emit_struct_field("code", 1, |s| self.code.encode(s))?;
+            if !self.metadata.is_empty() {
+                s.emit_struct_field("metadata", 2, |s| {
+                    s.emit_map(self.metadata.len(), |s| {
+                        for (i, (key, value)) in self.metadata.iter().enumerate() {
+                            s.emit_map_elt_key(i, |s| key.encode(s))?;
+                            s.emit_map_elt_val(i, |s| value.encode(s))?;
+                        }
+                        Ok(())
+                    })
+                })?;
+            }
+            s.emit_struct_field("level", 3 - skipped, |s| self.level.encode(s))?;
+            s.emit_struct_field("spans", 4 - skipped, |s| self.spans.encode(s))?;
+            s.emit_struct_field("children", 5 - skipped, |s| self.children.encode(s))?;
+            s.emit_struct_field("rendered", 6 - skipped, |s| self.rendered.encode(s))
+        })
+    }
+}
+
 #[derive(RustcEncodable)]
 struct DiagnosticSpan {
     file_name: String,
@@ -219,6 +248,7 @@ impl Diagnostic {
             Diagnostic {
                 message: sugg.msg.clone(),
                 code: None,
+                metadata: vec![],
                 level: "help",
                 spans: DiagnosticSpan::from_suggestion(sugg, je),
                 children: vec![],
@@ -251,6 +281,7 @@ impl Diagnostic {
         Diagnostic {
             message: diag.message(),
             code: DiagnosticCode::map_opt_string(diag.code.clone(), je),
+            metadata: diag.metadata.clone(),
             level: diag.level.to_str(),
             spans: DiagnosticSpan::from_multispan(&diag.span, je),
             children: diag.children.iter().map(|c| {
@@ -264,6 +295,7 @@ impl Diagnostic {
         Diagnostic {
             message: diag.message(),
             code: None,
+            metadata: vec![],
             level: diag.level.to_str(),
             spans: diag.render_span.as_ref()
                      .map(|sp| DiagnosticSpan::from_multispan(sp, je))
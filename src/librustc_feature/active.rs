@@ -52,6 +52,16 @@ macro_rules! declare_features {
             pub fn walk_feature_fields(&self, mut f: impl FnMut(&str, bool)) {
                 $(f(stringify!($feature), self.$feature);)+
             }
+
+            /// Returns `true` if the given active feature is enabled.
+            ///
+            /// Panics if `feature` is not an active feature.
+            pub fn enabled(&self, feature: Symbol) -> bool {
+                match feature {
+                    $( sym::$feature => self.$feature, )+
+                    _ => panic!("`{}` is not an active feature", feature),
+                }
+            }
         }
     };
 }
@@ -527,6 +537,31 @@ declare_features! (
     /// Allows the use of `#[cfg(sanitize = "option")]`; set when -Zsanitizer is used.
     (active, cfg_sanitize, "1.41.0", Some(39699), None),
 
+    /// Allows accessing `#[thread_local]` statics in the body of a `const fn`.
+    (active, const_fn_thread_local, "1.41.0", None, None),
+
+    /// Allows `const fn` in trait impls and calling their methods through `dyn Trait` in a const
+    /// context.
+    (active, const_trait_impl, "1.41.0", None, None),
+
+    /// Allows floating-point arithmetic and casts to and from floats in the body of a `const fn`.
+    (active, const_fn_floating_point_arithmetic, "1.41.0", None, None),
+
+    /// Allows constructing `#[repr(simd)]` types and accessing their fields in a const context.
+    (active, const_simd, "1.41.0", None, None),
+
+    /// Allows `loop`, `while` and `while let` in a const context.
+    (active, const_loop, "1.41.0", Some(52000), None),
+
+    /// Allows references to `static`s in the final value of a `const` and in a `const fn`.
+    (active, const_refs_to_static, "1.41.0", None, None),
+
+    /// Allows raising the number of steps a const evaluation may take with `#[const_eval_limit]`.
+    (active, const_eval_limit, "1.41.0", None, None),
+
+    /// Allows creating `*mut` pointers with `&raw mut` in a const context.
+    (active, const_raw_mut_ptr, "1.41.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
@@ -543,4 +578,5 @@ pub const INCOMPLETE_FEATURES: &[Symbol] = &[
     sym::let_chains,
     sym::raw_dylib,
     sym::track_caller,
+    sym::const_trait_impl,
 ];
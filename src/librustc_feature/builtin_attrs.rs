@@ -325,6 +325,10 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
         register_tool, CrateLevel, template!(List: "tool1, tool2, ..."),
         experimental!(register_tool),
     ),
+    gated!(
+        const_eval_limit, Whitelisted, template!(NameValueStr: "N"),
+        experimental!(const_eval_limit),
+    ),
 
     // ==========================================================================
     // Internal attributes: Stability, deprecation, and unsafe:
@@ -348,6 +352,11 @@ pub const BUILTIN_ATTRIBUTES: &[BuiltinAttribute] = &[
         rustc_const_unstable, Normal, template!(List: r#"feature = "name""#),
         "the `#[rustc_const_unstable]` attribute is an internal feature",
     ),
+    gated!(
+        default_method_body_is_const, Normal, template!(Word), const_trait_impl,
+        "`default_method_body_is_const` is a temporary placeholder for declaring default bodies \
+        as `const`, which may be removed or renamed in the future",
+    ),
     gated!(
         allow_internal_unstable, Normal, template!(Word, List: "feat1, feat2, ..."),
         "allow_internal_unstable side-steps feature gating and stability checks",
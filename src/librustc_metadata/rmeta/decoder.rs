@@ -1109,6 +1109,8 @@ impl<'a, 'tcx> CrateMetadata {
             EntryKind::AssocConst(AssocContainer::ImplFinal, qualif, _) => {
                 qualif
             }
+            EntryKind::Fn(data) => data.decode(self).qualifs,
+            EntryKind::Method(data) => data.decode(self).fn_data.qualifs,
             _ => bug!(),
         }
     }
@@ -845,10 +845,17 @@ impl EncodeContext<'tcx> {
                             self.encode_fn_param_names_for_body(body)
                         }
                     };
+                    // Only default bodies marked `#[default_method_body_is_const]` are `const`.
+                    let constness = if self.tcx.is_const_fn_raw(def_id) {
+                        hir::Constness::Const
+                    } else {
+                        hir::Constness::NotConst
+                    };
                     FnData {
                         asyncness: m_sig.header.asyncness,
-                        constness: hir::Constness::NotConst,
+                        constness,
                         param_names,
+                        qualifs: self.encode_fn_qualifs(def_id, constness),
                     }
                 } else {
                     bug!()
@@ -929,6 +936,7 @@ impl EncodeContext<'tcx> {
                         asyncness: sig.header.asyncness,
                         constness: sig.header.constness,
                         param_names: self.encode_fn_param_names_for_body(body),
+                        qualifs: self.encode_fn_qualifs(def_id, sig.header.constness),
                     }
                 } else {
                     bug!()
@@ -988,6 +996,13 @@ impl EncodeContext<'tcx> {
         })
     }
 
+    fn encode_fn_qualifs(&mut self, def_id: DefId, constness: hir::Constness) -> mir::ConstQualifs {
+        match constness {
+            hir::Constness::Const => self.tcx.mir_const_qualif(def_id),
+            hir::Constness::NotConst => Default::default(),
+        }
+    }
+
     fn encode_fn_param_names(&mut self, param_names: &[ast::Ident]) -> Lazy<[ast::Name]> {
         self.lazy(param_names.iter().map(|ident| ident.name))
     }
@@ -1059,6 +1074,7 @@ impl EncodeContext<'tcx> {
                     asyncness: sig.header.asyncness,
                     constness: sig.header.constness,
                     param_names: self.encode_fn_param_names_for_body(body),
+                    qualifs: self.encode_fn_qualifs(def_id, sig.header.constness),
                 };
 
                 EntryKind::Fn(self.lazy(data))
@@ -1527,6 +1543,7 @@ impl EncodeContext<'tcx> {
                     asyncness: hir::IsAsync::NotAsync,
                     constness: hir::Constness::NotConst,
                     param_names: self.encode_fn_param_names(names),
+                    qualifs: Default::default(),
                 };
                 EntryKind::ForeignFn(self.lazy(data))
             }
@@ -335,6 +335,8 @@ struct FnData {
     asyncness: hir::IsAsync,
     constness: hir::Constness,
     param_names: Lazy<[ast::Name]>,
+    /// The qualifs in the return place of a `const fn`, or the default for non-const functions.
+    qualifs: mir::ConstQualifs,
 }
 
 #[derive(RustcEncodable, RustcDecodable)]
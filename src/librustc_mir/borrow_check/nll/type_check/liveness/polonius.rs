@@ -74,7 +74,8 @@ impl Visitor<'tcx> for UseFactsExtractor<'_> {
                 }
             }
 
-            PlaceContext::MutatingUse(MutatingUseContext::Borrow) => {
+            PlaceContext::MutatingUse(MutatingUseContext::Borrow) |
+            PlaceContext::MutatingUse(MutatingUseContext::AddressOf) => {
                 if let Some(mpi) = self.place_to_mpi(place) {
                     self.insert_path_access(mpi, location);
                 }
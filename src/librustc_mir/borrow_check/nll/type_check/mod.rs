@@ -16,6 +16,7 @@ use crate::borrow_check::nll::type_check::free_region_relations::{
 };
 use crate::borrow_check::nll::universal_regions::{DefiningTy, UniversalRegions};
 use crate::borrow_check::nll::ToRegionVid;
+use crate::transform::check_consts::ConstKind;
 use crate::transform::promote_consts::should_suggest_const_in_array_repeat_expressions_attribute;
 use crate::dataflow::move_paths::MoveData;
 use crate::dataflow::FlowAtLocation;
@@ -1994,6 +1995,16 @@ impl<'a, 'tcx> TypeChecker<'a, 'tcx> {
                                 tcx, self.mir_def_id, body, operand);
                         debug!("check_rvalue: should_suggest={:?}", should_suggest);
 
+                        // In a `const` or `static`, const-checking reports a promotable element
+                        // as missing the feature gate instead (see `ops::NonCopyRepeat`).
+                        let is_const_context = match ConstKind::for_item(tcx, self.mir_def_id) {
+                            None | Some(ConstKind::ConstFn) => false,
+                            Some(_) => true,
+                        };
+                        if should_suggest && is_const_context {
+                            return;
+                        }
+
                         self.infcx.report_selection_error(
                             &traits::Obligation::new(
                                 ObligationCause::new(
@@ -2350,6 +2361,7 @@ impl<'a, 'tcx> TypeChecker<'a, 'tcx> {
             }
 
             Rvalue::Use(..)
+            | Rvalue::AddressOf(..)
             | Rvalue::Len(..)
             | Rvalue::BinaryOp(..)
             | Rvalue::CheckedBinaryOp(..)
@@ -2366,6 +2378,7 @@ impl<'a, 'tcx> TypeChecker<'a, 'tcx> {
             Rvalue::Use(_)
             | Rvalue::Repeat(..)
             | Rvalue::Ref(..)
+            | Rvalue::AddressOf(..)
             | Rvalue::Len(..)
             | Rvalue::Cast(..)
             | Rvalue::BinaryOp(..)
@@ -7,29 +7,33 @@ use std::hash::Hash;
 use std::collections::hash_map::Entry;
 use std::convert::TryInto;
 
-use rustc::hir::def::DefKind;
+use rustc::hir::{self, def::DefKind};
 use rustc::hir::def_id::DefId;
+use rustc::lint::{self, builtin::{
+    FAILING_PROMOTED_EXPRESSIONS, LONG_RUNNING_CONST_EVAL, NONPORTABLE_CONST_FLOAT,
+}};
 use rustc::middle::lang_items::PanicLocationLangItem;
 use rustc::mir::interpret::{ConstEvalErr, ErrorHandled, ScalarMaybeUndef};
 use rustc::mir;
-use rustc::ty::{self, Ty, TyCtxt, subst::Subst};
-use rustc::ty::layout::{self, LayoutOf, VariantIdx};
+use rustc::ty::{self, Ty, TyCtxt, TypeFoldable, subst::{InternalSubsts, Subst}};
+use rustc::ty::layout::{self, LayoutOf, Size, VariantIdx};
 use rustc::traits::Reveal;
-use rustc_data_structures::fx::FxHashMap;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use crate::interpret::eval_nullary_intrinsic;
 
-use syntax::{source_map::{Span, DUMMY_SP}, symbol::Symbol};
+use syntax::{attr, source_map::{Span, DUMMY_SP}, symbol::{sym, Symbol}};
 
 use crate::interpret::{self,
     PlaceTy, MPlaceTy, OpTy, ImmTy, Immediate, Scalar, Pointer,
     RawConst, ConstValue, Machine,
     InterpResult, InterpErrorInfo, GlobalId, InterpCx, StackPopCleanup,
-    Allocation, AllocId, MemoryKind, Memory,
+    Allocation, AllocId, MemoryKind, Memory, AllocCheck, GlobalAlloc,
     snapshot, RefTracking, intern_const_alloc_recursive,
 };
 
 /// Number of steps until the detector even starts doing anything.
-/// Also, a warning is shown to the user when this number is reached.
+/// This is below the default `const_eval_limit`, so that an evaluation that never terminates is
+/// usually reported as such instead of by the `long_running_const_eval` lint.
 const STEPS_UNTIL_DETECTOR_ENABLED: isize = 1_000_000;
 /// The number of steps between loop detector snapshots.
 /// Should be a power of two for performance reasons.
@@ -48,7 +52,11 @@ fn mk_eval_cx<'mir, 'tcx>(
     param_env: ty::ParamEnv<'tcx>,
 ) -> CompileTimeEvalContext<'mir, 'tcx> {
     debug!("mk_eval_cx: {:?}", param_env);
-    InterpCx::new(tcx.at(span), param_env, CompileTimeInterpreter::new(), Default::default())
+    let machine = CompileTimeInterpreter::new(
+        *tcx.sess.const_eval_limit.get(),
+        tcx.sess.opts.debugging_opts.const_eval_memory_limit,
+    );
+    InterpCx::new(tcx.at(span), param_env, machine, Default::default())
 }
 
 fn op_to_const<'tcx>(
@@ -63,7 +71,8 @@ fn op_to_const<'tcx>(
     // the usual cases of extracting e.g. a `usize`, without there being a real use case for the
     // `Undef` situation.
     let try_as_immediate = match op.layout.abi {
-        layout::Abi::Scalar(..) => true,
+        // Unions are never read as immediates, as their bytes may be partially undefined.
+        layout::Abi::Scalar(..) => op.layout.ty.ty_adt_def().map_or(true, |def| !def.is_union()),
         layout::Abi::ScalarPair(..) => match op.layout.ty.kind {
             ty::Ref(_, inner, _) => match inner.kind {
                 ty::Slice(elem) => elem == ecx.tcx.types.u8,
@@ -141,7 +150,7 @@ fn eval_body_using_ecx<'mir, 'tcx>(
     let tcx = ecx.tcx.tcx;
     let layout = ecx.layout_of(body.return_ty().subst(tcx, cid.instance.substs))?;
     assert!(!layout.is_unsized());
-    let ret = ecx.allocate(layout, MemoryKind::Stack);
+    let ret = ecx.allocate(layout, MemoryKind::Stack)?;
 
     let name = ty::tls::with(|tcx| tcx.def_path_str(cid.instance.def_id()));
     let prom = cid.promoted.map_or(String::new(), |p| format!("::promoted[{:?}]", p));
@@ -167,8 +176,17 @@ fn eval_body_using_ecx<'mir, 'tcx>(
     // The main interpreter loop.
     ecx.run()?;
 
-    // Intern the result
-    intern_const_alloc_recursive(ecx, tcx.static_mutability(cid.instance.def_id()), ret)?;
+    // Intern the result. The qualifs of the return place tell the interner whether the value
+    // may contain interior mutable data outside of a reference. Promoteds are not const-checked
+    // as items of their own, so we conservatively assume that they may.
+    let def_id = cid.instance.def_id();
+    let has_mut_interior = match (cid.promoted, tcx.def_kind(def_id)) {
+        (None, Some(DefKind::Const)) |
+        (None, Some(DefKind::AssocConst)) |
+        (None, Some(DefKind::Static)) => tcx.mir_const_qualif(def_id).has_mut_interior,
+        _ => true,
+    };
+    intern_const_alloc_recursive(ecx, tcx.static_mutability(def_id), has_mut_interior, ret)?;
 
     debug!("eval_body_using_ecx done: {:?}", *ret);
     Ok(ret)
@@ -177,6 +195,10 @@ fn eval_body_using_ecx<'mir, 'tcx>(
 #[derive(Clone, Debug)]
 pub enum ConstEvalError {
     NeedsRfc(String),
+    /// The evaluated code called an intrinsic that the const interpreter does not implement.
+    UnsupportedIntrinsic(Symbol),
+    /// The evaluated code called the `abort` intrinsic.
+    Aborted,
 }
 
 impl<'tcx> Into<InterpErrorInfo<'tcx>> for ConstEvalError {
@@ -196,15 +218,41 @@ impl fmt::Display for ConstEvalError {
                     msg
                 )
             }
+            UnsupportedIntrinsic(name) => {
+                write!(f, "calling intrinsic `{}` is not supported in constants", name)?;
+                if let Some(alternative) = const_alternative_for_intrinsic(name) {
+                    write!(f, "; use {} instead", alternative)?;
+                }
+                Ok(())
+            }
+            Aborted => write!(f, "evaluation of constant value aborted"),
         }
     }
 }
 
+/// Returns a const-compatible way to express what an intrinsic that the const interpreter does
+/// not implement does, if there is one.
+fn const_alternative_for_intrinsic(name: Symbol) -> Option<&'static str> {
+    Some(match &*name.as_str() {
+        "unchecked_add" => "`wrapping_add` or `checked_add`",
+        "unchecked_sub" => "`wrapping_sub` or `checked_sub`",
+        "unchecked_mul" => "`wrapping_mul` or `checked_mul`",
+        "unchecked_div" | "exact_div" => "the `/` operator",
+        "unchecked_rem" => "the `%` operator",
+        "size_of_val" => "`size_of` on a sized type",
+        "min_align_of_val" => "`min_align_of` on a sized type",
+        "likely" | "unlikely" => "the condition directly",
+        _ => return None,
+    })
+}
+
 impl Error for ConstEvalError {
     fn description(&self) -> &str {
         use self::ConstEvalError::*;
         match *self {
             NeedsRfc(_) => "this feature needs an rfc before being allowed inside constants",
+            UnsupportedIntrinsic(_) => "calling an intrinsic that is not supported in constants",
+            Aborted => "evaluation of constant value aborted",
         }
     }
 
@@ -223,13 +271,29 @@ pub struct CompileTimeInterpreter<'mir, 'tcx> {
 
     /// Extra state to detect loops.
     pub(super) loop_detector: snapshot::InfiniteLoopDetector<'mir, 'tcx>,
+
+    /// The number of interpreter steps taken so far. Every terminator counts as one step.
+    pub(super) steps: usize,
+
+    /// The number of steps after which the `long_running_const_eval` lint is reported.
+    pub(super) step_limit: usize,
+
+    /// The number of bytes in the locals and return places that are currently allocated.
+    pub(super) allocated: Size,
+
+    /// The number of bytes that `allocated` may not exceed, set by `-Z const-eval-memory-limit`.
+    pub(super) memory_limit: Option<Size>,
 }
 
 impl<'mir, 'tcx> CompileTimeInterpreter<'mir, 'tcx> {
-    fn new() -> Self {
+    fn new(step_limit: usize, memory_limit: Option<usize>) -> Self {
         CompileTimeInterpreter {
             loop_detector: Default::default(),
             steps_since_detector_enabled: -STEPS_UNTIL_DETECTOR_ENABLED,
+            steps: 0,
+            step_limit,
+            allocated: Size::ZERO,
+            memory_limit: memory_limit.map(|limit| Size::from_bytes(limit as u64)),
         }
     }
 }
@@ -298,6 +362,39 @@ impl<K: Hash + Eq, V> interpret::AllocMap<K, V> for FxHashMap<K, V> {
 crate type CompileTimeEvalContext<'mir, 'tcx> =
     InterpCx<'mir, 'tcx, CompileTimeInterpreter<'mir, 'tcx>>;
 
+/// Returns `true` if `a` and `b` are guaranteed to compare equal at runtime.
+///
+/// A return value of `false` means that the result of the comparison is not known at compile-time.
+fn guaranteed_eq(a: Scalar, b: Scalar) -> bool {
+    match (a, b) {
+        // Comparisons between integers are always known.
+        (Scalar::Raw { .. }, Scalar::Raw { .. }) => a == b,
+        // Equality with integers can never be known for sure.
+        (Scalar::Raw { .. }, Scalar::Ptr(_)) | (Scalar::Ptr(_), Scalar::Raw { .. }) => false,
+        // FIXME: return `true` when both sides are the same pointer, *except* that some things
+        // (like functions and vtables) do not have stable addresses, so we need to be careful
+        // around them.
+        (Scalar::Ptr(_), Scalar::Ptr(_)) => false,
+    }
+}
+
+/// Returns `true` if `a` and `b` are guaranteed to compare unequal at runtime.
+///
+/// A return value of `false` means that the result of the comparison is not known at compile-time.
+fn guaranteed_ne(a: Scalar, b: Scalar) -> bool {
+    match (a, b) {
+        // Comparisons between integers are always known.
+        (Scalar::Raw { .. }, Scalar::Raw { .. }) => a != b,
+        // Inequality with integers can never be known for sure, since we don't know the address
+        // of an allocation.
+        (Scalar::Raw { .. }, Scalar::Ptr(_)) | (Scalar::Ptr(_), Scalar::Raw { .. }) => false,
+        // FIXME: return `true` for at least some comparisons where we can reliably determine the
+        // result of runtime inequality tests at compile-time, e.g. pointers into two distinct
+        // `static` items.
+        (Scalar::Ptr(_), Scalar::Ptr(_)) => false,
+    }
+}
+
 impl interpret::MayLeak for ! {
     #[inline(always)]
     fn may_leak(self) -> bool {
@@ -388,11 +485,64 @@ impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter<'mir,
         if ecx.emulate_intrinsic(span, instance, args, ret)? {
             return Ok(());
         }
-        // An intrinsic that we do not support
         let intrinsic_name = ecx.tcx.item_name(instance.def_id());
-        Err(
-            ConstEvalError::NeedsRfc(format!("calling intrinsic `{}`", intrinsic_name)).into()
-        )
+
+        // `abort` diverges, so it is the only intrinsic we support without a return place.
+        if intrinsic_name == sym::abort {
+            return Err(ConstEvalError::Aborted.into());
+        }
+
+        // CTFE-specific intrinsics. These are not shared with miri, which knows the actual
+        // addresses of allocations and can compute the pointer comparisons precisely, and which
+        // implements the memory intrinsics itself to track its own aliasing information.
+        if let Some((dest, ret)) = ret {
+            match &*intrinsic_name.as_str() {
+                "ptr_guaranteed_eq" | "ptr_guaranteed_ne" => {
+                    let a = ecx.read_immediate(args[0])?.to_scalar()?;
+                    let b = ecx.read_immediate(args[1])?.to_scalar()?;
+                    let cmp = if intrinsic_name.as_str() == "ptr_guaranteed_eq" {
+                        guaranteed_eq(a, b)
+                    } else {
+                        guaranteed_ne(a, b)
+                    };
+                    ecx.write_scalar(Scalar::from_bool(cmp), dest)?;
+                    ecx.go_to_block(ret);
+                    return Ok(());
+                }
+                "copy" | "copy_nonoverlapping" => {
+                    let nonoverlapping = intrinsic_name.as_str() == "copy_nonoverlapping";
+                    ecx.copy_intrinsic(args[0], args[1], args[2], nonoverlapping)?;
+                    ecx.go_to_block(ret);
+                    return Ok(());
+                }
+                "write_bytes" => {
+                    ecx.write_bytes_intrinsic(args[0], args[1], args[2])?;
+                    ecx.go_to_block(ret);
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+
+        // An intrinsic that we do not support. These are usually called from wrappers in the
+        // standard library, so point the error at the call that led there from the evaluated crate.
+        ecx.tcx.span = local_call_span(ecx, span);
+        Err(ConstEvalError::UnsupportedIntrinsic(intrinsic_name).into())
+    }
+
+    #[inline(always)]
+    fn const_eval_select_compile_time(
+        _ecx: &InterpCx<'mir, 'tcx, Self>,
+    ) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn keep_ptr_in_int_cast(
+        ecx: &InterpCx<'mir, 'tcx, Self>,
+    ) -> bool {
+        // This is experimental, so we only keep pointers symbolic for unleashed evaluation.
+        ecx.tcx.sess.opts.debugging_opts.unleash_the_miri_inside_of_you
     }
 
     fn ptr_to_int(
@@ -441,6 +591,18 @@ impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter<'mir,
         ()
     }
 
+    fn before_access_thread_local_static(
+        tcx: TyCtxt<'tcx>,
+        def_id: DefId,
+    ) -> InterpResult<'tcx> {
+        // Every thread has its own copy of a thread-local static, so there is no single
+        // allocation that could be referred to at compile-time.
+        throw_unsup_format!(
+            "cannot access thread-local static `{}` at compile-time",
+            tcx.def_path_str(def_id),
+        )
+    }
+
     fn box_alloc(
         _ecx: &mut InterpCx<'mir, 'tcx, Self>,
         _dest: PlaceTy<'tcx>,
@@ -450,7 +612,33 @@ impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter<'mir,
         )
     }
 
+    fn nan_produced(ecx: &InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
+        report_nonportable_nan(ecx);
+        Ok(())
+    }
+
+    fn before_allocation(ecx: &mut InterpCx<'mir, 'tcx, Self>, size: Size) -> InterpResult<'tcx> {
+        let allocated = ecx.machine.allocated;
+        if let Some(limit) = ecx.machine.memory_limit {
+            if limit.bytes().saturating_sub(allocated.bytes()) < size.bytes() {
+                throw_exhaust!(MemoryLimitReached { allocation_size: size, allocated, limit })
+            }
+        }
+        ecx.machine.allocated = Size::from_bytes(allocated.bytes().saturating_add(size.bytes()));
+        Ok(())
+    }
+
+    fn after_deallocation(ecx: &mut InterpCx<'mir, 'tcx, Self>, size: Size) {
+        let allocated = ecx.machine.allocated.bytes();
+        ecx.machine.allocated = Size::from_bytes(allocated.saturating_sub(size.bytes()));
+    }
+
     fn before_terminator(ecx: &mut InterpCx<'mir, 'tcx, Self>) -> InterpResult<'tcx> {
+        ecx.machine.steps += 1;
+        if ecx.machine.steps == ecx.machine.step_limit {
+            report_long_running(ecx)?;
+        }
+
         {
             let steps = &mut ecx.machine.steps_since_detector_enabled;
 
@@ -465,10 +653,8 @@ impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter<'mir,
             }
         }
 
-        let span = ecx.frame().span;
         ecx.machine.loop_detector.observe_and_analyze(
             *ecx.tcx,
-            span,
             &ecx.memory,
             &ecx.stack[..],
         )
@@ -480,6 +666,79 @@ impl<'mir, 'tcx> interpret::Machine<'mir, 'tcx> for CompileTimeInterpreter<'mir,
     }
 }
 
+/// Reports the `long_running_const_eval` lint for the item being evaluated, which is where the
+/// limit can be raised. If the lint is reported as an error, evaluation is stopped; otherwise, it
+/// continues until it finishes or the loop detector finds that it will never terminate.
+fn report_long_running<'mir, 'tcx>(
+    ecx: &InterpCx<'mir, 'tcx, CompileTimeInterpreter<'mir, 'tcx>>,
+) -> InterpResult<'tcx> {
+    let tcx = *ecx.tcx;
+    let root = ecx.generate_stacktrace(None).pop().expect("evaluation without a stack frame");
+    let lint_root = root.lint_root.unwrap_or(hir::CRATE_HIR_ID);
+
+    let (level, _) = tcx.lint_level_at_node(LONG_RUNNING_CONST_EVAL, lint_root);
+    tcx.struct_span_lint_hir(
+        LONG_RUNNING_CONST_EVAL,
+        lint_root,
+        root.call_site,
+        "constant evaluation is taking a long time",
+    )
+    .note(&format!("the evaluation has taken {} steps", ecx.machine.step_limit))
+    .help("if the evaluation is expected to take this long, raise the limit with \
+        `#[const_eval_limit = \"...\"]` on the item or the crate")
+    .emit();
+
+    if level >= lint::Level::Deny {
+        throw_exhaust!(StepLimitReached)
+    }
+    Ok(())
+}
+
+/// Reports the `nonportable_const_float` lint at the operation that produced a NaN. If that
+/// operation is in a function from another crate, the lint is reported for the item being
+/// evaluated instead.
+fn report_nonportable_nan<'mir, 'tcx>(
+    ecx: &InterpCx<'mir, 'tcx, CompileTimeInterpreter<'mir, 'tcx>>,
+) {
+    let tcx = *ecx.tcx;
+    let mut frames = ecx.generate_stacktrace(None);
+    let (lint_root, span) = match frames.first().and_then(|frame| frame.lint_root) {
+        Some(lint_root) => (lint_root, ecx.tcx.span),
+        None => {
+            let root = frames.pop().expect("evaluation without a stack frame");
+            (root.lint_root.unwrap_or(hir::CRATE_HIR_ID), root.call_site)
+        }
+    };
+
+    tcx.struct_span_lint_hir(
+        NONPORTABLE_CONST_FLOAT,
+        lint_root,
+        span,
+        "floating-point operation produces NaN during constant evaluation",
+    )
+    .note("constant evaluation always produces a positive NaN without payload, but the NaN \
+        computed at runtime may have a different sign or payload, depending on the target")
+    .emit();
+}
+
+/// Returns the step limit for evaluating the body of `def_id`, which may be raised by a
+/// `#[const_eval_limit]` attribute on the item itself.
+fn const_eval_limit(tcx: TyCtxt<'_>, def_id: DefId) -> usize {
+    let attrs = tcx.get_attrs(def_id);
+    let attr = match attr::find_by_name(&attrs, sym::const_eval_limit) {
+        Some(attr) => attr,
+        None => return *tcx.sess.const_eval_limit.get(),
+    };
+
+    match attr.value_str().and_then(|limit| limit.as_str().parse().ok()) {
+        Some(limit) => limit,
+        None => {
+            tcx.sess.span_err(attr.span, "`#[const_eval_limit]` must be a non-negative integer");
+            *tcx.sess.const_eval_limit.get()
+        }
+    }
+}
+
 /// Extracts a field of a (variant of a) const.
 // this function uses `unwrap` copiously, because an already validated constant must have valid
 // fields and can thus never fail outside of compiler bugs
@@ -519,7 +778,7 @@ pub fn const_caller_location<'tcx>(
             .subst(tcx, tcx.mk_substs([tcx.lifetimes.re_static.into()].iter())),
     );
     let loc_place = ecx.alloc_caller_location(file, line, col).unwrap();
-    intern_const_alloc_recursive(&mut ecx, None, loc_place).unwrap();
+    intern_const_alloc_recursive(&mut ecx, None, false, loc_place).unwrap();
     let loc_const = ty::Const {
         ty: loc_ty,
         val: ty::ConstKind::Value(ConstValue::Scalar(loc_place.ptr.into())),
@@ -544,6 +803,16 @@ pub fn const_variant_index<'tcx>(
 /// Turn an interpreter error into something to report to the user.
 /// As a side-effect, if RUSTC_CTFE_BACKTRACE is set, this prints the backtrace.
 /// Should be called only if the error is actually going to to be reported!
+/// Returns the span of the call through which the innermost frame of the local crate led to the
+/// current frame, or `span` if the current frame belongs to the local crate or no frame does.
+fn local_call_span(ecx: &CompileTimeEvalContext<'_, '_>, span: Span) -> Span {
+    let stack = ecx.stack();
+    match stack.iter().rposition(|frame| frame.instance.def_id().is_local()) {
+        Some(idx) if idx + 1 < stack.len() => stack[idx + 1].span,
+        _ => span,
+    }
+}
+
 pub fn error_to_const_error<'mir, 'tcx, M: Machine<'mir, 'tcx>>(
     ecx: &InterpCx<'mir, 'tcx, M>,
     mut error: InterpErrorInfo<'tcx>,
@@ -559,6 +828,62 @@ pub fn note_on_undefined_behavior_error() -> &'static str {
      repository if you believe it should not be considered undefined behavior."
 }
 
+/// Checks that every pointer stored in the memory reachable from `root` points into a live
+/// allocation, or one past its end.
+///
+/// Type-based validation only sees the pointers that it can reach at their type, but codegen emits
+/// all of them as inbounds offsets into their allocation, including those inside unions and behind
+/// raw pointers.
+fn validate_relocations<'mir, 'tcx>(
+    ecx: &CompileTimeEvalContext<'mir, 'tcx>,
+    root: AllocId,
+) -> InterpResult<'tcx> {
+    let mut seen = FxHashSet::default();
+    seen.insert(root);
+    let mut todo = vec![root];
+    while let Some(alloc_id) = todo.pop() {
+        // Can't do this in the match argument, as the lock would be held throughout the match.
+        let global_alloc = ecx.tcx.alloc_map.lock().get(alloc_id);
+        let alloc = match global_alloc {
+            Some(GlobalAlloc::Memory(alloc)) => alloc,
+            // Statics are validated on their own, and functions do not contain pointers.
+            _ => continue,
+        };
+        let location = if alloc_id == root {
+            "in the final value"
+        } else {
+            "in memory referenced by the final value"
+        };
+        for &(offset, ((), target)) in alloc.relocations().iter() {
+            let ptr = alloc.read_ptr_sized(ecx, Pointer::new(alloc_id, offset))?
+                .not_undef()?
+                .to_ptr()?;
+            let size = match ecx.memory.get_size_and_align(target, AllocCheck::Live) {
+                Ok((size, _align)) => size,
+                Err(_) => throw_unsup!(ValidationFailure(format!(
+                    "encountered a dangling pointer at byte {} {}",
+                    offset.bytes(),
+                    location,
+                ))),
+            };
+            if ptr.offset > size {
+                throw_unsup!(ValidationFailure(format!(
+                    "encountered a pointer at byte {} {} that is out-of-bounds of its \
+                     allocation (offset {}, but the allocation has size {})",
+                    offset.bytes(),
+                    location,
+                    ptr.offset.bytes(),
+                    size.bytes(),
+                )))
+            }
+            if seen.insert(target) {
+                todo.push(target);
+            }
+        }
+    }
+    Ok(())
+}
+
 fn validate_and_turn_into_const<'tcx>(
     tcx: TyCtxt<'tcx>,
     constant: RawConst<'tcx>,
@@ -576,6 +901,9 @@ fn validate_and_turn_into_const<'tcx>(
                 Some(&mut ref_tracking),
             )?;
         }
+        if let Scalar::Ptr(ptr) = mplace.ptr {
+            validate_relocations(&ecx, ptr.alloc_id)?;
+        }
         // Now that we validated, turn this into a proper constant.
         // Statics/promoteds are always `ByRef`, for the rest `op_to_const` decides
         // whether they become immediates.
@@ -607,10 +935,40 @@ fn validate_and_turn_into_const<'tcx>(
     })
 }
 
+/// Promoteds whose MIR does not depend on the generic parameters of the surrounding item evaluate
+/// to the same value in every instance of that item. Returns the key under which such a promoted
+/// is evaluated once for all instances, or `None` if the promoted is generic or `key` already is
+/// that key.
+fn shared_promoted_key<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
+) -> Option<ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>> {
+    let promoted = key.value.promoted?;
+    let def_id = match key.value.instance.def {
+        ty::InstanceDef::Item(def_id) => def_id,
+        _ => return None,
+    };
+    let substs = InternalSubsts::identity_for_item(tcx, def_id);
+    let param_env = ty::ParamEnv::new(ty::List::empty(), key.param_env.reveal, None);
+    if key.value.instance.substs == substs && key.param_env == param_env {
+        return None;
+    }
+    if tcx.promoted_mir(def_id)[promoted].needs_subst() {
+        return None;
+    }
+    let instance = ty::Instance::new(def_id, substs);
+    Some(param_env.and(GlobalId { instance, promoted: Some(promoted) }))
+}
+
 pub fn const_eval_provider<'tcx>(
     tcx: TyCtxt<'tcx>,
     key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
 ) -> ::rustc::mir::interpret::ConstEvalResult<'tcx> {
+    // Evaluate promoteds that do not depend on generics only once for all instances.
+    if let Some(key) = shared_promoted_key(tcx, key) {
+        return tcx.const_eval(key);
+    }
+
     // see comment in const_eval_raw_provider for what we're doing here
     if key.param_env.reveal == Reveal::All {
         let mut key = key.clone();
@@ -652,6 +1010,11 @@ pub fn const_eval_raw_provider<'tcx>(
     tcx: TyCtxt<'tcx>,
     key: ty::ParamEnvAnd<'tcx, GlobalId<'tcx>>,
 ) -> ::rustc::mir::interpret::ConstEvalRawResult<'tcx> {
+    // See `const_eval_provider`.
+    if let Some(key) = shared_promoted_key(tcx, key) {
+        return tcx.const_eval_raw(key);
+    }
+
     // Because the constant is computed twice (once per value of `Reveal`), we are at risk of
     // reporting the same error twice here. To resolve this, we check whether we can evaluate the
     // constant in the more restrictive `Reveal::UserFacing`, which most likely already was
@@ -691,7 +1054,10 @@ pub fn const_eval_raw_provider<'tcx>(
     let mut ecx = InterpCx::new(
         tcx.at(span),
         key.param_env,
-        CompileTimeInterpreter::new(),
+        CompileTimeInterpreter::new(
+            const_eval_limit(tcx, def_id),
+            tcx.sess.opts.debugging_opts.const_eval_memory_limit,
+        ),
         Default::default()
     );
 
@@ -734,7 +1100,8 @@ pub fn const_eval_raw_provider<'tcx>(
                     )
                 },
                 // promoting runtime code is only allowed to error if it references broken constants
-                // any other kind of error will be reported to the user as a deny-by-default lint
+                // any other kind of error will be reported to the user as a future-compatibility
+                // lint, as such expressions should eventually not be promoted at all
                 _ => if let Some(p) = cid.promoted {
                     let span = tcx.promoted_mir(def_id)[p].span;
                     if let err_inval!(ReferencedConstant) = err.error {
@@ -743,8 +1110,9 @@ pub fn const_eval_raw_provider<'tcx>(
                             "evaluation of constant expression failed",
                         )
                     } else {
-                        err.report_as_lint(
+                        err.report_as_named_lint(
                             tcx.at(span),
+                            FAILING_PROMOTED_EXPRESSIONS,
                             "reaching this expression at runtime will panic or abort",
                             tcx.hir().as_local_hir_id(def_id).unwrap(),
                             Some(err.span),
@@ -0,0 +1,104 @@
+//! Computes the set of `Drop` terminators whose unwind edges can never be taken.
+
+use rustc::hir::def_id::DefId;
+use rustc::mir::{BasicBlock, Body, Location, TerminatorKind};
+use rustc::ty::{self, TyCtxt};
+use rustc_index::bit_set::BitSet;
+
+use super::move_paths::{HasMoveData, LookupResult, MoveData};
+use super::{do_dataflow, DebugFormatted, MaybeInitializedPlaces, MoveDataParamEnv};
+use super::{drop_flag_effects_for_location, on_all_drop_children_bits};
+use crate::util::elaborate_drops::DropFlagState;
+
+/// Returns the set of basic blocks whose unwind edges are known
+/// to not be reachable, because they are `drop` terminators
+/// that can't drop anything.
+pub(crate) fn find_dead_unwinds<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    def_id: DefId,
+    env: &MoveDataParamEnv<'tcx>,
+) -> BitSet<BasicBlock> {
+    debug!("find_dead_unwinds({:?})", body.span);
+    // We only need to do this pass once, because unwind edges can only
+    // reach cleanup blocks, which can't have unwind edges themselves.
+    let mut dead_unwinds = BitSet::new_empty(body.basic_blocks().len());
+    let flow_inits =
+        do_dataflow(tcx, body, def_id, &[], &dead_unwinds,
+                    MaybeInitializedPlaces::new(tcx, body, &env),
+                    |bd, p| DebugFormatted::new(&bd.move_data().move_paths[p]));
+    for (bb, bb_data) in body.basic_blocks().iter_enumerated() {
+        let location = match bb_data.terminator().kind {
+            TerminatorKind::Drop { ref location, unwind: Some(_), .. } |
+            TerminatorKind::DropAndReplace { ref location, unwind: Some(_), .. } => location,
+            _ => continue,
+        };
+
+        let mut maybe_init = flow_inits.sets().entry_set_for(bb.index()).to_owned();
+        debug!("find_dead_unwinds @ {:?}: {:?}; maybe_init={:?}",
+               bb, bb_data, maybe_init);
+        for stmt in 0..bb_data.statements.len() {
+            let loc = Location { block: bb, statement_index: stmt };
+            drop_flag_effects_for_location(tcx, body, env, loc, |path, df| {
+                match df {
+                    DropFlagState::Present => { maybe_init.insert(path); }
+                    DropFlagState::Absent => { maybe_init.remove(path); }
+                }
+            });
+        }
+
+        let path = match env.move_data.rev_lookup.find(location.as_ref()) {
+            LookupResult::Exact(e) => e,
+            LookupResult::Parent(..) => {
+                debug!("find_dead_unwinds: has parent; skipping");
+                continue
+            }
+        };
+
+        debug!("find_dead_unwinds @ {:?}: path({:?})={:?}", bb, location, path);
+
+        let mut maybe_live = false;
+        on_all_drop_children_bits(tcx, body, &env, path, |child| {
+            maybe_live |= maybe_init.contains(child);
+        });
+
+        debug!("find_dead_unwinds @ {:?}: maybe_live={}", bb, maybe_live);
+        if !maybe_live {
+            dead_unwinds.insert(bb);
+        }
+    }
+
+    dead_unwinds
+}
+
+/// Like `find_dead_unwinds`, but gathers the move data for `body` itself.
+///
+/// This is meant for analyses that run before borrowck, when `body` may still contain illegal
+/// moves. If the move data cannot be built, or if `body` has no `Drop` terminators with an unwind
+/// edge, every unwind edge is treated as live.
+pub(crate) fn dead_unwinds<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    def_id: DefId,
+    param_env: ty::ParamEnv<'tcx>,
+) -> BitSet<BasicBlock> {
+    let has_drop_with_unwind = body.basic_blocks().iter().any(|bb_data| {
+        match bb_data.terminator().kind {
+            TerminatorKind::Drop { unwind: Some(_), .. } |
+            TerminatorKind::DropAndReplace { unwind: Some(_), .. } => true,
+            _ => false,
+        }
+    });
+
+    if !has_drop_with_unwind {
+        return BitSet::new_empty(body.basic_blocks().len());
+    }
+
+    let move_data = match MoveData::gather_moves(body, tcx) {
+        Ok(move_data) => move_data,
+        Err(_) => return BitSet::new_empty(body.basic_blocks().len()),
+    };
+
+    let env = MoveDataParamEnv { move_data, param_env };
+    find_dead_unwinds(tcx, body, def_id, &env)
+}
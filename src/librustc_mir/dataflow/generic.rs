@@ -17,31 +17,41 @@
 //! [#64566]: https://github.com/rust-lang/rust/pull/64566
 
 use std::borrow::Borrow;
-use std::cmp::Ordering;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 use std::{fs, io, ops};
 
 use rustc::hir::def_id::DefId;
 use rustc::mir::{self, traversal, BasicBlock, Location};
+use rustc::session::DataflowStatsInfo;
 use rustc::ty::{self, TyCtxt};
-use rustc_data_structures::work_queue::WorkQueue;
-use rustc_index::bit_set::BitSet;
+use rustc_index::bit_set::{BitSet, HybridBitSet};
 use rustc_index::vec::{Idx, IndexVec};
 use syntax::symbol::sym;
 
-use crate::dataflow::BottomValue;
+use crate::dataflow::GenKillSet;
+use crate::transform::MirSource;
+use crate::util;
 
 mod graphviz;
+mod lattice;
+mod visitor;
+
+pub use self::lattice::{DebugState, JoinSemiLattice};
+pub use self::visitor::{visit_results, ResultsVisitable, ResultsVisitor};
 
 /// A specific kind of dataflow analysis.
 ///
 /// To run a dataflow analysis, one must set the initial state of the `START_BLOCK` via
 /// `initialize_start_block` and define a transfer function for each statement or terminator via
 /// the various `effect` methods. The entry set for all other basic blocks is initialized to
-/// `Self::BOTTOM_VALUE`. The dataflow `Engine` then iteratively updates the various entry sets for
+/// `bottom_value`. The dataflow `Engine` then iteratively updates the various entry sets for
 /// each block with the cumulative effects of the transfer functions of all preceding blocks.
 ///
+/// The state of an analysis can be any `Domain` that forms a join-semilattice. Most analyses use a
+/// `BitSet`, which is joined by union.
+///
 /// You should use an `Engine` to actually run an analysis, and a `ResultsCursor` to inspect the
 /// results of that analysis like so:
 ///
@@ -54,15 +64,15 @@ mod graphviz;
 ///     let mut cursor = ResultsCursor::new(body, results);
 ///
 ///     for (_, statement_index) in body.block_data[START_BLOCK].statements.iter_enumerated() {
-///         cursor.seek_after(Location { block: START_BLOCK, statement_index });
+///         cursor.seek_after_primary_effect(Location { block: START_BLOCK, statement_index });
 ///         let state = cursor.get();
 ///         println!("{:?}", state);
 ///     }
 /// }
 /// ```
-pub trait Analysis<'tcx>: BottomValue {
-    /// The index type used to access the dataflow state.
-    type Idx: Idx;
+pub trait Analysis<'tcx> {
+    /// The type that holds the dataflow state at any given point in the program.
+    type Domain: Clone + JoinSemiLattice + DebugState;
 
     /// A name, used for debugging, that describes this dataflow analysis.
     ///
@@ -70,24 +80,18 @@ pub trait Analysis<'tcx>: BottomValue {
     /// and try to keep it short.
     const NAME: &'static str;
 
-    /// How each element of your dataflow state will be displayed during debugging.
-    ///
-    /// By default, this is the `fmt::Debug` representation of `Self::Idx`.
-    fn pretty_print_idx(&self, w: &mut impl io::Write, idx: Self::Idx) -> io::Result<()> {
-        write!(w, "{:?}", idx)
-    }
-
-    /// The size of each bitvector allocated for each block.
-    fn bits_per_block(&self, body: &mir::Body<'tcx>) -> usize;
+    /// The initial value of the dataflow state upon entry to each basic block other than the
+    /// `START_BLOCK`. This should be the bottom element of the lattice (e.g. an empty `BitSet`).
+    fn bottom_value(&self, body: &mir::Body<'tcx>) -> Self::Domain;
 
     /// Mutates the entry set of the `START_BLOCK` to contain the initial state for dataflow
     /// analysis.
-    fn initialize_start_block(&self, body: &mir::Body<'tcx>, state: &mut BitSet<Self::Idx>);
+    fn initialize_start_block(&self, body: &mir::Body<'tcx>, state: &mut Self::Domain);
 
     /// Updates the current dataflow state with the effect of evaluating a statement.
     fn apply_statement_effect(
         &self,
-        state: &mut BitSet<Self::Idx>,
+        state: &mut Self::Domain,
         statement: &mir::Statement<'tcx>,
         location: Location,
     );
@@ -99,7 +103,7 @@ pub trait Analysis<'tcx>: BottomValue {
     /// in the `InitializedPlaces` analyses, the return place is not marked as initialized here.
     fn apply_terminator_effect(
         &self,
-        state: &mut BitSet<Self::Idx>,
+        state: &mut Self::Domain,
         terminator: &mir::Terminator<'tcx>,
         location: Location,
     );
@@ -111,13 +115,35 @@ pub trait Analysis<'tcx>: BottomValue {
     /// unwind edges for `Call`s.
     fn apply_call_return_effect(
         &self,
-        state: &mut BitSet<Self::Idx>,
+        state: &mut Self::Domain,
         block: BasicBlock,
         func: &mir::Operand<'tcx>,
         args: &[mir::Operand<'tcx>],
         return_place: &mir::Place<'tcx>,
     );
 
+    /// Whether this analysis has any effects along the outgoing edges of a `SwitchInt`
+    /// terminator.
+    ///
+    /// If this is `false`, `apply_switch_int_edge_effect` is never called, and the `Engine`
+    /// propagates the same state along every outgoing edge.
+    const HAS_SWITCH_INT_EDGE_EFFECTS: bool = false;
+
+    /// Updates the current dataflow state with the effect of taking the outgoing edge of a
+    /// `SwitchInt` terminator for which `discr` is equal to `value`, or the `otherwise` edge if
+    /// `value` is `None`.
+    ///
+    /// Much like `apply_call_return_effect`, this effect is only propagated along a single outgoing
+    /// edge of the block. For example, an analysis can record which variant an enum is known to
+    /// have after matching on its discriminant.
+    fn apply_switch_int_edge_effect(
+        &self,
+        _state: &mut Self::Domain,
+        _block: BasicBlock,
+        _discr: &mir::Operand<'tcx>,
+        _value: Option<u128>,
+    ) {}
+
     /// Applies the cumulative effect of an entire basic block to the dataflow state (except for
     /// `call_return_effect`, which is handled in the `Engine`).
     ///
@@ -127,7 +153,7 @@ pub trait Analysis<'tcx>: BottomValue {
     /// override `block_effect`.
     fn apply_whole_block_effect(
         &self,
-        state: &mut BitSet<Self::Idx>,
+        state: &mut Self::Domain,
         block: BasicBlock,
         block_data: &mir::BasicBlockData<'tcx>,
     ) {
@@ -147,7 +173,7 @@ pub trait Analysis<'tcx>: BottomValue {
     /// is equivalent to `apply_whole_block_effect`.
     fn apply_partial_block_effect(
         &self,
-        state: &mut BitSet<Self::Idx>,
+        state: &mut Self::Domain,
         block: BasicBlock,
         block_data: &mir::BasicBlockData<'tcx>,
         mut range: ops::Range<usize>,
@@ -180,7 +206,173 @@ pub trait Analysis<'tcx>: BottomValue {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+/// A dataflow problem whose transfer functions only add ("gen") and remove ("kill") elements of a
+/// `BitSet`, independently of the current state.
+///
+/// Every `GenKillAnalysis` is an `Analysis`. When run with `Engine::new_gen_kill`, the transfer
+/// function of each block is computed once up front and then applied in a single step whenever the
+/// block is visited during fixpoint iteration or when a cursor seeks to the end of it.
+pub trait GenKillAnalysis<'tcx> {
+    /// The index type used to access the dataflow state.
+    type Idx: Idx;
+
+    /// A name, used for debugging, that describes this dataflow analysis.
+    const NAME: &'static str;
+
+    /// The number of elements in the `BitSet` that holds the dataflow state.
+    fn domain_size(&self, body: &mir::Body<'tcx>) -> usize;
+
+    /// Mutates the entry set of the `START_BLOCK` to contain the initial state for dataflow
+    /// analysis.
+    fn initialize_start_block(&self, body: &mir::Body<'tcx>, state: &mut BitSet<Self::Idx>);
+
+    /// See `Analysis::apply_statement_effect`.
+    fn statement_effect(
+        &self,
+        trans: &mut impl GenKill<Self::Idx>,
+        statement: &mir::Statement<'tcx>,
+        location: Location,
+    );
+
+    /// See `Analysis::apply_terminator_effect`.
+    fn terminator_effect(
+        &self,
+        trans: &mut impl GenKill<Self::Idx>,
+        terminator: &mir::Terminator<'tcx>,
+        location: Location,
+    );
+
+    /// See `Analysis::apply_call_return_effect`.
+    fn call_return_effect(
+        &self,
+        trans: &mut impl GenKill<Self::Idx>,
+        block: BasicBlock,
+        func: &mir::Operand<'tcx>,
+        args: &[mir::Operand<'tcx>],
+        return_place: &mir::Place<'tcx>,
+    );
+
+    /// See `Analysis::HAS_SWITCH_INT_EDGE_EFFECTS`.
+    const HAS_SWITCH_INT_EDGE_EFFECTS: bool = false;
+
+    /// See `Analysis::apply_switch_int_edge_effect`.
+    fn switch_int_edge_effect(
+        &self,
+        _trans: &mut impl GenKill<Self::Idx>,
+        _block: BasicBlock,
+        _discr: &mir::Operand<'tcx>,
+        _value: Option<u128>,
+    ) {}
+}
+
+impl<A> Analysis<'tcx> for A
+where
+    A: GenKillAnalysis<'tcx>,
+{
+    type Domain = BitSet<A::Idx>;
+
+    const NAME: &'static str = <A as GenKillAnalysis<'tcx>>::NAME;
+
+    fn bottom_value(&self, body: &mir::Body<'tcx>) -> Self::Domain {
+        BitSet::new_empty(self.domain_size(body))
+    }
+
+    fn initialize_start_block(&self, body: &mir::Body<'tcx>, state: &mut Self::Domain) {
+        GenKillAnalysis::initialize_start_block(self, body, state);
+    }
+
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::Domain,
+        statement: &mir::Statement<'tcx>,
+        location: Location,
+    ) {
+        self.statement_effect(state, statement, location);
+    }
+
+    fn apply_terminator_effect(
+        &self,
+        state: &mut Self::Domain,
+        terminator: &mir::Terminator<'tcx>,
+        location: Location,
+    ) {
+        self.terminator_effect(state, terminator, location);
+    }
+
+    fn apply_call_return_effect(
+        &self,
+        state: &mut Self::Domain,
+        block: BasicBlock,
+        func: &mir::Operand<'tcx>,
+        args: &[mir::Operand<'tcx>],
+        return_place: &mir::Place<'tcx>,
+    ) {
+        self.call_return_effect(state, block, func, args, return_place);
+    }
+
+    const HAS_SWITCH_INT_EDGE_EFFECTS: bool =
+        <A as GenKillAnalysis<'tcx>>::HAS_SWITCH_INT_EDGE_EFFECTS;
+
+    fn apply_switch_int_edge_effect(
+        &self,
+        state: &mut Self::Domain,
+        block: BasicBlock,
+        discr: &mir::Operand<'tcx>,
+        value: Option<u128>,
+    ) {
+        self.switch_int_edge_effect(state, block, discr, value);
+    }
+}
+
+/// The effects of a `GenKillAnalysis`, which are either applied directly to the dataflow state or
+/// accumulated into the transfer function for an entire block.
+pub trait GenKill<T> {
+    /// Inserts `elem` into the state vector.
+    fn gen(&mut self, elem: T);
+
+    /// Removes `elem` from the state vector.
+    fn kill(&mut self, elem: T);
+
+    /// Calls `gen` for each element in `elems`.
+    fn gen_all(&mut self, elems: impl IntoIterator<Item = T>) {
+        for elem in elems {
+            self.gen(elem);
+        }
+    }
+
+    /// Calls `kill` for each element in `elems`.
+    fn kill_all(&mut self, elems: impl IntoIterator<Item = T>) {
+        for elem in elems {
+            self.kill(elem);
+        }
+    }
+}
+
+impl<T: Idx> GenKill<T> for BitSet<T> {
+    fn gen(&mut self, elem: T) {
+        self.insert(elem);
+    }
+
+    fn kill(&mut self, elem: T) {
+        self.remove(elem);
+    }
+}
+
+impl<T: Idx> GenKill<T> for GenKillSet<T> {
+    fn gen(&mut self, elem: T) {
+        GenKillSet::gen(self, elem);
+    }
+
+    fn kill(&mut self, elem: T) {
+        GenKillSet::kill(self, elem);
+    }
+}
+
+/// Applies the cumulative transfer function of a block to the dataflow state. Only available for
+/// analyses run with `Engine::new_gen_kill`.
+type ApplyTransForBlock<'tcx, A> = Box<dyn Fn(BasicBlock, &mut <A as Analysis<'tcx>>::Domain)>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum CursorPosition {
     AtBlockStart(BasicBlock),
     After(Location),
@@ -195,29 +387,46 @@ impl CursorPosition {
     }
 }
 
-type ResultsRefCursor<'a, 'mir, 'tcx, A> =
+/// A `ResultsCursor` that borrows the underlying `Results`.
+///
+/// Any number of these can point into the same `Results` at once, so several consumers can inspect
+/// the results of a single analysis at different locations without re-running it.
+pub type ResultsRefCursor<'a, 'mir, 'tcx, A> =
     ResultsCursor<'mir, 'tcx, A, &'a Results<'tcx, A>>;
 
 /// Inspect the results of dataflow analysis.
 ///
-/// This cursor has linear performance when visiting statements in a block in order. Visiting
-/// statements within a block in reverse order is `O(n^2)`, where `n` is the number of statements
-/// in that block.
+/// This cursor has linear performance when visiting statements in a block in order. The state
+/// after each statement of the current block is cached as the cursor moves past it, so seeking
+/// back to an earlier location in the same block only requires copying the cached state.
+///
+/// The *primary effect* of a statement or terminator is the one applied by
+/// `Analysis::apply_statement_effect` or `Analysis::apply_terminator_effect`. A cursor can be
+/// positioned either before or after the primary effect of any location. Effects that only hold
+/// along some of the outgoing edges of a terminator are not part of its primary effect, and are
+/// applied by the `seek_after_assume_*` methods instead.
 pub struct ResultsCursor<'mir, 'tcx, A, R = Results<'tcx, A>>
 where
     A: Analysis<'tcx>,
 {
     body: &'mir mir::Body<'tcx>,
     results: R,
-    state: BitSet<A::Idx>,
+    state: A::Domain,
 
     pos: CursorPosition,
 
-    /// Whether the effects of `apply_call_return_effect` are currently stored in `state`.
+    /// The effect along an outgoing edge of the current terminator that is stored in `state`, if
+    /// any.
     ///
-    /// This flag ensures that multiple calls to `seek_after_assume_call_returns` with the same
-    /// target only result in one invocation of `apply_call_return_effect`.
-    is_call_return_effect_applied: bool,
+    /// This ensures that multiple calls to `seek_after_assume_call_returns` (or
+    /// `seek_after_assume_switch_int_edge`) with the same target only apply the effect once.
+    edge_effect: Option<EdgeEffect>,
+
+    /// The state after each statement (and the terminator) of the current block, in order. Only
+    /// the first `num_cached` entries are valid; the rest are kept around to reuse their
+    /// allocations when the cursor moves to another block.
+    block_cache: Vec<A::Domain>,
+    num_cached: usize,
 }
 
 impl<'mir, 'tcx, A, R> ResultsCursor<'mir, 'tcx, A, R>
@@ -230,9 +439,11 @@ where
         ResultsCursor {
             body,
             pos: CursorPosition::AtBlockStart(mir::START_BLOCK),
-            is_call_return_effect_applied: false,
+            edge_effect: None,
             state: results.borrow().entry_sets[mir::START_BLOCK].clone(),
             results,
+            block_cache: Vec::new(),
+            num_cached: 0,
         }
     }
 
@@ -242,71 +453,124 @@ where
 
     /// Resets the cursor to the start of the given `block`.
     pub fn seek_to_block_start(&mut self, block: BasicBlock) {
-        self.state.overwrite(&self.results.borrow().entry_sets[block]);
+        if self.pos.block() != block {
+            self.num_cached = 0;
+        }
+
+        self.state.clone_from(&self.results.borrow().entry_sets[block]);
         self.pos = CursorPosition::AtBlockStart(block);
-        self.is_call_return_effect_applied = false;
+        self.edge_effect = None;
     }
 
-    /// Updates the cursor to hold the dataflow state immediately before `target`.
-    pub fn seek_before(&mut self, target: Location) {
+    /// Updates the cursor to hold the dataflow state immediately before the primary effect of the
+    /// statement or terminator at `target`.
+    ///
+    /// This is the state that the statement or terminator observes when it is evaluated.
+    pub fn seek_before_primary_effect(&mut self, target: Location) {
         assert!(target <= self.body.terminator_loc(target.block));
 
         if target.statement_index == 0 {
             self.seek_to_block_start(target.block);
         } else {
-            self._seek_after(Location {
+            self.seek_after_primary_effect(Location {
                 block: target.block,
                 statement_index: target.statement_index - 1,
             });
         }
     }
 
-    /// Updates the cursor to hold the dataflow state at `target`.
+    /// Updates the cursor to hold the dataflow state immediately after the primary effect of the
+    /// statement or terminator at `target`.
     ///
-    /// If `target` is a `Call` terminator, `apply_call_return_effect` will not be called. See
+    /// If `target` is a terminator, the effects along its outgoing edges are not applied. In
+    /// particular, `apply_call_return_effect` is not called for a `Call` terminator. See
     /// `seek_after_assume_call_returns` if you wish to observe the dataflow state upon a
     /// successful return.
-    pub fn seek_after(&mut self, target: Location) {
+    pub fn seek_after_primary_effect(&mut self, target: Location) {
         assert!(target <= self.body.terminator_loc(target.block));
 
         // This check ensures the correctness of a call to `seek_after_assume_call_returns`
-        // followed by one to `seek_after` with the same target.
-        if self.is_call_return_effect_applied {
+        // followed by one to `seek_after_primary_effect` with the same target.
+        if self.edge_effect.is_some() {
             self.seek_to_block_start(target.block);
         }
 
         self._seek_after(target);
     }
 
-    /// Equivalent to `seek_after`, but also calls `apply_call_return_effect` if `target` is a
-    /// `Call` terminator whose callee is convergent.
+    /// Equivalent to `seek_after_primary_effect`, but also calls `apply_call_return_effect` if
+    /// `target` is a `Call` terminator whose callee is convergent.
     pub fn seek_after_assume_call_returns(&mut self, target: Location) {
         assert!(target <= self.body.terminator_loc(target.block));
 
-        self._seek_after(target);
+        let body = self.body;
+        if target == body.terminator_loc(target.block) {
+            let term = body.basic_blocks()[target.block].terminator();
+            if let mir::TerminatorKind::Call {
+                destination: Some((return_place, _)),
+                func,
+                args,
+                ..
+            } = &term.kind {
+                self.seek_after_with_edge_effect(
+                    target.block,
+                    EdgeEffect::CallReturn,
+                    |analysis, state| {
+                        analysis.apply_call_return_effect(
+                            state,
+                            target.block,
+                            func,
+                            args,
+                            return_place,
+                        );
+                    },
+                );
+                return;
+            }
+        }
+
+        self.seek_after_primary_effect(target);
+    }
 
-        if target != self.body.terminator_loc(target.block) {
+    /// Equivalent to `seek_after_primary_effect` for the `SwitchInt` terminator of `block`, but
+    /// also calls `apply_switch_int_edge_effect` for the outgoing edge that is taken when the
+    /// discriminant is equal to `value` (or the `otherwise` edge if `value` is `None`).
+    pub fn seek_after_assume_switch_int_edge(&mut self, block: BasicBlock, value: Option<u128>) {
+        let body = self.body;
+        let term = body.basic_blocks()[block].terminator();
+        let discr = match &term.kind {
+            mir::TerminatorKind::SwitchInt { discr, .. } => discr,
+            kind => bug!("`{:?}` is not a `SwitchInt` terminator", kind),
+        };
+
+        self.seek_after_with_edge_effect(
+            block,
+            EdgeEffect::SwitchInt(value),
+            |analysis, state| analysis.apply_switch_int_edge_effect(state, block, discr, value),
+        );
+    }
+
+    /// Updates the cursor to hold the dataflow state after the terminator of `block`, with the
+    /// given `effect` applied to it.
+    fn seek_after_with_edge_effect(
+        &mut self,
+        block: BasicBlock,
+        effect: EdgeEffect,
+        apply_effect: impl FnOnce(&A, &mut A::Domain),
+    ) {
+        let target = self.body.terminator_loc(block);
+        if self.pos == CursorPosition::After(target) && self.edge_effect == Some(effect) {
             return;
         }
 
-        let term = self.body.basic_blocks()[target.block].terminator();
-        if let mir::TerminatorKind::Call {
-            destination: Some((return_place, _)),
-            func,
-            args,
-            ..
-        } = &term.kind {
-            if !self.is_call_return_effect_applied {
-                self.is_call_return_effect_applied = true;
-                self.results.borrow().analysis.apply_call_return_effect(
-                    &mut self.state,
-                    target.block,
-                    func,
-                    args,
-                    return_place,
-                );
-            }
+        // The effect of another outgoing edge may be stored in `state`.
+        if self.edge_effect.is_some() {
+            self.seek_to_block_start(block);
         }
+
+        self._seek_after(target);
+        apply_effect(&self.results.borrow().analysis, &mut self.state);
+        self.edge_effect = Some(effect);
     }
 
     fn _seek_after(&mut self, target: Location) {
@@ -316,53 +580,101 @@ where
             self.seek_to_block_start(target_block);
         }
 
-        // If we're in the same block but after the target statement, we need to reset to the start
-        // of the block.
         if let CursorPosition::After(Location { statement_index: curr_index, .. }) = self.pos {
-            match curr_index.cmp(&target_index) {
-                Ordering::Equal => return,
-                Ordering::Less => {},
-                Ordering::Greater => self.seek_to_block_start(target_block),
+            if curr_index == target_index {
+                return;
             }
         }
 
-        // The cursor is now in the same block as the target location pointing at an earlier
-        // statement.
-        debug_assert_eq!(self.pos.block(), target_block);
-        if let CursorPosition::After(Location { statement_index, .. }) = self.pos {
-            debug_assert!(statement_index < target_index);
+        // Seeking from the start of a block to its terminator can use the cached transfer
+        // function for the whole block, if there is one.
+        let block_data = &self.body.basic_blocks()[target_block];
+        if self.num_cached == 0 && target_index == block_data.statements.len() {
+            let results = self.results.borrow();
+            if let Some(apply_trans) = &results.apply_trans_for_block {
+                self.state.clone_from(&results.entry_sets[target_block]);
+                apply_trans(target_block, &mut self.state);
+
+                self.pos = CursorPosition::After(target);
+                self.edge_effect = None;
+                return;
+            }
         }
 
-        let first_unapplied_statement = match self.pos {
-            CursorPosition::AtBlockStart(_) => 0,
-            CursorPosition::After(Location { statement_index, .. }) => statement_index + 1,
-        };
+        // If the cursor has already moved past `target`, the state after it is in the cache.
+        if target_index < self.num_cached {
+            self.state.clone_from(&self.block_cache[target_index]);
+        } else {
+            // Otherwise, resume from the last cached state and cache the state after each
+            // statement up to and including `target`.
+            match self.num_cached.checked_sub(1) {
+                Some(last_cached) => self.state.clone_from(&self.block_cache[last_cached]),
+                None => self.state.clone_from(&self.results.borrow().entry_sets[target_block]),
+            }
 
-        let block_data = &self.body.basic_blocks()[target_block];
-        self.results.borrow().analysis.apply_partial_block_effect(
-            &mut self.state,
-            target_block,
-            block_data,
-            first_unapplied_statement..target_index + 1,
-        );
+            for statement_index in self.num_cached..=target_index {
+                self.results.borrow().analysis.apply_partial_block_effect(
+                    &mut self.state,
+                    target_block,
+                    block_data,
+                    statement_index..statement_index + 1,
+                );
+
+                match self.block_cache.get_mut(statement_index) {
+                    Some(cached) => cached.clone_from(&self.state),
+                    None => self.block_cache.push(self.state.clone()),
+                }
+            }
+
+            self.num_cached = target_index + 1;
+        }
 
         self.pos = CursorPosition::After(target);
-        self.is_call_return_effect_applied = false;
+        self.edge_effect = None;
     }
 
     /// Gets the dataflow state at the current location.
-    pub fn get(&self) -> &BitSet<A::Idx> {
+    pub fn get(&self) -> &A::Domain {
         &self.state
     }
 }
 
+/// An effect that only holds along some of the outgoing edges of a terminator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EdgeEffect {
+    /// See `Analysis::apply_call_return_effect`.
+    CallReturn,
+
+    /// See `Analysis::apply_switch_int_edge_effect`.
+    SwitchInt(Option<u128>),
+}
+
 /// A completed dataflow analysis.
 pub struct Results<'tcx, A>
 where
     A: Analysis<'tcx>,
 {
     analysis: A,
-    entry_sets: IndexVec<BasicBlock, BitSet<A::Idx>>,
+    entry_sets: IndexVec<BasicBlock, A::Domain>,
+    apply_trans_for_block: Option<ApplyTransForBlock<'tcx, A>>,
+}
+
+impl<A> Results<'tcx, A>
+where
+    A: Analysis<'tcx>,
+{
+    /// Creates a `ResultsCursor` that takes ownership of these results.
+    pub fn into_results_cursor(self, body: &'mir mir::Body<'tcx>) -> ResultsCursor<'mir, 'tcx, A> {
+        ResultsCursor::new(body, self)
+    }
+
+    /// Creates a `ResultsCursor` that borrows these results.
+    pub fn as_results_cursor(
+        &'a self,
+        body: &'mir mir::Body<'tcx>,
+    ) -> ResultsRefCursor<'a, 'mir, 'tcx, A> {
+        ResultsCursor::new(body, self)
+    }
 }
 
 /// All information required to iterate a dataflow analysis to fixpoint.
@@ -371,12 +683,51 @@ where
     A: Analysis<'tcx>,
 {
     analysis: A,
-    bits_per_block: usize,
     tcx: TyCtxt<'tcx>,
     body: &'a mir::Body<'tcx>,
     def_id: DefId,
     dead_unwinds: &'a BitSet<BasicBlock>,
-    entry_sets: IndexVec<BasicBlock, BitSet<A::Idx>>,
+    entry_sets: IndexVec<BasicBlock, A::Domain>,
+
+    /// The cached transfer function of each block, if it was computed up front.
+    apply_trans_for_block: Option<ApplyTransForBlock<'tcx, A>>,
+}
+
+impl<A> Engine<'a, 'tcx, A>
+where
+    A: GenKillAnalysis<'tcx>,
+{
+    /// Creates a new `Engine` for a gen/kill problem, computing the transfer function of each block
+    /// up front.
+    pub fn new_gen_kill(
+        tcx: TyCtxt<'tcx>,
+        body: &'a mir::Body<'tcx>,
+        def_id: DefId,
+        dead_unwinds: &'a BitSet<BasicBlock>,
+        analysis: A,
+    ) -> Self {
+        let domain_size = analysis.domain_size(body);
+        let identity = GenKillSet::from_elem(HybridBitSet::new_empty(domain_size));
+        let mut trans_for_block = IndexVec::from_elem(identity, body.basic_blocks());
+
+        for (block, block_data) in body.basic_blocks().iter_enumerated() {
+            let trans = &mut trans_for_block[block];
+
+            for (statement_index, statement) in block_data.statements.iter().enumerate() {
+                let location = Location { block, statement_index };
+                analysis.statement_effect(trans, statement, location);
+            }
+
+            let location = body.terminator_loc(block);
+            analysis.terminator_effect(trans, block_data.terminator(), location);
+        }
+
+        let apply_trans = Box::new(move |block: BasicBlock, state: &mut BitSet<A::Idx>| {
+            trans_for_block[block].apply(state);
+        });
+
+        Self::new_with_trans(tcx, body, def_id, dead_unwinds, analysis, Some(apply_trans as _))
+    }
 }
 
 impl<A> Engine<'a, 'tcx, A>
@@ -390,50 +741,56 @@ where
         dead_unwinds: &'a BitSet<BasicBlock>,
         analysis: A,
     ) -> Self {
-        let bits_per_block = analysis.bits_per_block(body);
-
-        let bottom_value_set = if A::BOTTOM_VALUE == true {
-            BitSet::new_filled(bits_per_block)
-        } else {
-            BitSet::new_empty(bits_per_block)
-        };
+        Self::new_with_trans(tcx, body, def_id, dead_unwinds, analysis, None)
+    }
 
-        let mut entry_sets = IndexVec::from_elem(bottom_value_set, body.basic_blocks());
+    fn new_with_trans(
+        tcx: TyCtxt<'tcx>,
+        body: &'a mir::Body<'tcx>,
+        def_id: DefId,
+        dead_unwinds: &'a BitSet<BasicBlock>,
+        analysis: A,
+        apply_trans_for_block: Option<ApplyTransForBlock<'tcx, A>>,
+    ) -> Self {
+        let bottom_value = analysis.bottom_value(body);
+        let mut entry_sets = IndexVec::from_elem(bottom_value, body.basic_blocks());
         analysis.initialize_start_block(body, &mut entry_sets[mir::START_BLOCK]);
 
         Engine {
             analysis,
-            bits_per_block,
             tcx,
             body,
             def_id,
             dead_unwinds,
             entry_sets,
+            apply_trans_for_block,
         }
     }
 
     pub fn iterate_to_fixpoint(mut self) -> Results<'tcx, A> {
-        let mut temp_state = BitSet::new_empty(self.bits_per_block);
+        let mut temp_state = self.analysis.bottom_value(self.body);
 
-        let mut dirty_queue: WorkQueue<BasicBlock> =
-            WorkQueue::with_none(self.body.basic_blocks().len());
+        let start_time = Instant::now();
 
-        for (bb, _) in traversal::reverse_postorder(self.body) {
-            dirty_queue.insert(bb);
-        }
-
-        // Add blocks that are not reachable from START_BLOCK to the work queue. These blocks will
-        // be processed after the ones added above.
+        let mut dirty_queue = RpoWorkQueue::new(self.body);
         for bb in self.body.basic_blocks().indices() {
             dirty_queue.insert(bb);
         }
 
+        // Always visiting the dirty block that comes first in reverse postorder means that, in the
+        // absence of back-edges, each block is visited only after all of its predecessors.
+        let mut num_visits = 0;
         while let Some(bb) = dirty_queue.pop() {
+            num_visits += 1;
+
             let bb_data = &self.body[bb];
             let on_entry = &self.entry_sets[bb];
 
-            temp_state.overwrite(on_entry);
-            self.analysis.apply_whole_block_effect(&mut temp_state, bb, bb_data);
+            temp_state.clone_from(on_entry);
+            match &self.apply_trans_for_block {
+                Some(apply_trans) => apply_trans(bb, &mut temp_state),
+                None => self.analysis.apply_whole_block_effect(&mut temp_state, bb, bb_data),
+            }
 
             self.propagate_bits_into_graph_successors_of(
                 &mut temp_state,
@@ -442,16 +799,28 @@ where
             );
         }
 
+        if self.tcx.sess.dataflow_stats() {
+            self.tcx.sess.code_stats.record_dataflow_stats(DataflowStatsInfo {
+                body: self.tcx.def_path_str(self.def_id),
+                analysis: A::NAME,
+                num_blocks: self.body.basic_blocks().len(),
+                num_passes: dirty_queue.num_passes,
+                num_block_visits: num_visits,
+                time: start_time.elapsed(),
+            });
+        }
+
         let Engine {
             tcx,
             body,
             def_id,
             analysis,
             entry_sets,
+            apply_trans_for_block,
             ..
         } = self;
 
-        let results = Results { analysis, entry_sets };
+        let results = Results { analysis, entry_sets, apply_trans_for_block };
 
         let attrs = tcx.get_attrs(def_id);
         if let Some(path) = get_dataflow_graphviz_output_path(tcx, attrs, A::NAME) {
@@ -459,6 +828,8 @@ where
             if let Err(e) = result {
                 warn!("Failed to write dataflow results to {}: {}", path.display(), e);
             }
+        } else if tcx.sess.opts.debugging_opts.dump_mir_dataflow {
+            dump_dataflow_graphviz_results(tcx, body, def_id, &results);
         }
 
         results
@@ -466,9 +837,9 @@ where
 
     fn propagate_bits_into_graph_successors_of(
         &mut self,
-        in_out: &mut BitSet<A::Idx>,
+        in_out: &mut A::Domain,
         (bb, bb_data): (BasicBlock, &'a mir::BasicBlockData<'tcx>),
-        dirty_list: &mut WorkQueue<BasicBlock>,
+        dirty_list: &mut RpoWorkQueue,
     ) {
         match bb_data.terminator().kind {
             mir::TerminatorKind::Return
@@ -505,9 +876,21 @@ where
                 }
             }
 
-            mir::TerminatorKind::SwitchInt { ref targets, .. } => {
-                for target in targets {
-                    self.propagate_bits_into_entry_set_for(in_out, *target, dirty_list);
+            mir::TerminatorKind::SwitchInt { ref targets, ref values, ref discr, .. } => {
+                if !A::HAS_SWITCH_INT_EDGE_EFFECTS {
+                    for target in targets {
+                        self.propagate_bits_into_entry_set_for(in_out, *target, dirty_list);
+                    }
+                    return;
+                }
+
+                // The last target is the `otherwise` edge, which has no value.
+                let mut edge_state = in_out.clone();
+                for (i, &target) in targets.iter().enumerate() {
+                    let value = values.get(i).copied();
+                    edge_state.clone_from(in_out);
+                    self.analysis.apply_switch_int_edge_effect(&mut edge_state, bb, discr, value);
+                    self.propagate_bits_into_entry_set_for(&edge_state, target, dirty_list);
                 }
             }
 
@@ -544,18 +927,73 @@ where
 
     fn propagate_bits_into_entry_set_for(
         &mut self,
-        in_out: &BitSet<A::Idx>,
+        in_out: &A::Domain,
         bb: BasicBlock,
-        dirty_queue: &mut WorkQueue<BasicBlock>,
+        dirty_queue: &mut RpoWorkQueue,
     ) {
         let entry_set = &mut self.entry_sets[bb];
-        let set_changed = self.analysis.join(entry_set, &in_out);
+        let set_changed = entry_set.join(in_out);
         if set_changed {
             dirty_queue.insert(bb);
         }
     }
 }
 
+/// A de-duplicating work queue of basic blocks that always pops the block that comes first in
+/// reverse postorder. Blocks that are unreachable from the `START_BLOCK` come after all others.
+struct RpoWorkQueue {
+    rpo_index: IndexVec<BasicBlock, usize>,
+    rpo_order: Vec<BasicBlock>,
+    dirty: BitSet<usize>,
+
+    /// The number of passes over the blocks in reverse postorder so far. A new pass begins whenever
+    /// the popped block does not come after the previously popped one.
+    num_passes: usize,
+    last_popped: Option<usize>,
+}
+
+impl RpoWorkQueue {
+    fn new(body: &mir::Body<'_>) -> Self {
+        let num_blocks = body.basic_blocks().len();
+
+        let mut rpo_order: Vec<_> = traversal::reverse_postorder(body).map(|(bb, _)| bb).collect();
+        let mut is_reachable = BitSet::new_empty(num_blocks);
+        for &bb in &rpo_order {
+            is_reachable.insert(bb);
+        }
+        rpo_order.extend(body.basic_blocks().indices().filter(|&bb| !is_reachable.contains(bb)));
+
+        let mut rpo_index = IndexVec::from_elem_n(0, num_blocks);
+        for (i, &bb) in rpo_order.iter().enumerate() {
+            rpo_index[bb] = i;
+        }
+
+        RpoWorkQueue {
+            rpo_index,
+            rpo_order,
+            dirty: BitSet::new_empty(num_blocks),
+            num_passes: 0,
+            last_popped: None,
+        }
+    }
+
+    fn insert(&mut self, bb: BasicBlock) {
+        self.dirty.insert(self.rpo_index[bb]);
+    }
+
+    fn pop(&mut self) -> Option<BasicBlock> {
+        let i = self.dirty.iter().next()?;
+        self.dirty.remove(i);
+
+        if self.last_popped.map_or(true, |last| i <= last) {
+            self.num_passes += 1;
+        }
+        self.last_popped = Some(i);
+
+        Some(self.rpo_order[i])
+    }
+}
+
 /// Looks for attributes like `#[rustc_mir(borrowck_graphviz_postflow="./path/to/suffix.dot")]` and
 /// extracts the path with the given analysis name prepended to the suffix.
 ///
@@ -611,3 +1049,27 @@ fn write_dataflow_graphviz_results<A: Analysis<'tcx>>(
     dot::render(&graphviz, &mut buf)?;
     fs::write(path, buf)
 }
+
+/// Writes the results of an analysis to the `-Z dump-mir-dir` directory, alongside the `.mir`
+/// files, if `-Z dump-mir` matches either the name of the analysis or the item being analyzed.
+fn dump_dataflow_graphviz_results<A: Analysis<'tcx>>(
+    tcx: TyCtxt<'tcx>,
+    body: &mir::Body<'tcx>,
+    def_id: DefId,
+    results: &Results<'tcx, A>,
+) {
+    let source = MirSource::item(def_id);
+    if !util::dump_enabled(tcx, A::NAME, source) {
+        return;
+    }
+
+    let result: io::Result<()> = try {
+        let mut file = util::pretty::create_dump_file(tcx, "dot", None, A::NAME, &"", source)?;
+        let graphviz = graphviz::Formatter::new(body, def_id, results);
+        dot::render(&graphviz, &mut file)?;
+    };
+
+    if let Err(e) = result {
+        warn!("Failed to dump dataflow results for {:?}: {}", def_id, e);
+    }
+}
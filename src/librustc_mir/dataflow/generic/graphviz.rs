@@ -4,11 +4,10 @@ use std::{ops, str};
 
 use rustc::hir::def_id::DefId;
 use rustc::mir::{self, BasicBlock, Body, Location};
-use rustc_index::bit_set::{BitSet, HybridBitSet};
 use rustc_index::vec::Idx;
 
 use crate::util::graphviz_safe_def_name;
-use super::{Analysis, Results, ResultsRefCursor};
+use super::{Analysis, DebugState, Results, ResultsRefCursor};
 
 pub struct Formatter<'a, 'tcx, A>
 where
@@ -32,8 +31,8 @@ where
     ) -> Self {
         let block_formatter = BlockFormatter {
             bg: Background::Light,
-            prev_state: BitSet::new_empty(results.analysis.bits_per_block(body)),
-            results: ResultsRefCursor::new(body, results),
+            prev_state: results.analysis.bottom_value(body),
+            results: results.as_results_cursor(body),
         };
 
         Formatter {
@@ -143,7 +142,7 @@ struct BlockFormatter<'a, 'tcx, A>
 where
     A: Analysis<'tcx>,
 {
-    prev_state: BitSet<A::Idx>,
+    prev_state: A::Domain,
     results: ResultsRefCursor<'a, 'a, 'tcx, A>,
     bg: Background,
 }
@@ -214,7 +213,7 @@ where
         self.bg = Background::Light;
         self.results.seek_to_block_start(block);
         self.write_row_with_curr_state(w, "", "(on entry)")?;
-        self.prev_state.overwrite(self.results.get());
+        self.prev_state.clone_from(self.results.get());
 
         // D: Statement transfer functions
         for (i, statement) in body[block].statements.iter().enumerate() {
@@ -223,9 +222,9 @@ where
             let mir_col = format!("{:?}", statement);
             let i_col = i.to_string();
 
-            self.results.seek_after(location);
+            self.results.seek_after_primary_effect(location);
             self.write_row_with_curr_diff(w, &i_col, &mir_col)?;
-            self.prev_state.overwrite(self.results.get());
+            self.prev_state.clone_from(self.results.get());
         }
 
         // E: Terminator transfer function
@@ -235,9 +234,9 @@ where
         let mut mir_col = String::new();
         terminator.kind.fmt_head(&mut mir_col).unwrap();
 
-        self.results.seek_after(location);
+        self.results.seek_after_primary_effect(location);
         self.write_row_with_curr_diff(w, "T", &mir_col)?;
-        self.prev_state.overwrite(self.results.get());
+        self.prev_state.clone_from(self.results.get());
 
         // F: Exit state
         if let mir::TerminatorKind::Call { destination: Some(_), ..  } = &terminator.kind {
@@ -261,9 +260,7 @@ where
         let bg = self.toggle_background();
 
         let mut out = Vec::new();
-        write!(&mut out, "{{")?;
-        pretty_print_state_elems(&mut out, self.results.analysis(), self.results.get().iter())?;
-        write!(&mut out, "}}")?;
+        self.results.get().fmt_state(&mut out)?;
 
         write!(
             w,
@@ -286,15 +283,10 @@ where
         mir: &str,
     ) -> io::Result<()> {
         let bg = self.toggle_background();
-        let analysis = self.results.analysis();
-
-        let diff = BitSetDiff::compute(&self.prev_state, self.results.get());
 
         let mut set = Vec::new();
-        pretty_print_state_elems(&mut set, analysis, diff.set.iter())?;
-
         let mut clear = Vec::new();
-        pretty_print_state_elems(&mut clear, analysis, diff.clear.iter())?;
+        self.results.get().fmt_diff(&self.prev_state, &mut set, &mut clear)?;
 
         write!(
             w,
@@ -331,60 +323,6 @@ where
     }
 }
 
-/// The operations required to transform one `BitSet` into another.
-struct BitSetDiff<T: Idx> {
-    set: HybridBitSet<T>,
-    clear: HybridBitSet<T>,
-}
-
-impl<T: Idx> BitSetDiff<T> {
-    fn compute(from: &BitSet<T>, to: &BitSet<T>) -> Self {
-        assert_eq!(from.domain_size(), to.domain_size());
-        let len = from.domain_size();
-
-        let mut set = HybridBitSet::new_empty(len);
-        let mut clear = HybridBitSet::new_empty(len);
-
-        // FIXME: This could be made faster if `BitSet::xor` were implemented.
-        for i in (0..len).map(|i| T::new(i)) {
-            match (from.contains(i), to.contains(i)) {
-                (false, true) => set.insert(i),
-                (true, false) => clear.insert(i),
-                _ => continue,
-            };
-        }
-
-        BitSetDiff {
-            set,
-            clear,
-        }
-    }
-}
-
-/// Formats each `elem` using the pretty printer provided by `analysis` into a comma-separated
-/// list.
-fn pretty_print_state_elems<A>(
-    w: &mut impl io::Write,
-    analysis: &A,
-    elems: impl Iterator<Item = A::Idx>,
-) -> io::Result<()>
-where
-    A: Analysis<'tcx>,
-{
-    let mut first = true;
-    for idx in elems {
-        if first {
-            first = false;
-        } else {
-            write!(w, ",")?;
-        }
-
-        analysis.pretty_print_idx(w, idx)?;
-    }
-
-    Ok(())
-}
-
 /// The background color used for zebra-striping the table.
 #[derive(Clone, Copy)]
 enum Background {
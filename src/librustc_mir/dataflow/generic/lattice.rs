@@ -0,0 +1,89 @@
+//! Traits for the domain of a dataflow analysis, the set of values its state can take, along with
+//! their implementations for `BitSet`.
+
+use std::io;
+
+use rustc_index::bit_set::BitSet;
+use rustc_index::vec::Idx;
+
+/// A [partially ordered set][poset] in which every pair of elements has a least upper bound, called
+/// their join.
+///
+/// The `Engine` joins the exit state of each predecessor of a block into that block's entry state
+/// until none of them change.
+///
+/// [poset]: https://en.wikipedia.org/wiki/Partially_ordered_set
+pub trait JoinSemiLattice: Eq {
+    /// Computes the least upper bound of `self` and `other`, storing it in `self`.
+    ///
+    /// Returns `true` if `self` changed.
+    fn join(&mut self, other: &Self) -> bool;
+}
+
+/// How the state of a dataflow analysis is displayed during debugging (e.g. in graphviz diagrams).
+pub trait DebugState: Eq {
+    /// Writes the entire state to `w`.
+    fn fmt_state(&self, w: &mut impl io::Write) -> io::Result<()>;
+
+    /// Writes the parts of the state that were added to `added`, and the ones that were removed to
+    /// `removed`, when the state went from `old` to `self`.
+    ///
+    /// By default, the entire new state is written to `added` if the state changed at all.
+    fn fmt_diff(
+        &self,
+        old: &Self,
+        added: &mut impl io::Write,
+        _removed: &mut impl io::Write,
+    ) -> io::Result<()> {
+        if self == old {
+            return Ok(());
+        }
+
+        self.fmt_state(added)
+    }
+}
+
+/// A `BitSet` is joined by union, so its bottom value is the empty set.
+impl<T: Idx> JoinSemiLattice for BitSet<T> {
+    fn join(&mut self, other: &Self) -> bool {
+        self.union(other)
+    }
+}
+
+impl<T: Idx> DebugState for BitSet<T> {
+    fn fmt_state(&self, w: &mut impl io::Write) -> io::Result<()> {
+        write!(w, "{{")?;
+        fmt_elems(w, self.iter())?;
+        write!(w, "}}")
+    }
+
+    fn fmt_diff(
+        &self,
+        old: &Self,
+        added: &mut impl io::Write,
+        removed: &mut impl io::Write,
+    ) -> io::Result<()> {
+        assert_eq!(old.domain_size(), self.domain_size());
+
+        // FIXME: This could be made faster if `BitSet::xor` were implemented.
+        let indices = (0..self.domain_size()).map(T::new);
+        fmt_elems(added, indices.clone().filter(|&i| self.contains(i) && !old.contains(i)))?;
+        fmt_elems(removed, indices.filter(|&i| !self.contains(i) && old.contains(i)))
+    }
+}
+
+/// Formats each element of a `BitSet` into a comma-separated list.
+fn fmt_elems<T: Idx>(w: &mut impl io::Write, elems: impl Iterator<Item = T>) -> io::Result<()> {
+    let mut first = true;
+    for idx in elems {
+        if first {
+            first = false;
+        } else {
+            write!(w, ",")?;
+        }
+
+        write!(w, "{:?}", idx)?;
+    }
+
+    Ok(())
+}
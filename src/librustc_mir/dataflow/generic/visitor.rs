@@ -0,0 +1,142 @@
+use rustc::mir::{self, BasicBlock, Location};
+
+use super::{Analysis, Results};
+
+/// Calls the corresponding method in `ResultsVisitor` for every location in the given `blocks` of
+/// a `mir::Body` with the dataflow state at that location.
+///
+/// Blocks are visited in the order they are yielded by `blocks`. Within a block, statements are
+/// visited in order, so the state at each location is computed without seeking.
+pub fn visit_results<F>(
+    body: &'mir mir::Body<'tcx>,
+    blocks: impl IntoIterator<Item = BasicBlock>,
+    results: &impl ResultsVisitable<'tcx, FlowState = F>,
+    vis: &mut impl ResultsVisitor<'mir, 'tcx, FlowState = F>,
+) {
+    let mut state = results.new_flow_state(body);
+
+    for block in blocks {
+        let block_data = &body[block];
+        results.reset_to_block_start(&mut state, block);
+        vis.visit_block_start(&state, block_data, block);
+
+        for (statement_index, stmt) in block_data.statements.iter().enumerate() {
+            let loc = Location { block, statement_index };
+            vis.visit_statement(&state, stmt, loc);
+            results.reconstruct_statement_effect(&mut state, stmt, loc);
+        }
+
+        let loc = body.terminator_loc(block);
+        let term = block_data.terminator();
+        vis.visit_terminator(&state, term, loc);
+        results.reconstruct_terminator_effect(&mut state, term, loc);
+
+        vis.visit_block_end(&state, block_data, block);
+    }
+}
+
+/// A visitor that is passed the dataflow state at each location in a `mir::Body`.
+///
+/// See `visit_results`.
+pub trait ResultsVisitor<'mir, 'tcx> {
+    type FlowState;
+
+    /// Called with the entry set of `block`, before any of its statements are visited.
+    fn visit_block_start(
+        &mut self,
+        _state: &Self::FlowState,
+        _block_data: &'mir mir::BasicBlockData<'tcx>,
+        _block: BasicBlock,
+    ) {}
+
+    /// Called with the dataflow state immediately before the primary effect of `statement`.
+    fn visit_statement(
+        &mut self,
+        _state: &Self::FlowState,
+        _statement: &'mir mir::Statement<'tcx>,
+        _location: Location,
+    ) {}
+
+    /// Called with the dataflow state immediately before the primary effect of `terminator`.
+    fn visit_terminator(
+        &mut self,
+        _state: &Self::FlowState,
+        _terminator: &'mir mir::Terminator<'tcx>,
+        _location: Location,
+    ) {}
+
+    /// Called with the dataflow state after the primary effect of the terminator of `block`.
+    ///
+    /// This does not include the effects along its outgoing edges, such as a successful return from
+    /// a `Call` terminator.
+    fn visit_block_end(
+        &mut self,
+        _state: &Self::FlowState,
+        _block_data: &'mir mir::BasicBlockData<'tcx>,
+        _block: BasicBlock,
+    ) {}
+}
+
+/// Things that can be visited by a `ResultsVisitor`.
+///
+/// This is implemented for the `Results` of a single analysis. Visiting the results of several
+/// analyses at once only requires a type that holds all of them and implements this trait by
+/// forwarding to each one, with a `FlowState` that holds the state of each analysis.
+pub trait ResultsVisitable<'tcx> {
+    type FlowState;
+
+    /// Creates an empty `FlowState` to hold the dataflow state at each location.
+    fn new_flow_state(&self, body: &mir::Body<'tcx>) -> Self::FlowState;
+
+    /// Overwrites `state` with the entry set of `block`.
+    fn reset_to_block_start(&self, state: &mut Self::FlowState, block: BasicBlock);
+
+    /// Updates `state` with the effect of `statement`.
+    fn reconstruct_statement_effect(
+        &self,
+        state: &mut Self::FlowState,
+        statement: &mir::Statement<'tcx>,
+        location: Location,
+    );
+
+    /// Updates `state` with the effect of `terminator`.
+    fn reconstruct_terminator_effect(
+        &self,
+        state: &mut Self::FlowState,
+        terminator: &mir::Terminator<'tcx>,
+        location: Location,
+    );
+}
+
+impl<'tcx, A> ResultsVisitable<'tcx> for Results<'tcx, A>
+where
+    A: Analysis<'tcx>,
+{
+    type FlowState = A::Domain;
+
+    fn new_flow_state(&self, body: &mir::Body<'tcx>) -> Self::FlowState {
+        self.analysis.bottom_value(body)
+    }
+
+    fn reset_to_block_start(&self, state: &mut Self::FlowState, block: BasicBlock) {
+        state.clone_from(&self.entry_sets[block]);
+    }
+
+    fn reconstruct_statement_effect(
+        &self,
+        state: &mut Self::FlowState,
+        statement: &mir::Statement<'tcx>,
+        location: Location,
+    ) {
+        self.analysis.apply_statement_effect(state, statement, location);
+    }
+
+    fn reconstruct_terminator_effect(
+        &self,
+        state: &mut Self::FlowState,
+        terminator: &mir::Terminator<'tcx>,
+        location: Location,
+    ) {
+        self.analysis.apply_terminator_effect(state, terminator, location);
+    }
+}
@@ -103,10 +103,13 @@ impl<'tcx> Visitor<'tcx> for BorrowedLocalsVisitor<'_> {
     fn visit_rvalue(&mut self,
                     rvalue: &Rvalue<'tcx>,
                     location: Location) {
-        if let Rvalue::Ref(_, _, ref place) = *rvalue {
-            if let Some(local) = find_local(place) {
-                self.trans.gen(local);
+        match *rvalue {
+            Rvalue::Ref(_, _, ref place) | Rvalue::AddressOf(_, ref place) => {
+                if let Some(local) = find_local(place) {
+                    self.trans.gen(local);
+                }
             }
+            _ => {}
         }
 
         self.super_rvalue(rvalue, location)
@@ -124,14 +124,22 @@ impl<'tcx> Visitor<'tcx> for TransferFunction<'_, '_, 'tcx> {
         rvalue: &mir::Rvalue<'tcx>,
         location: Location,
     ) {
-        if let mir::Rvalue::Ref(_, kind, ref borrowed_place) = *rvalue {
-            if self.borrow_allows_mutation(kind, borrowed_place) {
-                match borrowed_place.base {
-                    mir::PlaceBase::Local(borrowed_local) if !borrowed_place.is_indirect()
-                        => self.trans.gen(borrowed_local),
-
-                    _ => (),
-                }
+        let (allows_mutation, borrowed_place) = match *rvalue {
+            mir::Rvalue::Ref(_, kind, ref borrowed_place) => {
+                (self.borrow_allows_mutation(kind, borrowed_place), borrowed_place)
+            }
+            // A raw pointer to the place can always be used to mutate it, either directly or
+            // after a cast to `*mut T`.
+            mir::Rvalue::AddressOf(_, ref borrowed_place) => (true, borrowed_place),
+            _ => return self.super_rvalue(rvalue, location),
+        };
+
+        if allows_mutation {
+            match borrowed_place.base {
+                mir::PlaceBase::Local(borrowed_local) if !borrowed_place.is_indirect()
+                    => self.trans.gen(borrowed_local),
+
+                _ => (),
             }
         }
 
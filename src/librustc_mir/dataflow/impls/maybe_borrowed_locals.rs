@@ -0,0 +1,144 @@
+use rustc::mir::visit::Visitor;
+use rustc::mir::{self, BasicBlock, Local, Location};
+use rustc_index::bit_set::BitSet;
+
+use std::io;
+
+use crate::dataflow::generic::{self as dataflow, DebugState, JoinSemiLattice};
+
+/// The locals that may have been borrowed at a given point in the program, split by the kind of
+/// borrow. See `MaybeBorrowedLocals`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BorrowedLocals {
+    /// Locals that may be pointed to by a shared reference (`&`).
+    ///
+    /// Through such a reference, a local can only be mutated if its type has interior mutability.
+    pub shared: BitSet<Local>,
+
+    /// Locals that may be pointed to by a mutable (`&mut`) or unique reference, or by a raw pointer
+    /// derived from one.
+    pub mutable: BitSet<Local>,
+}
+
+impl BorrowedLocals {
+    /// Returns `true` if `local` may have been borrowed by any kind of reference.
+    pub fn contains(&self, local: Local) -> bool {
+        self.shared.contains(local) || self.mutable.contains(local)
+    }
+}
+
+impl JoinSemiLattice for BorrowedLocals {
+    fn join(&mut self, other: &Self) -> bool {
+        // Don't short-circuit: both sets must be joined.
+        let shared = self.shared.join(&other.shared);
+        let mutable = self.mutable.join(&other.mutable);
+        shared || mutable
+    }
+}
+
+impl DebugState for BorrowedLocals {
+    fn fmt_state(&self, w: &mut impl io::Write) -> io::Result<()> {
+        write!(w, "shared: ")?;
+        self.shared.fmt_state(w)?;
+        write!(w, " mut: ")?;
+        self.mutable.fmt_state(w)
+    }
+}
+
+/// Whether a shared or a mutable borrow of a `Local` may have been created before a given point in
+/// the program.
+///
+/// Unlike `IndirectlyMutableLocals`, shared and mutable borrows are tracked separately, so a
+/// consumer can tell whether a `Local` may have been mutated indirectly in a way that is relevant
+/// to it. For example, a shared borrow only allows a value to be mutated inside an `UnsafeCell`.
+///
+/// A `StorageDead` statement invalidates every borrow of that `Local`, so it is removed from both
+/// sets. Drop terminators mutably borrow the dropped location, but that borrow cannot outlive the
+/// terminator, so they have no effect either.
+pub struct MaybeBorrowedLocals;
+
+impl dataflow::Analysis<'tcx> for MaybeBorrowedLocals {
+    type Domain = BorrowedLocals;
+
+    const NAME: &'static str = "maybe_borrowed_locals";
+
+    fn bottom_value(&self, body: &mir::Body<'tcx>) -> Self::Domain {
+        // bottom = unborrowed
+        BorrowedLocals {
+            shared: BitSet::new_empty(body.local_decls.len()),
+            mutable: BitSet::new_empty(body.local_decls.len()),
+        }
+    }
+
+    fn initialize_start_block(&self, _body: &mir::Body<'tcx>, _state: &mut Self::Domain) {
+        // Nothing is borrowed on function entry
+    }
+
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::Domain,
+        statement: &mir::Statement<'tcx>,
+        location: Location,
+    ) {
+        TransferFunction { state }.visit_statement(statement, location);
+
+        if let mir::StatementKind::StorageDead(local) = statement.kind {
+            state.shared.remove(local);
+            state.mutable.remove(local);
+        }
+    }
+
+    fn apply_terminator_effect(
+        &self,
+        state: &mut Self::Domain,
+        terminator: &mir::Terminator<'tcx>,
+        location: Location,
+    ) {
+        TransferFunction { state }.visit_terminator(terminator, location);
+    }
+
+    fn apply_call_return_effect(
+        &self,
+        _state: &mut Self::Domain,
+        _block: BasicBlock,
+        _func: &mir::Operand<'tcx>,
+        _args: &[mir::Operand<'tcx>],
+        _return_place: &mir::Place<'tcx>,
+    ) {
+        // Nothing to do when a call returns successfully
+    }
+}
+
+/// A `Visitor` that defines the transfer function for `MaybeBorrowedLocals`.
+struct TransferFunction<'a> {
+    state: &'a mut BorrowedLocals,
+}
+
+impl<'tcx> Visitor<'tcx> for TransferFunction<'_> {
+    fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: Location) {
+        let (is_mut, borrowed_place) = match *rvalue {
+            mir::Rvalue::Ref(_, kind, ref borrowed_place) => match kind {
+                mir::BorrowKind::Mut { .. } | mir::BorrowKind::Unique => (true, borrowed_place),
+                mir::BorrowKind::Shared | mir::BorrowKind::Shallow => (false, borrowed_place),
+            },
+            mir::Rvalue::AddressOf(mutability, ref borrowed_place) => {
+                (mutability == mir::Mutability::Mut, borrowed_place)
+            }
+            _ => return self.super_rvalue(rvalue, location),
+        };
+
+        match borrowed_place.base {
+            mir::PlaceBase::Local(borrowed_local) if !borrowed_place.is_indirect() => {
+                if is_mut {
+                    self.state.mutable.insert(borrowed_local);
+                } else {
+                    self.state.shared.insert(borrowed_local);
+                }
+            }
+
+            _ => (),
+        }
+
+        self.super_rvalue(rvalue, location);
+    }
+}
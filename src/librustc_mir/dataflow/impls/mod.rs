@@ -20,10 +20,12 @@ use super::on_lookup_result_bits;
 
 mod borrowed_locals;
 mod indirect_mutation;
+mod maybe_borrowed_locals;
 mod storage_liveness;
 
 pub use self::borrowed_locals::*;
 pub use self::indirect_mutation::IndirectlyMutableLocals;
+pub use self::maybe_borrowed_locals::{BorrowedLocals, MaybeBorrowedLocals};
 pub use self::storage_liveness::*;
 
 pub(super) mod borrows;
@@ -25,12 +25,15 @@ pub use self::impls::EverInitializedPlaces;
 pub use self::impls::borrows::Borrows;
 pub use self::impls::HaveBeenBorrowedLocals;
 pub use self::impls::IndirectlyMutableLocals;
+pub use self::impls::{BorrowedLocals, MaybeBorrowedLocals};
 pub use self::at_location::{FlowAtLocation, FlowsAtLocation};
 pub(crate) use self::drop_flag_effects::*;
+pub(crate) use self::dead_unwinds::{dead_unwinds, find_dead_unwinds};
 
 use self::move_paths::MoveData;
 
 mod at_location;
+mod dead_unwinds;
 pub mod drop_flag_effects;
 pub mod generic;
 mod graphviz;
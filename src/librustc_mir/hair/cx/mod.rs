@@ -97,6 +97,16 @@ impl<'a, 'tcx> Cx<'a, 'tcx> {
     pub fn control_flow_destroyed(self) -> Vec<(Span, String)> {
         self.control_flow_destroyed
     }
+
+    /// Returns `true` if `&&` and `||` in a `const` or `static` can be lowered to real control
+    /// flow, i.e. if either of the feature gates that enable control flow in a const context is
+    /// active.
+    ///
+    /// When this returns `true`, control flow is never destroyed.
+    pub fn const_eval_supports_control_flow(&self) -> bool {
+        let features = self.tcx.features();
+        features.const_if_match || features.const_loop
+    }
 }
 
 impl<'a, 'tcx> Cx<'a, 'tcx> {
@@ -20,18 +20,22 @@ impl<'a, 'tcx> PatCtxt<'a, 'tcx> {
     /// Converts an evaluated constant to a pattern (if possible).
     /// This means aggregate values (like structs and enums) are converted
     /// to a pattern that matches the value (as if you'd compared via structural equality).
+    ///
+    /// `mir_structural_match_violation` is the `CustomEq` qualif of the constant, computed by the
+    /// MIR const-checker. It is `false` if the value cannot contain a non-structural-match type.
     pub(super) fn const_to_pat(
         &self,
         cv: &'tcx ty::Const<'tcx>,
         id: hir::HirId,
         span: Span,
+        mir_structural_match_violation: bool,
     ) -> Pat<'tcx> {
         debug!("const_to_pat: cv={:#?} id={:?}", cv, id);
         debug!("const_to_pat: cv.ty={:?} span={:?}", cv.ty, span);
 
         self.tcx.infer_ctxt().enter(|infcx| {
             let mut convert = ConstToPat::new(self, id, span, infcx);
-            convert.to_pat(cv)
+            convert.to_pat(cv, mir_structural_match_violation)
         })
     }
 }
@@ -78,7 +82,11 @@ impl<'a, 'tcx> ConstToPat<'a, 'tcx> {
         ty::type_marked_structural(self.id, self.span, &self.infcx, ty)
     }
 
-    fn to_pat(&mut self, cv: &'tcx ty::Const<'tcx>) -> Pat<'tcx> {
+    fn to_pat(
+        &mut self,
+        cv: &'tcx ty::Const<'tcx>,
+        mir_structural_match_violation: bool,
+    ) -> Pat<'tcx> {
         // This method is just a wrapper handling a validity check; the heavy lifting is
         // performed by the recursive `recur` method, which is not meant to be
         // invoked except by this method.
@@ -95,6 +103,14 @@ impl<'a, 'tcx> ConstToPat<'a, 'tcx> {
             let structural = self.search_for_structural_match_violation(cv.ty);
             debug!("search_for_structural_match_violation cv.ty: {:?} returned: {:?}",
                    cv.ty, structural);
+
+            // The type of the constant is not structural-match, but the MIR const-checker
+            // found that its value cannot contain any of the offending parts (e.g. it is
+            // `None::<NonStructural>`), so the pattern behaves as if it were.
+            if structural.is_some() && !mir_structural_match_violation {
+                return inlined_const_as_pat;
+            }
+
             if let Some(non_sm_ty) = structural {
                 let adt_def = match non_sm_ty {
                     ty::NonStructuralMatchTy::Adt(adt_def) => adt_def,
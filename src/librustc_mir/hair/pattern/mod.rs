@@ -9,6 +9,7 @@ pub(crate) use self::check_match::check_match;
 use crate::hair::util::UserAnnotatedTyHelpers;
 use crate::hair::constant::*;
 
+use rustc::lint;
 use rustc::mir::{Field, BorrowKind, Mutability};
 use rustc::mir::{UserTypeProjection};
 use rustc::mir::interpret::{GlobalId, ConstValue, get_slice_bytes, sign_extend};
@@ -867,7 +868,24 @@ impl<'a, 'tcx> PatCtxt<'a, 'tcx> {
                         };
                         match self.tcx.at(span).const_eval(self.param_env.and(cid)) {
                             Ok(value) => {
-                                let pattern = self.const_to_pat(value, id, span);
+                                // Don't peek inside trait associated constants, just like
+                                // the `Qualif`s themselves.
+                                let def_id = instance.def_id();
+                                let is_trait_item = self.tcx.trait_of_item(def_id).is_some();
+                                let mir_structural_match_violation =
+                                    is_trait_item || self.tcx.mir_const_qualif(def_id).custom_eq;
+                                if self.include_lint_checks
+                                    && !is_trait_item
+                                    && self.tcx.mir_const_qualif(def_id).contains_ptr
+                                {
+                                    self.lint_pointer_in_pattern(id, span);
+                                }
+                                let pattern = self.const_to_pat(
+                                    value,
+                                    id,
+                                    span,
+                                    mir_structural_match_violation,
+                                );
                                 if !is_associated_const {
                                     return pattern;
                                 }
@@ -924,6 +942,22 @@ impl<'a, 'tcx> PatCtxt<'a, 'tcx> {
         }
     }
 
+    /// Lints a constant pattern whose value contains a function pointer or a raw pointer. Pointer
+    /// addresses are not guaranteed to be unique or stable, so such a pattern may not match.
+    fn lint_pointer_in_pattern(&self, id: hir::HirId, span: Span) {
+        let mut lint = self.tcx.struct_span_lint_hir(
+            lint::builtin::POINTER_STRUCTURAL_MATCH,
+            id,
+            span,
+            "constant containing a function pointer or raw pointer used in a pattern",
+        );
+        lint.note(
+            "the address of a function or of the data behind a raw pointer may differ between \
+             uses of the constant, so this pattern may not match as expected",
+        );
+        lint.emit();
+    }
+
     /// Converts literals, paths and negation of literals to patterns.
     /// The special case for negation exists to allow things like `-128_i8`
     /// which would overflow if we tried to evaluate `128_i8` and then negate
@@ -934,7 +968,7 @@ impl<'a, 'tcx> PatCtxt<'a, 'tcx> {
                 let ty = self.tables.expr_ty(expr);
                 match lit_to_const(&lit.node, self.tcx, ty, false) {
                     Ok(val) => {
-                        *self.const_to_pat(val, expr.hir_id, lit.span).kind
+                        *self.const_to_pat(val, expr.hir_id, lit.span, false).kind
                     },
                     Err(LitToConstError::UnparseableFloat) => {
                         self.errors.push(PatternError::FloatBug);
@@ -952,7 +986,7 @@ impl<'a, 'tcx> PatCtxt<'a, 'tcx> {
                 };
                 match lit_to_const(&lit.node, self.tcx, ty, true) {
                     Ok(val) => {
-                        *self.const_to_pat(val, expr.hir_id, lit.span).kind
+                        *self.const_to_pat(val, expr.hir_id, lit.span, false).kind
                     },
                     Err(LitToConstError::UnparseableFloat) => {
                         self.errors.push(PatternError::FloatBug);
@@ -7,7 +7,7 @@ use syntax::symbol::sym;
 use rustc_apfloat::ieee::{Single, Double};
 use rustc_apfloat::{Float, FloatConvert};
 use rustc::mir::interpret::{
-    Scalar, InterpResult, PointerArithmetic,
+    Scalar, ScalarMaybeUndef, InterpResult, PointerArithmetic,
 };
 use rustc::mir::CastKind;
 
@@ -162,6 +162,17 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
             return Ok(*src);
         }
 
+        // Casting a pointer to a pointer-sized integer, or such an integer back to a raw pointer,
+        // does not need the actual address if the machine can keep it symbolic.
+        if let Immediate::Scalar(ScalarMaybeUndef::Scalar(Scalar::Ptr(_))) = *src {
+            if (dest_layout.ty.is_integral() || dest_layout.ty.is_unsafe_ptr()) &&
+                dest_layout.size == self.pointer_size() &&
+                M::keep_ptr_in_int_cast(self)
+            {
+                return Ok(*src);
+            }
+        }
+
         // For all remaining casts, we either
         // (a) cast a raw ptr to usize, or
         // (b) cast from an integer-like (including bool, char, enums).
@@ -237,12 +248,13 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
                 let v = f.to_i128(width).value;
                 Ok(Scalar::from_int(v, Size::from_bits(width as u64)))
             },
+            // A conversion only produces a NaN from a NaN, so there is nothing to report.
             // float -> f32
             Float(FloatTy::F32) =>
-                Ok(Scalar::from_f32(f.convert(&mut false).value)),
+                Ok(Scalar::from_f32(self.canonicalize_nan(f.convert(&mut false).value, true)?)),
             // float -> f64
             Float(FloatTy::F64) =>
-                Ok(Scalar::from_f64(f.convert(&mut false).value)),
+                Ok(Scalar::from_f64(self.canonicalize_nan(f.convert(&mut false).value, true)?)),
             // That's it.
             _ => bug!("invalid float to {:?} cast", dest_ty),
         }
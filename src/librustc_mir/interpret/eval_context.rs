@@ -736,7 +736,13 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
             if log_enabled!(::log::Level::Trace) {
                 self.memory.dump_alloc(ptr.alloc_id);
             }
+            // The allocation might be already removed by static interning, in which case it no
+            // longer counts towards the memory used by the program.
+            let size = self.memory.alloc_map.get(ptr.alloc_id).map(|(_, alloc)| alloc.size);
             self.memory.deallocate_local(ptr)?;
+            if let Some(size) = size {
+                M::after_deallocation(self, size);
+            }
         };
         Ok(())
     }
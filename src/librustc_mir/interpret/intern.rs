@@ -56,7 +56,7 @@ enum InternMode {
     /// UnsafeCell is OK in the value of a constant: `const FOO = Cell::new(0)` creates
     /// a new cell every time it is used.
     ConstBase,
-    /// `UnsafeCell` ICEs.
+    /// `UnsafeCell` is an error: it would be shared between all uses of the constant.
     Const,
 }
 
@@ -179,14 +179,16 @@ for
                 // We are crossing over an `UnsafeCell`, we can mutate again. This means that
                 // References we encounter inside here are interned as pointing to mutable
                 // allocations.
+                // `UnsafeCell`s are not allowed behind references in constants. Const qualification
+                // prevents this, but it can be skipped with `-Zunleash-the-miri-inside-of-you`. If
+                // this were allowed one would be able to change a constant at one use site and
+                // other use sites could observe that mutation.
+                if self.mode == InternMode::Const {
+                    throw_unsup!(Unsupported(
+                        "encountered interior mutable data behind a reference in a constant".into()
+                    ))
+                }
                 let old = std::mem::replace(&mut self.mutability, Mutability::Mutable);
-                assert_ne!(
-                    self.mode, InternMode::Const,
-                    "UnsafeCells are not allowed behind references in constants. This should have \
-                    been prevented statically by const qualification. If this were allowed one \
-                    would be able to change a constant at one use site and other use sites could \
-                    observe that mutation.",
-                );
                 let walked = self.walk_aggregate(mplace, fields);
                 self.mutability = old;
                 return walked;
@@ -278,6 +280,9 @@ pub fn intern_const_alloc_recursive<M: CompileTimeMachine<'mir, 'tcx>>(
     ecx: &mut InterpCx<'mir, 'tcx, M>,
     // The `mutability` of the place, ignoring the type.
     place_mut: Option<hir::Mutability>,
+    // Whether the `HasMutInterior` qualif holds for the value, i.e. whether it may contain an
+    // `UnsafeCell` that is not behind a reference.
+    has_mut_interior: bool,
     ret: MPlaceTy<'tcx>,
 ) -> InterpResult<'tcx> {
     let tcx = ecx.tcx;
@@ -297,14 +302,17 @@ pub fn intern_const_alloc_recursive<M: CompileTimeMachine<'mir, 'tcx>>(
     let mut ref_tracking = RefTracking::new((ret, base_mutability, base_intern_mode));
     let leftover_allocations = &mut FxHashSet::default();
 
-    // start with the outermost allocation
+    // start with the outermost allocation. Its type might not be `Freeze` even though const
+    // qualification determined that the actual value contains no `UnsafeCell`
+    // (e.g. `static FOO: Option<Cell<i32>> = None;`), in which case we can intern it immutably.
+    let base_ty = if has_mut_interior { Some(ret.layout.ty) } else { None };
     intern_shallow(
         ecx,
         leftover_allocations,
         base_intern_mode,
         ret.ptr.to_ptr()?.alloc_id,
         base_mutability,
-        Some(ret.layout.ty)
+        base_ty,
     )?;
 
     while let Some(((mplace, mutability, mode), _)) = ref_tracking.todo.pop() {
@@ -316,6 +324,11 @@ pub fn intern_const_alloc_recursive<M: CompileTimeMachine<'mir, 'tcx>>(
             mutability,
         }.visit_value(mplace);
         if let Err(error) = interned {
+            // Interior mutable data behind a reference can't be interned soundly, so the whole
+            // constant must be rejected.
+            if let err_unsup!(Unsupported(_)) = error.kind {
+                return Err(error);
+            }
             // This can happen when e.g. the tag of an enum is not a valid discriminant. We do have
             // to read enum discriminants in order to find references in enum variant fields.
             if let err_unsup!(ValidationFailure(_)) = error.kind {
@@ -4,14 +4,16 @@
 
 use syntax::symbol::Symbol;
 use syntax_pos::Span;
-use rustc::ty;
-use rustc::ty::layout::{LayoutOf, Primitive, Size};
+use rustc::ty::{self, Ty};
+use rustc::ty::layout::{self, IntegerExt, LayoutOf, Primitive, Size};
 use rustc::ty::subst::SubstsRef;
 use rustc::hir::def_id::DefId;
 use rustc::ty::TyCtxt;
 use rustc::mir::{
     self, BinOp,
-    interpret::{InterpResult, Scalar, GlobalId, ConstValue}
+    interpret::{
+        CheckInAllocMsg, InterpResult, Scalar, GlobalId, ConstValue, sign_extend, truncate,
+    },
 };
 
 use super::{
@@ -120,6 +122,20 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
                 self.write_scalar(location.ptr, dest)?;
             }
 
+            "discriminant_value" => {
+                let place = self.deref_operand(args[0])?;
+                let (discr_val, _) = self.read_discriminant(place.into())?;
+                // Like codegen, sign-extend signed discriminants to the `u64` return type.
+                let discr_val = match place.layout.ty.kind {
+                    ty::Adt(adt, _) if adt.is_enum() && adt.repr.discr_type().is_signed() => {
+                        let size = layout::Integer::from_attr(self, adt.repr.discr_type()).size();
+                        truncate(sign_extend(discr_val, size), dest.layout.size)
+                    }
+                    _ => discr_val,
+                };
+                self.write_scalar(Scalar::from_uint(discr_val, dest.layout.size), dest)?;
+            }
+
             "min_align_of" |
             "pref_align_of" |
             "needs_drop" |
@@ -309,6 +325,27 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
                 }
             }
 
+            "offset" => {
+                let ptr = self.read_scalar(args[0])?.not_undef()?;
+                let offset_count = self.read_scalar(args[1])?.to_machine_isize(self)?;
+                let pointee_ty = substs.type_at(0);
+
+                let offset_ptr = self.ptr_offset_inbounds(ptr, pointee_ty, offset_count)?;
+                self.write_scalar(offset_ptr, dest)?;
+            }
+
+            "arith_offset" => {
+                let ptr = self.read_scalar(args[0])?.not_undef()?;
+                let offset_count = self.read_scalar(args[1])?.to_machine_isize(self)?;
+                let pointee_ty = substs.type_at(0);
+
+                // A type's size is at most `isize::max_value()`, so this cannot overflow.
+                let pointee_size = self.layout_of(pointee_ty)?.size.bytes() as i64;
+                let offset_bytes = offset_count.wrapping_mul(pointee_size);
+                let offset_ptr = ptr.ptr_wrapping_signed_offset(offset_bytes, self);
+                self.write_scalar(offset_ptr, dest)?;
+            }
+
             "transmute" => {
                 self.copy_op_transmute(args[0], dest)?;
             }
@@ -395,6 +432,13 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
             let line = self.read_scalar(line.into())?.to_u32()?;
             let col = self.read_scalar(col.into())?.to_u32()?;
             throw_panic!(Panic { msg, file, line, col })
+        } else if Some(def_id) == self.tcx.lang_items().panic_bounds_check_fn() {
+            // &core::panic::Location { &'static str, u32, u32 }, usize, usize
+            assert!(args.len() == 3);
+
+            let index = self.read_scalar(args[1])?.to_machine_usize(self)?;
+            let len = self.read_scalar(args[2])?.to_machine_usize(self)?;
+            throw_panic!(BoundsCheck { len, index })
         } else if Some(def_id) == self.tcx.lang_items().begin_panic_fn() {
             assert!(args.len() == 2);
             // &'static str, &(&'static str, u32, u32)
@@ -443,4 +487,91 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
         }
         self.binop_ignore_overflow(BinOp::Div, a, b, dest)
     }
+
+    /// Offsets `ptr` by `offset_count` values of type `pointee_ty`, with the semantics of the
+    /// `offset` intrinsic: both `ptr` and the result must be in bounds of the same allocation.
+    ///
+    /// An integer pointer is treated as an allocation of size 0, so it can only be offset by 0,
+    /// unless it is NULL, which cannot be offset at all.
+    pub fn ptr_offset_inbounds(
+        &self,
+        ptr: Scalar<M::PointerTag>,
+        pointee_ty: Ty<'tcx>,
+        offset_count: i64,
+    ) -> InterpResult<'tcx, Scalar<M::PointerTag>> {
+        // A type's size is at most `isize::max_value()`, so this cannot overflow.
+        let pointee_size = self.layout_of(pointee_ty)?.size.bytes() as i64;
+        let offset_bytes = match offset_count.checked_mul(pointee_size) {
+            Some(offset_bytes) => offset_bytes,
+            None => throw_ub_format!("inbounds pointer arithmetic: overflow computing offset"),
+        };
+        // Being in bounds cannot rely on wrapping around the address space, so rule out overflow
+        // first. This also catches offsetting to before the start of an allocation.
+        let offset_ptr = match ptr.ptr_signed_offset(offset_bytes, self) {
+            Ok(offset_ptr) => offset_ptr,
+            Err(_) => throw_ub_format!("inbounds pointer arithmetic: overflow computing offset"),
+        };
+
+        // All the memory between `ptr` and `offset_ptr` must be part of the same allocation.
+        // Unlike for a memory access, alignment does not matter.
+        let min_ptr = if offset_bytes >= 0 { ptr } else { offset_ptr };
+        let size = (offset_bytes as i128).abs() as u64;
+        self.memory.check_ptr_access_align(
+            min_ptr,
+            Size::from_bytes(size),
+            None,
+            CheckInAllocMsg::PointerArithmeticTest,
+        )?;
+        Ok(offset_ptr)
+    }
+
+    /// Copies `count` values of the pointee type of `src` from `src` to `dest`, with the
+    /// semantics of the `copy` and `copy_nonoverlapping` intrinsics. Undefined bytes and
+    /// relocations are copied along with the data.
+    pub fn copy_intrinsic(
+        &mut self,
+        src: OpTy<'tcx, M::PointerTag>,
+        dest: OpTy<'tcx, M::PointerTag>,
+        count: OpTy<'tcx, M::PointerTag>,
+        nonoverlapping: bool,
+    ) -> InterpResult<'tcx> {
+        let count = self.read_scalar(count)?.to_machine_usize(self)?;
+        let layout = self.layout_of(src.layout.ty.builtin_deref(true).unwrap().ty)?;
+        let size = match layout.size.checked_mul(count, self) {
+            Some(size) => size,
+            None => throw_ub_format!("overflow computing the size of a copy of {} values", count),
+        };
+
+        // Both pointers have to be valid for the whole access even if it is empty.
+        let src = self.read_scalar(src)?.not_undef()?;
+        let src = self.memory.check_ptr_access(src, size, layout.align.abi)?;
+        let dest = self.read_scalar(dest)?.not_undef()?;
+        let dest = self.memory.check_ptr_access(dest, size, layout.align.abi)?;
+
+        if let (Some(src), Some(dest)) = (src, dest) {
+            self.memory.copy(src, dest, size, nonoverlapping)?;
+        }
+        Ok(())
+    }
+
+    /// Sets `count` values of the pointee type of `dest` to `byte` in every byte, with the
+    /// semantics of the `write_bytes` intrinsic.
+    pub fn write_bytes_intrinsic(
+        &mut self,
+        dest: OpTy<'tcx, M::PointerTag>,
+        byte: OpTy<'tcx, M::PointerTag>,
+        count: OpTy<'tcx, M::PointerTag>,
+    ) -> InterpResult<'tcx> {
+        let byte = self.read_scalar(byte)?.to_u8()?;
+        let count = self.read_scalar(count)?.to_machine_usize(self)?;
+        let layout = self.layout_of(dest.layout.ty.builtin_deref(true).unwrap().ty)?;
+        let size = match layout.size.checked_mul(count, self) {
+            Some(size) => size,
+            None => throw_ub_format!("overflow computing the size of a write of {} values", count),
+        };
+
+        let dest = self.read_scalar(dest)?.not_undef()?;
+        self.memory.check_ptr_access(dest, size, layout.align.abi)?;
+        self.memory.write_bytes(dest, std::iter::repeat(byte).take(size.bytes() as usize))
+    }
 }
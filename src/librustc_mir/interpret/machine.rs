@@ -8,6 +8,7 @@ use std::hash::Hash;
 use rustc::hir::def_id::DefId;
 use rustc::mir;
 use rustc::ty::{self, Ty, TyCtxt};
+use rustc::ty::layout::Size;
 use syntax_pos::Span;
 
 use super::{
@@ -197,6 +198,52 @@ pub trait Machine<'mir, 'tcx>: Sized {
         right: ImmTy<'tcx, Self::PointerTag>,
     ) -> InterpResult<'tcx, (Scalar<Self::PointerTag>, bool, Ty<'tcx>)>;
 
+    /// Called when a floating-point operation produced a NaN from non-NaN inputs, before it gets
+    /// replaced by the canonical NaN. The NaN computed at runtime may have a different sign and
+    /// payload.
+    #[inline]
+    fn nan_produced(
+        _ecx: &InterpCx<'mir, 'tcx, Self>,
+    ) -> InterpResult<'tcx> {
+        Ok(())
+    }
+
+    /// Whether a pointer cast to a pointer-sized integer stays a symbolic "pointer with offset"
+    /// value instead of being turned into an address via `ptr_to_int`. Such integers can be
+    /// copied around and cast back to pointers; only operations that need their actual bits go
+    /// through `ptr_to_int`.
+    #[inline]
+    fn keep_ptr_in_int_cast(
+        _ecx: &InterpCx<'mir, 'tcx, Self>,
+    ) -> bool {
+        false
+    }
+
+    /// Called before the interpreter creates an allocation of `size` bytes for a local or a
+    /// return place.
+    #[inline]
+    fn before_allocation(
+        _ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        _size: Size,
+    ) -> InterpResult<'tcx> {
+        Ok(())
+    }
+
+    /// Called after the interpreter deallocated the `size` bytes backing a local.
+    #[inline]
+    fn after_deallocation(
+        _ecx: &mut InterpCx<'mir, 'tcx, Self>,
+        _size: Size,
+    ) {}
+
+    /// Whether `const_eval_select` calls its compile-time variant instead of its runtime one.
+    #[inline]
+    fn const_eval_select_compile_time(
+        _ecx: &InterpCx<'mir, 'tcx, Self>,
+    ) -> bool {
+        false
+    }
+
     /// Heap allocations via the `box` keyword.
     fn box_alloc(
         ecx: &mut InterpCx<'mir, 'tcx, Self>,
@@ -219,6 +266,14 @@ pub trait Machine<'mir, 'tcx>: Sized {
         Ok(())
     }
 
+    /// Called before the interpreted program accesses a `#[thread_local]` static.
+    fn before_access_thread_local_static(
+        _tcx: TyCtxt<'tcx>,
+        _def_id: DefId,
+    ) -> InterpResult<'tcx> {
+        Ok(())
+    }
+
     /// Called to initialize the "extra" state of an allocation and make the pointers
     /// it contains (in relocations) tagged.  The way we construct allocations is
     /// to always first construct it without extra and then add the extra.
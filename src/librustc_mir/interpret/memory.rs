@@ -617,7 +617,7 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> Memory<'mir, 'tcx, M> {
         } else {
             match self.tcx.alloc_map.lock().get(id) {
                 Some(GlobalAlloc::Function(instance)) => Ok(FnVal::Instance(instance)),
-                _ => throw_unsup!(ExecuteMemory),
+                _ => throw_unsup!(InvalidFunctionPointer),
             }
         }
     }
@@ -626,10 +626,12 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> Memory<'mir, 'tcx, M> {
         &self,
         ptr: Scalar<M::PointerTag>,
     ) -> InterpResult<'tcx, FnVal<'tcx, M::ExtraFnVal>> {
-        let ptr = self.force_ptr(ptr)?; // We definitely need a pointer value.
-        if ptr.offset.bytes() != 0 {
-            throw_unsup!(InvalidFunctionPointer)
-        }
+        // Only a pointer to the start of a function is a function pointer. In particular, an
+        // integer that cannot be turned into a pointer is not one.
+        let ptr = match self.force_ptr(ptr) {
+            Ok(ptr) if ptr.offset.bytes() == 0 => ptr,
+            _ => throw_unsup!(InvalidFunctionPointer),
+        };
         self.get_fn_alloc(ptr.alloc_id)
     }
 
@@ -235,6 +235,15 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
             // Don't touch unsized
             return Ok(None);
         }
+        if let ty::Adt(def, _) = mplace.layout.ty.kind {
+            if def.is_union() {
+                // Unions are copied as raw bytes, even if their layout has a scalar ABI: some of
+                // their bytes may be undefined while others are not (e.g. in a `MaybeUninit`
+                // that is only partially initialized), and a scalar is either entirely defined
+                // or not at all.
+                return Ok(None);
+            }
+        }
 
         let ptr = match self.check_mplace_access(mplace, None)
             .expect("places should be checked on creation")
@@ -86,15 +86,19 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
         return (Scalar::from_bool(res), false, self.tcx.types.bool);
     }
 
+    /// Float arithmetic always rounds to the nearest representable value of the operand type,
+    /// with ties to even. This matches targets that implement IEEE 754 arithmetic directly, but
+    /// not e.g. x87, which computes intermediate results with extended precision.
     fn binary_float_op<F: Float + Into<Scalar<M::PointerTag>>>(
         &self,
         bin_op: mir::BinOp,
         ty: Ty<'tcx>,
         l: F,
         r: F,
-    ) -> (Scalar<M::PointerTag>, bool, Ty<'tcx>) {
+    ) -> InterpResult<'tcx, (Scalar<M::PointerTag>, bool, Ty<'tcx>)> {
         use rustc::mir::BinOp::*;
 
+        let nan_input = l.is_nan() || r.is_nan();
         let (val, ty) = match bin_op {
             Eq => (Scalar::from_bool(l == r), self.tcx.types.bool),
             Ne => (Scalar::from_bool(l != r), self.tcx.types.bool),
@@ -102,14 +106,35 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
             Le => (Scalar::from_bool(l <= r), self.tcx.types.bool),
             Gt => (Scalar::from_bool(l > r), self.tcx.types.bool),
             Ge => (Scalar::from_bool(l >= r), self.tcx.types.bool),
-            Add => ((l + r).value.into(), ty),
-            Sub => ((l - r).value.into(), ty),
-            Mul => ((l * r).value.into(), ty),
-            Div => ((l / r).value.into(), ty),
-            Rem => ((l % r).value.into(), ty),
+            Add => (self.canonicalize_nan((l + r).value, nan_input)?.into(), ty),
+            Sub => (self.canonicalize_nan((l - r).value, nan_input)?.into(), ty),
+            Mul => (self.canonicalize_nan((l * r).value, nan_input)?.into(), ty),
+            Div => (self.canonicalize_nan((l / r).value, nan_input)?.into(), ty),
+            Rem => (self.canonicalize_nan((l % r).value, nan_input)?.into(), ty),
             _ => bug!("invalid float op: `{:?}`", bin_op),
         };
-        return (val, false, ty);
+        Ok((val, false, ty))
+    }
+
+    /// Replaces a NaN result of a floating-point operation by the canonical NaN (a positive quiet
+    /// NaN with no payload), so that the result of evaluation does not depend on how the NaN was
+    /// produced. Negation only flips the sign bit, at runtime as well, so it does not need this.
+    ///
+    /// Only a NaN computed from non-NaN inputs (`nan_input` is `false`) is reported to the
+    /// machine. One that was propagated from an input was already reported when it was produced.
+    pub(super) fn canonicalize_nan<F: Float>(
+        &self,
+        f: F,
+        nan_input: bool,
+    ) -> InterpResult<'tcx, F> {
+        if f.is_nan() {
+            if !nan_input {
+                M::nan_produced(self)?;
+            }
+            Ok(F::NAN)
+        } else {
+            Ok(f)
+        }
     }
 
     fn binary_int_op(
@@ -292,12 +317,12 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
                 let ty = left.layout.ty;
                 let left = left.to_scalar()?;
                 let right = right.to_scalar()?;
-                Ok(match fty {
+                match fty {
                     FloatTy::F32 =>
                         self.binary_float_op(bin_op, ty, left.to_f32()?, right.to_f32()?),
                     FloatTy::F64 =>
                         self.binary_float_op(bin_op, ty, left.to_f64()?, right.to_f64()?),
-                })
+                }
             }
             _ if left.layout.ty.is_integral() => {
                 // the RHS type can be different, e.g. for shifts -- but it has to be integral, too
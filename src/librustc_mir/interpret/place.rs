@@ -13,6 +13,7 @@ use rustc::ty::layout::{
 };
 use rustc::ty::TypeFoldable;
 use rustc_macros::HashStable;
+use syntax::symbol::sym;
 
 use super::{
     GlobalId, AllocId, Allocation, Scalar, InterpResult, Pointer, PointerArithmetic,
@@ -612,6 +613,10 @@ where
             }
 
             StaticKind::Static => {
+                if self.tcx.has_attr(place_static.def_id, sym::thread_local) {
+                    M::before_access_thread_local_static(*self.tcx, place_static.def_id)?;
+                }
+
                 let ty = place_static.ty;
                 assert!(!ty.needs_subst());
                 let layout = self.layout_of(ty)?;
@@ -1000,6 +1005,7 @@ where
                         let local_layout = self.layout_of_local(&self.stack[frame], local, None)?;
                         let (size, align) = self.size_and_align_of(meta, local_layout)?
                             .expect("Cannot allocate for non-dyn-sized type");
+                        M::before_allocation(self, size)?;
                         let ptr = self.memory.allocate(size, align, MemoryKind::Stack);
                         let mplace = MemPlace { ptr: ptr.into(), align, meta };
                         if let Some(value) = old_val {
@@ -1036,9 +1042,10 @@ where
         &mut self,
         layout: TyLayout<'tcx>,
         kind: MemoryKind<M::MemoryKinds>,
-    ) -> MPlaceTy<'tcx, M::PointerTag> {
+    ) -> InterpResult<'tcx, MPlaceTy<'tcx, M::PointerTag>> {
+        M::before_allocation(self, layout.size)?;
         let ptr = self.memory.allocate(layout.size, layout.align.abi, kind);
-        MPlaceTy::from_aligned_ptr(ptr, layout)
+        Ok(MPlaceTy::from_aligned_ptr(ptr, layout))
     }
 
     pub fn write_discriminant_index(
@@ -47,7 +47,6 @@ impl<'mir, 'tcx> InfiniteLoopDetector<'mir, 'tcx> {
     pub fn observe_and_analyze(
         &mut self,
         tcx: TyCtxt<'tcx>,
-        span: Span,
         memory: &Memory<'mir, 'tcx, CompileTimeInterpreter<'mir, 'tcx>>,
         stack: &[Frame<'mir, 'tcx>],
     ) -> InterpResult<'tcx, ()> {
@@ -58,11 +57,6 @@ impl<'mir, 'tcx> InfiniteLoopDetector<'mir, 'tcx> {
         let hash = hasher.finish::<u64>();
 
         // Check if we know that hash already
-        if self.hashes.is_empty() {
-            // FIXME(#49980): make this warning a lint
-            tcx.sess.span_warn(span,
-                "Constant evaluating a complex constant, this might take some time");
-        }
         if self.hashes.insert(hash) {
             // No collision
             return Ok(())
@@ -3,10 +3,12 @@
 //! The main entry point is the `step` method.
 
 use rustc::mir;
-use rustc::ty::layout::LayoutOf;
-use rustc::mir::interpret::{InterpResult, Scalar, PointerArithmetic};
+use rustc::ty::layout::{LayoutOf, Size};
+use rustc::mir::interpret::{
+    InterpResult, Scalar, ScalarMaybeUndef, PointerArithmetic, write_target_uint,
+};
 
-use super::{InterpCx, Machine};
+use super::{InterpCx, Machine, OpTy, ImmTy, Immediate};
 
 /// Classify whether an operator is "left-homogeneous", i.e., the LHS has the
 /// same type as the result.
@@ -220,18 +222,27 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
                 let length = dest.len(self)?;
 
                 if let Some(first_ptr) = self.check_mplace_access(dest, None)? {
-                    // Write the first.
-                    let first = self.mplace_field(dest, 0)?;
-                    self.copy_op(op, first.into())?;
-
-                    if length > 1 {
-                        let elem_size = first.layout.size;
-                        // Copy the rest. This is performance-sensitive code
-                        // for big static/const arrays!
-                        let rest_ptr = first_ptr.offset(elem_size, self)?;
-                        self.memory.copy_repeatedly(
-                            first_ptr, rest_ptr, elem_size, length - 1, /*nonoverlapping:*/true
-                        )?;
+                    if let Some(elem_bytes) = self.repeatable_scalar_bytes(op)? {
+                        // Fill the whole array with the bytes of the element in one go, like a
+                        // `memset`. This is performance-sensitive code for big static/const
+                        // arrays!
+                        let size = dest.layout.size.bytes() as usize;
+                        let bytes = elem_bytes.into_iter().cycle().take(size);
+                        self.memory.write_bytes(first_ptr.into(), bytes)?;
+                    } else {
+                        // Write the first.
+                        let first = self.mplace_field(dest, 0)?;
+                        self.copy_op(op, first.into())?;
+
+                        if length > 1 {
+                            let elem_size = first.layout.size;
+                            // Copy the rest. This is performance-sensitive code
+                            // for big static/const arrays!
+                            let rest_ptr = first_ptr.offset(elem_size, self)?;
+                            self.memory.copy_repeatedly(
+                                first_ptr, rest_ptr, elem_size, length - 1, /*nonoverlapping:*/true
+                            )?;
+                        }
                     }
                 }
             }
@@ -258,6 +269,12 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
                 self.write_immediate(place.to_ref(), dest)?;
             }
 
+            AddressOf(_, ref place) => {
+                let src = self.eval_place(place)?;
+                let place = self.force_allocation(src)?;
+                self.write_immediate(place.to_ref(), dest)?;
+            }
+
             NullaryOp(mir::NullOp::Box, _) => {
                 M::box_alloc(self, dest)?;
             }
@@ -308,4 +325,26 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
         }
         Ok(())
     }
+
+    /// Returns the bytes that make up `op` if it is a fully initialized scalar without
+    /// relocations that covers its entire layout, so that repeating `op` amounts to repeating
+    /// these bytes.
+    fn repeatable_scalar_bytes(
+        &self,
+        op: OpTy<'tcx, M::PointerTag>,
+    ) -> InterpResult<'tcx, Option<Vec<u8>>> {
+        let (data, size) = match self.try_read_immediate(op)? {
+            Ok(ImmTy {
+                imm: Immediate::Scalar(ScalarMaybeUndef::Scalar(Scalar::Raw { data, size })),
+                ..
+            }) => (data, size),
+            _ => return Ok(None),
+        };
+        if Size::from_bytes(u64::from(size)) != op.layout.size {
+            return Ok(None);
+        }
+        let mut bytes = vec![0; usize::from(size)];
+        write_target_uint(self.tcx.data_layout.endian, &mut bytes, data).unwrap();
+        Ok(Some(bytes))
+    }
 }
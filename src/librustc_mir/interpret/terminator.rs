@@ -4,6 +4,7 @@ use rustc::{mir, ty};
 use rustc::ty::Instance;
 use rustc::ty::layout::{self, TyLayout, LayoutOf};
 use syntax::source_map::Span;
+use syntax::symbol::sym;
 use rustc_target::spec::abi::Abi;
 
 use super::{
@@ -275,8 +276,11 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
         }
 
         match instance.def {
-            ty::InstanceDef::Intrinsic(..) => {
+            ty::InstanceDef::Intrinsic(def_id) => {
                 assert!(caller_abi == Abi::RustIntrinsic || caller_abi == Abi::PlatformIntrinsic);
+                if self.tcx.item_name(def_id) == sym::const_eval_select {
+                    return self.eval_const_eval_select(span, instance, args, ret, unwind);
+                }
                 return M::call_intrinsic(self, span, instance, args, ret, unwind);
             }
             ty::InstanceDef::VtableShim(..) |
@@ -482,6 +486,36 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
         return Ok(())
     }
 
+    /// Evaluate a call to the `const_eval_select` intrinsic by calling the variant chosen by the
+    /// machine with the fields of the tupled arguments.
+    fn eval_const_eval_select(
+        &mut self,
+        span: Span,
+        instance: ty::Instance<'tcx>,
+        args: &[OpTy<'tcx, M::PointerTag>],
+        ret: Option<(PlaceTy<'tcx, M::PointerTag>, mir::BasicBlock)>,
+        unwind: Option<mir::BasicBlock>,
+    ) -> InterpResult<'tcx> {
+        let callee_ty = if M::const_eval_select_compile_time(self) {
+            instance.substs.type_at(1)
+        } else {
+            instance.substs.type_at(2)
+        };
+        let callee = match callee_ty.kind {
+            ty::FnDef(def_id, substs) =>
+                ty::Instance::resolve(*self.tcx, self.param_env, def_id, substs)
+                    .ok_or_else(|| err_inval!(TooGeneric))?,
+            _ => throw_unsup!(Unsupported(format!(
+                "calling `const_eval_select` with `{}`, which is not a function item",
+                callee_ty,
+            ))),
+        };
+        let fields = (0..args[0].layout.fields.count() as u64)
+            .map(|field| self.operand_field(args[0], field))
+            .collect::<InterpResult<'tcx, Vec<_>>>()?;
+        self.eval_fn_call(FnVal::Instance(callee), span, Abi::Rust, &fields, ret, unwind)
+    }
+
     fn drop_in_place(
         &mut self,
         place: PlaceTy<'tcx, M::PointerTag>,
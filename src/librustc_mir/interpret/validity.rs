@@ -504,6 +504,12 @@ impl<'rt, 'mir, 'tcx, M: Machine<'mir, 'tcx>> ValueVisitor<'mir, 'tcx, M>
         op: OpTy<'tcx, M::PointerTag>,
         layout: &layout::Scalar,
     ) -> InterpResult<'tcx> {
+        // `bool` and `char` are fully checked by `visit_primitive`, which can say what was
+        // expected in terms of the type rather than of its valid range.
+        match op.layout.ty.kind {
+            ty::Bool | ty::Char => return Ok(()),
+            _ => {}
+        }
         let value = self.ecx.read_scalar(op)?;
         // Determine the allowed range
         let (lo, hi) = layout.valid_range.clone().into_inner();
@@ -194,7 +194,9 @@ use rustc::util::nodemap::{FxHashSet, FxHashMap, DefIdMap};
 use rustc::util::common::time;
 
 use rustc_index::bit_set::GrowableBitSet;
+use rustc_target::spec::abi::Abi;
 use rustc_data_structures::sync::{MTRef, MTLock, ParallelIterator, par_iter};
+use syntax::symbol::sym;
 
 use std::iter;
 
@@ -635,6 +637,15 @@ impl<'a, 'tcx> MirVisitor<'tcx> for MirNeighborCollector<'a, 'tcx> {
                     &callee_ty,
                 );
                 visit_fn_use(self.tcx, callee_ty, true, &mut self.output);
+
+                // `const_eval_select` gets codegened as a direct call to its runtime variant.
+                if let ty::FnDef(def_id, substs) = callee_ty.kind {
+                    if tcx.fn_sig(def_id).abi() == Abi::RustIntrinsic &&
+                        tcx.item_name(def_id) == sym::const_eval_select
+                    {
+                        visit_fn_use(self.tcx, substs.type_at(2), true, &mut self.output);
+                    }
+                }
             }
             mir::TerminatorKind::Drop { ref location, .. } |
             mir::TerminatorKind::DropAndReplace { ref location, .. } => {
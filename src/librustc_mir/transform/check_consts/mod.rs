@@ -5,16 +5,20 @@
 //! it finds operations that are invalid in a certain context.
 
 use rustc::hir::{self, def_id::DefId};
-use rustc::mir;
-use rustc::ty::{self, TyCtxt};
+use rustc::mir::{self, BasicBlock};
+use rustc::ty::{self, Ty, TyCtxt};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_index::bit_set::BitSet;
+use syntax_pos::DUMMY_SP;
 
+use std::cell::RefCell;
 use std::fmt;
 
 pub use self::qualifs::Qualif;
 
 pub mod ops;
 pub mod qualifs;
-mod resolver;
+pub mod resolver;
 pub mod validation;
 
 /// Information about the item currently being const-checked, as well as a reference to the global
@@ -25,6 +29,19 @@ pub struct Item<'mir, 'tcx> {
     pub def_id: DefId,
     pub param_env: ty::ParamEnv<'tcx>,
     pub const_kind: Option<ConstKind>,
+
+    /// The basic blocks that can be reached from the entry block. Blocks that are only reachable
+    /// through a `SwitchInt` on a literal (e.g. the body of `if false { ... }`) are not included.
+    pub reachable: BitSet<BasicBlock>,
+
+    /// Additional qualifs, registered by the caller, whose value in the return place is computed
+    /// along with the built-in ones.
+    pub extra_qualifs: Option<&'mir qualifs::ExtraQualifs<'mir>>,
+
+    /// Memoized results of `is_freeze` and `needs_drop` for the types seen in this item. Both
+    /// require trait selection, and bodies tend to query the same few types over and over.
+    freeze_cache: RefCell<FxHashMap<Ty<'tcx>, bool>>,
+    needs_drop_cache: RefCell<FxHashMap<Ty<'tcx>, bool>>,
 }
 
 impl Item<'mir, 'tcx> {
@@ -33,8 +50,22 @@ impl Item<'mir, 'tcx> {
         def_id: DefId,
         body: &'mir mir::Body<'tcx>,
     ) -> Self {
-        let param_env = tcx.param_env(def_id);
         let const_kind = ConstKind::for_item(tcx, def_id);
+        Self::with_const_kind(tcx, def_id, body, const_kind)
+    }
+
+    /// Like `new`, but checks `body` as if it belonged to the given kind of item.
+    ///
+    /// This lets us find out whether a non-const `fn` would pass const checking if it were declared
+    /// as a `const fn`.
+    pub fn with_const_kind(
+        tcx: TyCtxt<'tcx>,
+        def_id: DefId,
+        body: &'mir mir::Body<'tcx>,
+        const_kind: Option<ConstKind>,
+    ) -> Self {
+        let param_env = tcx.param_env(def_id);
+        let reachable = reachable_blocks(tcx, param_env, body);
 
         Item {
             body,
@@ -42,9 +73,45 @@ impl Item<'mir, 'tcx> {
             def_id,
             param_env,
             const_kind,
+            reachable,
+            extra_qualifs: None,
+            freeze_cache: Default::default(),
+            needs_drop_cache: Default::default(),
+        }
+    }
+
+    /// Computes the given `ExtraQualifs` for this item as well as the built-in qualifs.
+    pub fn with_extra_qualifs(mut self, extra_qualifs: &'mir qualifs::ExtraQualifs<'mir>) -> Self {
+        self.extra_qualifs = Some(extra_qualifs);
+        self
+    }
+
+    /// Returns the value of each registered `ExtraQualif` in the return place, keyed by name.
+    pub fn extra_qualifs_in_return_place(&self) -> FxHashMap<&'static str, bool> {
+        match self.extra_qualifs {
+            Some(extra) => extra.in_return_place(self),
+            None => Default::default(),
         }
     }
 
+    /// Returns `true` if `ty` is `Freeze` in the `ParamEnv` of this item.
+    pub fn is_freeze(&self, ty: Ty<'tcx>) -> bool {
+        let Item { tcx, param_env, .. } = *self;
+        *self.freeze_cache
+            .borrow_mut()
+            .entry(ty)
+            .or_insert_with(|| ty.is_freeze(tcx, param_env, DUMMY_SP))
+    }
+
+    /// Returns `true` if values of type `ty` need to be dropped in the `ParamEnv` of this item.
+    pub fn needs_drop(&self, ty: Ty<'tcx>) -> bool {
+        let Item { tcx, param_env, .. } = *self;
+        *self.needs_drop_cache
+            .borrow_mut()
+            .entry(ty)
+            .or_insert_with(|| ty.needs_drop(tcx, param_env))
+    }
+
     /// Returns the kind of const context this `Item` represents (`const`, `static`, etc.).
     ///
     /// Panics if this `Item` is not const.
@@ -53,6 +120,89 @@ impl Item<'mir, 'tcx> {
     }
 }
 
+/// Returns the basic blocks of `body` that are reachable from the entry block, following only the
+/// target that will actually be taken when a `SwitchInt` has a literal discriminant.
+fn reachable_blocks(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    body: &mir::Body<'tcx>,
+) -> BitSet<BasicBlock> {
+    let mut reachable = BitSet::new_empty(body.basic_blocks().len());
+    let mut stack = vec![mir::START_BLOCK];
+
+    while let Some(bb) = stack.pop() {
+        if !reachable.insert(bb) {
+            continue;
+        }
+
+        let block = &body[bb];
+        let terminator = block.terminator();
+        if let mir::TerminatorKind::SwitchInt { discr, switch_ty, values, targets } =
+            &terminator.kind
+        {
+            if let Some(bits) = const_discriminant(tcx, param_env, block, discr, *switch_ty) {
+                let target = values.iter()
+                    .position(|&v| v == bits)
+                    .map_or_else(|| *targets.last().unwrap(), |i| targets[i]);
+                stack.push(target);
+                continue;
+            }
+        }
+
+        stack.extend(terminator.successors().cloned());
+    }
+
+    reachable
+}
+
+/// Returns the value of `discr` if it is a literal, or a local that was assigned a literal by the
+/// last statement in `block` that could modify it.
+fn const_discriminant(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    block: &mir::BasicBlockData<'tcx>,
+    discr: &mir::Operand<'tcx>,
+    switch_ty: Ty<'tcx>,
+) -> Option<u128> {
+    use mir::{Operand, Rvalue, StatementKind};
+
+    let constant = match discr {
+        Operand::Constant(constant) => constant,
+
+        Operand::Copy(place) | Operand::Move(place) => {
+            let local = place.as_local()?;
+
+            // Any statement other than these might write to `local`, possibly through a pointer.
+            let last_write = block.statements.iter().rev().find(|stmt| match stmt.kind {
+                StatementKind::StorageLive(_)
+                | StatementKind::StorageDead(_)
+                | StatementKind::FakeRead(..)
+                | StatementKind::AscribeUserType(..)
+                | StatementKind::Nop => false,
+                _ => true,
+            })?;
+
+            match &last_write.kind {
+                StatementKind::Assign(box (lhs, Rvalue::Use(Operand::Constant(constant))))
+                    if lhs.as_local() == Some(local) => constant,
+
+                _ => return None,
+            }
+        }
+    };
+
+    // Only look at literals. Evaluating anything else (e.g. a path to a `const` item) could require
+    // the qualifs of the item that is being checked, which would be a query cycle.
+    match constant.literal.val {
+        ty::ConstKind::Value(_) => {
+            let size = tcx.layout_of(param_env.and(switch_ty)).ok()?.size;
+            constant.literal.val.try_to_bits(size)
+        }
+
+        _ => None,
+    }
+}
+
 /// The kinds of items which require compile-time evaluation.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ConstKind {
@@ -108,7 +258,10 @@ impl fmt::Display for ConstKind {
 }
 
 /// Returns `true` if this `DefId` points to one of the official `panic` lang items.
+///
+/// This includes `panic_bounds_check`, which is what checked indexing panics with.
 pub fn is_lang_panic_fn(tcx: TyCtxt<'tcx>, def_id: DefId) -> bool {
     Some(def_id) == tcx.lang_items().panic_fn() ||
+    Some(def_id) == tcx.lang_items().panic_bounds_check_fn() ||
     Some(def_id) == tcx.lang_items().begin_panic_fn()
 }
@@ -1,9 +1,13 @@
 //! Concrete error types for all operations which may be invalid in a certain const context.
 
-use rustc::hir::def_id::DefId;
+use rustc::hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc::hir::{self, def::{DefKind, Res}, def_id::{DefId, LOCAL_CRATE}};
+use rustc::lint;
 use rustc::mir::BorrowKind;
 use rustc::session::config::nightly_options;
-use rustc::ty::TyCtxt;
+use rustc::ty::{self, TyCtxt};
+use rustc_errors::{Applicability, Diagnostic};
+use rustc_target::spec::abi::Abi;
 use syntax::feature_gate::feature_err;
 use syntax::symbol::sym;
 use syntax_pos::{Span, Symbol};
@@ -17,21 +21,39 @@ pub trait NonConstOp: std::fmt::Debug {
     /// Whether this operation can be evaluated by miri.
     const IS_SUPPORTED_IN_MIRI: bool = true;
 
-    /// Returns a boolean indicating whether the feature gate that would allow this operation is
-    /// enabled, or `None` if such a feature gate does not exist.
-    fn feature_gate(_tcx: TyCtxt<'tcx>) -> Option<bool> {
+    /// The name of this operation (e.g. `MutBorrow`), used to refer to it in diagnostics.
+    fn name() -> &'static str {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
+    /// Returns the feature gate that would allow this operation, or `None` if such a feature gate
+    /// does not exist.
+    ///
+    /// If there is one, the `Validator` adds the name of the gate and its tracking issue to the
+    /// error for this operation.
+    fn feature_gate() -> Option<Symbol> {
         None
     }
 
+    /// Returns `true` if enabling `feature_gate` would allow this operation in the given item.
+    fn is_gated_in_item(&self, _item: &Item<'_, '_>) -> bool {
+        Self::feature_gate().is_some()
+    }
+
     /// Returns `true` if this operation is allowed in the given item.
     ///
     /// This check should assume that we are not in a non-const `fn`, where all operations are
     /// legal.
     fn is_allowed_in_item(&self, item: &Item<'_, '_>) -> bool {
-        Self::feature_gate(item.tcx).unwrap_or(false)
+        Self::feature_gate().map_or(false, |gate| item.tcx.features().enabled(gate))
     }
 
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    /// Adds the error for this operation at `span` to `errors`.
+    ///
+    /// Errors are emitted by the `Validator` once the whole item has been checked, so that it can
+    /// merge the errors for several operations of the same kind.
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
         let mut err = struct_span_err!(
             item.tcx.sess,
             span,
@@ -45,7 +67,7 @@ pub trait NonConstOp: std::fmt::Debug {
             err.note("Remember: you can't use a function call inside a const's initialization \
                       expression! However, you can use it anywhere else.");
         }
-        err.emit();
+        err.buffer(errors);
     }
 }
 
@@ -53,8 +75,63 @@ pub trait NonConstOp: std::fmt::Debug {
 #[derive(Debug)]
 pub struct Downcast;
 impl NonConstOp for Downcast {
-    fn feature_gate(tcx: TyCtxt<'_>) -> Option<bool> {
-        Some(tcx.features().const_if_match)
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_if_match)
+    }
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        feature_err(
+            &item.tcx.sess.parse_sess,
+            sym::const_if_match,
+            span,
+            &format!("matching on enum variants in {}s is unstable", item.const_kind()),
+        )
+        .buffer(errors);
+    }
+}
+
+/// Floating-point arithmetic, or a cast to or from a float.
+#[derive(Debug)]
+pub struct FloatingPointOp;
+impl NonConstOp for FloatingPointOp {
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_fn_floating_point_arithmetic)
+    }
+
+    fn is_allowed_in_item(&self, item: &Item<'_, '_>) -> bool {
+        // Floating-point arithmetic is always allowed in `const`s and `static`s.
+        item.const_kind() != ConstKind::ConstFn
+            || item.tcx.features().const_fn_floating_point_arithmetic
+    }
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        feature_err(
+            &item.tcx.sess.parse_sess,
+            sym::const_fn_floating_point_arithmetic,
+            span,
+            &format!("floating point arithmetic is not allowed in {}s", item.const_kind()),
+        )
+        .buffer(errors);
+    }
+}
+
+/// A call to a trait method through a `dyn Trait` object.
+///
+/// With `#![feature(const_trait_impl)]`, the call is checked dynamically: the interpreter resolves
+/// the vtable slot and rejects the call if the method in the underlying impl is not `const`.
+#[derive(Debug)]
+pub struct FnCallDynTrait;
+impl NonConstOp for FnCallDynTrait {
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_trait_impl)
+    }
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        feature_err(
+            &item.tcx.sess.parse_sess, sym::const_trait_impl, span,
+            &format!("calling methods on trait objects in {}s is unstable", item.const_kind()),
+        )
+        .buffer(errors);
     }
 }
 
@@ -62,11 +139,14 @@ impl NonConstOp for Downcast {
 #[derive(Debug)]
 pub struct FnCallIndirect;
 impl NonConstOp for FnCallIndirect {
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
-        let mut err = item.tcx.sess.struct_span_err(
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        struct_span_err!(
+            item.tcx.sess,
             span,
-            &format!("function pointers are not allowed in const fn"));
-        err.emit();
+            E0746,
+            "function pointers are not allowed in const fn",
+        )
+        .buffer(errors);
     }
 }
 
@@ -74,7 +154,7 @@ impl NonConstOp for FnCallIndirect {
 #[derive(Debug)]
 pub struct FnCallNonConst(pub DefId);
 impl NonConstOp for FnCallNonConst {
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
         let mut err = struct_span_err!(
             item.tcx.sess,
             span,
@@ -83,8 +163,143 @@ impl NonConstOp for FnCallNonConst {
              tuple structs and tuple variants",
             item.const_kind(),
         );
-        err.emit();
+
+        // With method calls and glob imports, it is not always obvious which function is called.
+        let FnCallNonConst(def_id) = *self;
+        let path = item.tcx.def_path_str(def_id);
+        err.note(&format!(
+            "the function called here is `{}` from crate `{}`",
+            path,
+            item.tcx.crate_name(def_id.krate),
+        ));
+
+        let trait_methods = same_name_trait_methods(item.tcx, def_id);
+        if !trait_methods.is_empty() {
+            let trait_methods: Vec<_> = trait_methods
+                .into_iter()
+                .map(|method| format!("`{}`", item.tcx.def_path_str(method)))
+                .collect();
+            err.note(&format!(
+                "this calls the inherent method `{}`, not {}",
+                path,
+                trait_methods.join(" or "),
+            ));
+        }
+
+        if let Some(const_span) = const_fn_suggestion_span(item.tcx, def_id) {
+            if item.tcx.passes_as_const_fn(def_id) {
+                err.span_suggestion(
+                    const_span,
+                    &format!("consider making `{}` a `const fn`", item.tcx.def_path_str(def_id)),
+                    "const ".to_owned(),
+                    Applicability::MachineApplicable,
+                );
+            }
+        }
+
+        // A `static` whose value can only be computed at runtime is still possible, it just can't
+        // be initialized directly.
+        if let ConstKind::Static | ConstKind::StaticMut = item.const_kind() {
+            err.note(
+                "to initialize a static with a value computed at runtime, initialize it lazily, \
+                 e.g. with `std::sync::Once`",
+            );
+        }
+
+        err.buffer(errors);
+    }
+}
+
+/// Returns the methods with the same name as the inherent method with the given `DefId` that
+/// belong to a trait implemented for its `Self` type.
+///
+/// A method call could have resolved to one of these if the inherent method did not exist. Blanket
+/// impls are ignored, since we don't check whether their bounds hold.
+fn same_name_trait_methods(tcx: TyCtxt<'_>, def_id: DefId) -> Vec<DefId> {
+    let method = match tcx.opt_associated_item(def_id) {
+        Some(method) if method.method_has_self_argument => method,
+        _ => return vec![],
+    };
+
+    let impl_def_id = match method.container {
+        ty::ImplContainer(impl_def_id) if tcx.trait_id_of_impl(impl_def_id).is_none() => {
+            impl_def_id
+        }
+        _ => return vec![],
+    };
+
+    let self_ty = tcx.type_of(impl_def_id);
+    let mut trait_methods = vec![];
+    for &trait_def_id in tcx.all_traits(LOCAL_CRATE) {
+        let trait_method = tcx.associated_items(trait_def_id).find(|item| {
+            item.method_has_self_argument && item.ident.name == method.ident.name
+        });
+
+        if let Some(trait_method) = trait_method {
+            let mut is_implemented = false;
+            tcx.for_each_relevant_impl(trait_def_id, self_ty, |impl_def_id| {
+                if let ty::Param(_) = tcx.type_of(impl_def_id).kind {
+                    return;
+                }
+                is_implemented = true;
+            });
+
+            if is_implemented {
+                trait_methods.push(trait_method.def_id);
+            }
+        }
+    }
+
+    trait_methods
+}
+
+/// Returns the span where `const` would need to be inserted to turn the function with the given
+/// `DefId` into a `const fn`, or `None` if we shouldn't suggest doing so.
+///
+/// We only suggest this for free functions and inherent methods in the current crate that use the
+/// Rust ABI and are not `async`. Changing an item of the standard library is not something users
+/// can do.
+fn const_fn_suggestion_span(tcx: TyCtxt<'_>, def_id: DefId) -> Option<Span> {
+    if tcx.features().staged_api {
+        return None;
+    }
+
+    let hir_id = tcx.hir().as_local_hir_id(def_id)?;
+    let (sig, vis) = match tcx.hir().get(hir_id) {
+        hir::Node::Item(hir::Item { kind: hir::ItemKind::Fn(sig, ..), vis, .. }) => (sig, vis),
+
+        hir::Node::ImplItem(hir::ImplItem {
+            kind: hir::ImplItemKind::Method(sig, _),
+            vis,
+            defaultness,
+            ..
+        }) => {
+            let impl_def_id = tcx.hir().get_parent_did(hir_id);
+            if defaultness.is_default() || tcx.impl_trait_ref(impl_def_id).is_some() {
+                return None;
+            }
+
+            (sig, vis)
+        }
+
+        _ => return None,
+    };
+
+    if sig.header.abi != Abi::Rust || sig.header.asyncness != hir::IsAsync::NotAsync {
+        return None;
     }
+
+    // `const` goes right after the visibility, before `unsafe` or `fn`.
+    let sig_span = tcx.def_span(def_id);
+    let span = match vis.node {
+        hir::VisibilityKind::Inherited => sig_span.shrink_to_lo(),
+        _ => {
+            let after_vis = vis.span.between(sig_span.shrink_to_hi());
+            tcx.sess.source_map().span_until_non_whitespace(after_vis).shrink_to_hi()
+        }
+    };
+
+    Some(span)
 }
 
 /// A function call where the callee is not a function definition or function pointer, e.g. a
@@ -95,6 +310,54 @@ impl NonConstOp for FnCallNonConst {
 pub struct FnCallOther;
 impl NonConstOp for FnCallOther {
     const IS_SUPPORTED_IN_MIRI: bool = false;
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        struct_span_err!(
+            item.tcx.sess,
+            span,
+            E0747,
+            "calls in {}s are limited to functions, tuple structs and tuple variants",
+            item.const_kind(),
+        )
+        .buffer(errors);
+    }
+}
+
+/// A call to a trait method that is not known to resolve to a `const` impl.
+#[derive(Debug)]
+pub struct FnCallTraitMethod(pub DefId);
+impl NonConstOp for FnCallTraitMethod {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        let FnCallTraitMethod(def_id) = *self;
+        let tcx = item.tcx;
+        let trait_id = tcx.trait_of_item(def_id).expect("`FnCallTraitMethod` of non-trait method");
+
+        let mut err = struct_span_err!(
+            tcx.sess,
+            span,
+            E0015,
+            "calls in {}s are limited to constant functions, \
+             tuple structs and tuple variants",
+            item.const_kind(),
+        );
+
+        let trait_name = tcx.def_path_str(trait_id);
+        let method = format!("`{}::{}`", trait_name, tcx.item_name(def_id));
+        if tcx.features().const_trait_impl {
+            err.note(&format!(
+                "{} is a method of the trait `{}`, and the impl it resolves to is not known to \
+                 be `const`",
+                method, trait_name,
+            ));
+        } else {
+            err.note(&format!(
+                "{} is a method of the trait `{}`, and const trait impls are not yet supported",
+                method, trait_name,
+            ));
+        }
+
+        err.buffer(errors);
+    }
 }
 
 /// A call to a `#[unstable]` const fn or `#[rustc_const_unstable]` function.
@@ -103,19 +366,66 @@ impl NonConstOp for FnCallOther {
 #[derive(Debug)]
 pub struct FnCallUnstable(pub DefId, pub Symbol);
 impl NonConstOp for FnCallUnstable {
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
         let FnCallUnstable(def_id, feature) = *self;
 
-        let mut err = item.tcx.sess.struct_span_err(span,
-            &format!("`{}` is not yet stable as a const fn",
-                    item.tcx.def_path_str(def_id)));
+        let mut err = struct_span_err!(item.tcx.sess, span, E0748,
+                                       "`{}` is not yet stable as a const fn",
+                                       item.tcx.def_path_str(def_id));
         if nightly_options::is_nightly_build() {
-            help!(&mut err,
-                  "add `#![feature({})]` to the \
-                   crate attributes to enable",
-                  feature);
+            // Crate attributes must come before any item, so insert it before the first token of
+            // the crate root.
+            let crate_root = item.tcx.hir().krate().span.shrink_to_lo();
+            err.span_suggestion(
+                crate_root,
+                &format!("add `#![feature({})]` to the crate attributes to enable", feature),
+                format!("#![feature({})]\n", feature),
+                Applicability::MachineApplicable,
+            );
         }
-        err.emit();
+        err.buffer(errors);
+    }
+}
+
+/// A call to `IntoIterator::into_iter` or `Iterator::next` in the desugaring of a `for` loop that
+/// does not resolve to a `const fn`.
+#[derive(Debug)]
+pub struct ForLoopIterator(pub DefId);
+impl NonConstOp for ForLoopIterator {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        let features = item.tcx.features();
+        if !(features.const_loop && features.const_if_match && features.const_trait_impl) {
+            // This should be caught by the HIR const-checker.
+            item.tcx.sess.delay_span_bug(
+                span,
+                "`for` loops are forbidden in a const context",
+            );
+            return;
+        }
+
+        let mut err = struct_span_err!(
+            item.tcx.sess,
+            span,
+            E0749,
+            "`for` loops in {}s require `const` impls of `IntoIterator` and `Iterator`",
+            item.const_kind(),
+        );
+        err.note(&format!("`{}` is not a `const fn`", item.tcx.def_path_str(self.0)));
+        err.buffer(errors);
+    }
+}
+
+/// An argument to a `#[rustc_args_required_const]` function (or the index argument of a SIMD
+/// shuffle) that cannot be promoted to a constant.
+#[derive(Debug)]
+pub struct NonConstArgument(pub usize);
+impl NonConstOp for NonConstArgument {
+    const IS_SUPPORTED_IN_MIRI: bool = false;
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        struct_span_err!(item.tcx.sess, span, E0752,
+                         "argument {} is required to be a constant", self.0 + 1)
+            .buffer(errors);
     }
 }
 
@@ -124,7 +434,7 @@ pub struct HeapAllocation;
 impl NonConstOp for HeapAllocation {
     const IS_SUPPORTED_IN_MIRI: bool = false;
 
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
         let mut err = struct_span_err!(item.tcx.sess, span, E0010,
                                        "allocations are not allowed in {}s", item.const_kind());
         err.span_label(span, format!("allocation not allowed in {}s", item.const_kind()));
@@ -136,18 +446,83 @@ impl NonConstOp for HeapAllocation {
                  be done at compile time."
             );
         }
-        err.emit();
+        err.help("use a fixed-size array or a reference to `'static` data instead");
+        err.help("if the value must live on the heap, allocate it at runtime instead");
+
+        if let Some(parts) = static_ref_suggestion(item.tcx, item.def_id, span) {
+            err.multipart_suggestion(
+                "use a reference to `'static` data instead of a `Box`",
+                parts,
+                Applicability::MaybeIncorrect,
+            );
+        }
+
+        err.buffer(errors);
     }
 }
 
+/// Returns a rewrite of `const X: Box<T> = box EXPR;` into `const X: &'static T = &EXPR;` if the
+/// allocation at `span` is the entire initializer of the item and `EXPR` is a literal or an array.
+fn static_ref_suggestion(
+    tcx: TyCtxt<'_>,
+    def_id: DefId,
+    span: Span,
+) -> Option<Vec<(Span, String)>> {
+    let hir_id = tcx.hir().as_local_hir_id(def_id)?;
+    let ty = match tcx.hir().get(hir_id) {
+        hir::Node::Item(hir::Item { kind: hir::ItemKind::Const(ty, _), .. })
+        | hir::Node::Item(hir::Item { kind: hir::ItemKind::Static(ty, ..), .. })
+        | hir::Node::ImplItem(hir::ImplItem { kind: hir::ImplItemKind::Const(ty, _), .. })
+        | hir::Node::TraitItem(hir::TraitItem {
+            kind: hir::TraitItemKind::Const(ty, Some(_)),
+            ..
+        }) => ty,
+
+        _ => return None,
+    };
+
+    let body = tcx.hir().body(tcx.hir().body_owned_by(hir_id));
+    let init = match body.value.kind {
+        hir::ExprKind::Box(ref init) if body.value.span == span => init,
+        _ => return None,
+    };
+
+    match init.kind {
+        hir::ExprKind::Array(_) | hir::ExprKind::Repeat(..) | hir::ExprKind::Lit(_) => {}
+        _ => return None,
+    }
+
+    // The type of the item needs to change as well, so it must be spelled out as `Box<T>`.
+    let boxed_ty = match ty.kind {
+        hir::TyKind::Path(hir::QPath::Resolved(None, ref path)) => {
+            match path.res {
+                Res::Def(DefKind::Struct, did) if Some(did) == tcx.lang_items().owned_box() => {}
+                _ => return None,
+            }
+
+            match &path.segments.last()?.generic_args().args[..] {
+                [hir::GenericArg::Type(boxed_ty)] => boxed_ty,
+                _ => return None,
+            }
+        }
+
+        _ => return None,
+    };
+
+    let source_map = tcx.sess.source_map();
+    let boxed_ty = source_map.span_to_snippet(boxed_ty.span).ok()?;
+    let init = source_map.span_to_snippet(init.span).ok()?;
+    Some(vec![(ty.span, format!("&'static {}", boxed_ty)), (span, format!("&{}", init))])
+}
+
 #[derive(Debug)]
 pub struct IfOrMatch;
 impl NonConstOp for IfOrMatch {
-    fn feature_gate(tcx: TyCtxt<'_>) -> Option<bool> {
-        Some(tcx.features().const_if_match)
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_if_match)
     }
 
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, _errors: &mut Vec<Diagnostic>) {
         // This should be caught by the HIR const-checker.
         item.tcx.sess.delay_span_bug(
             span,
@@ -157,21 +532,78 @@ impl NonConstOp for IfOrMatch {
 }
 
 #[derive(Debug)]
-pub struct LiveDrop;
+pub struct InlineAsm;
+impl NonConstOp for InlineAsm {
+    const IS_SUPPORTED_IN_MIRI: bool = false;
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        struct_span_err!(item.tcx.sess, span, E0750,
+                         "inline assembly is not allowed in {}s", item.const_kind())
+            .span_label(span, "inline assembly cannot be evaluated at compile-time")
+            .buffer(errors);
+    }
+}
+
+/// A value that may need to be dropped with a non-const destructor.
+#[derive(Debug)]
+pub struct LiveDrop {
+    /// Where the value is dropped, if the error is reported at the declaration of a variable.
+    pub dropped_at: Option<Span>,
+
+    /// The initializer of the variable, if wrapping it in a `ManuallyDrop` would fix the error.
+    pub manually_drop_init: Option<Span>,
+
+    /// Whether the value is a temporary, in which case the error is reported at the expression
+    /// that created it.
+    pub is_temp: bool,
+}
+
 impl NonConstOp for LiveDrop {
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
-        struct_span_err!(item.tcx.sess, span, E0493,
-                         "destructors cannot be evaluated at compile-time")
-            .span_label(span, format!("{}s cannot evaluate destructors",
-                                      item.const_kind()))
-            .emit();
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        let mut err = struct_span_err!(item.tcx.sess, span, E0493,
+                                       "destructors cannot be evaluated at compile-time");
+        err.span_label(span, format!("{}s cannot evaluate destructors", item.const_kind()));
+
+        if let Some(dropped_at) = self.dropped_at {
+            err.span_label(dropped_at, "value is dropped here");
+        }
+
+        if self.is_temp {
+            err.note("temporary values are dropped at the end of the statement or body that \
+                      encloses them");
+        }
+
+        if let Some(init_span) = self.manually_drop_init {
+            if let Ok(init) = item.tcx.sess.source_map().span_to_snippet(init_span) {
+                let krate = if item.tcx.has_attr(LOCAL_CRATE.as_def_id(), sym::no_std) {
+                    "core"
+                } else {
+                    "std"
+                };
+
+                err.span_suggestion(
+                    init_span,
+                    "wrap the value in a `ManuallyDrop` so that it is never dropped",
+                    format!("{}::mem::ManuallyDrop::new({})", krate, init),
+                    Applicability::MaybeIncorrect,
+                );
+            }
+        }
+
+        err.buffer(errors);
     }
 }
 
 #[derive(Debug)]
 pub struct Loop;
 impl NonConstOp for Loop {
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    // The `const_loop` gate is enforced by the HIR const-checker, so it is not reported as the
+    // `feature_gate` here. This lets `-Zunleash-the-miri-inside-of-you` skip this check.
+    fn is_allowed_in_item(&self, item: &Item<'_, '_>) -> bool {
+        item.tcx.features().const_loop
+    }
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, _errors: &mut Vec<Diagnostic>) {
         // This should be caught by the HIR const-checker.
         item.tcx.sess.delay_span_bug(
             span,
@@ -180,10 +612,48 @@ impl NonConstOp for Loop {
     }
 }
 
+/// A repeat expression (`[expr; N]`) whose element is moved, not `Copy` and has no drop glue.
+///
+/// Such an element is duplicated bitwise, which is fine in a `const` or `static` since the element
+/// is created once at compile-time, just like a constant that is used multiple times.
 #[derive(Debug)]
-pub struct MutBorrow(pub BorrowKind);
+pub struct NonCopyRepeat;
+impl NonConstOp for NonCopyRepeat {
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_in_array_repeat_expressions)
+    }
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        feature_err(
+            &item.tcx.sess.parse_sess,
+            sym::const_in_array_repeat_expressions,
+            span,
+            &format!(
+                "repeat expressions with non-`Copy` elements in {}s are unstable",
+                item.const_kind(),
+            ),
+        )
+        .buffer(errors);
+    }
+}
+
+/// The type that makes a borrowed place interior mutable.
+#[derive(Debug)]
+pub struct InteriorMut {
+    /// The interior mutable type, e.g. `std::cell::Cell<u8>`.
+    pub ty: String,
+
+    /// The field of a local type through which the interior mutable type is reached, if any.
+    pub field_span: Option<Span>,
+}
+
+/// A mutable borrow, or a shared borrow of a place with interior mutability.
+///
+/// For a shared borrow, the second field holds the type responsible for the interior mutability.
+#[derive(Debug)]
+pub struct MutBorrow(pub BorrowKind, pub Option<InteriorMut>);
 impl NonConstOp for MutBorrow {
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
         let kind = self.0;
         if let BorrowKind::Mut { .. } = kind {
             let mut err = struct_span_err!(item.tcx.sess, span, E0017,
@@ -201,63 +671,172 @@ impl NonConstOp for MutBorrow {
                           If you really want global mutable state, try using \
                           static mut or a global UnsafeCell.");
             }
-            err.emit();
+            err.buffer(errors);
         } else {
-            span_err!(item.tcx.sess, span, E0492,
-                      "cannot borrow a constant which may contain \
-                       interior mutability, create a static instead");
+            let mut err = struct_span_err!(item.tcx.sess, span, E0492,
+                                           "cannot borrow a constant which may contain \
+                                            interior mutability, create a static instead");
+            if let Some(interior_mut) = &self.1 {
+                match interior_mut.field_span {
+                    Some(field_span) => {
+                        let msg = format!("`{}` here is interior mutable", interior_mut.ty);
+                        err.span_note(field_span, &msg);
+                    }
+                    None => {
+                        err.note(&format!("`{}` is interior mutable", interior_mut.ty));
+                    }
+                }
+            }
+            err.help("move the interior mutable value into its own `static` and borrow that \
+                      instead");
+            err.buffer(errors);
         }
     }
 }
 
+/// A `*mut` pointer to a place, created with `&raw mut`.
+#[derive(Debug)]
+pub struct MutAddressOf;
+impl NonConstOp for MutAddressOf {
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_raw_mut_ptr)
+    }
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        feature_err(
+            &item.tcx.sess.parse_sess, sym::const_raw_mut_ptr, span,
+            &format!("`&raw mut` in {}s is unstable", item.const_kind()),
+        )
+        .buffer(errors);
+    }
+}
+
+/// A mutation of a place behind a reference or a pointer, e.g. `*x = 42`.
 #[derive(Debug)]
 pub struct MutDeref;
-impl NonConstOp for MutDeref {}
+impl NonConstOp for MutDeref {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        struct_span_err!(item.tcx.sess, span, E0751,
+                         "mutation through a reference is not allowed in {}s", item.const_kind())
+            .buffer(errors);
+    }
+}
 
 #[derive(Debug)]
 pub struct Panic;
 impl NonConstOp for Panic {
-    fn feature_gate(tcx: TyCtxt<'_>) -> Option<bool> {
-        Some(tcx.features().const_panic)
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_panic)
     }
 
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
-        feature_err(
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        let mut err = feature_err(
             &item.tcx.sess.parse_sess,
             sym::const_panic,
             span,
             &format!("panicking in {}s is unstable", item.const_kind()),
-        )
-        .emit();
+        );
+
+        if let Some((macro_name, condition)) = panic_origin(item, span) {
+            let label = match condition {
+                Some(condition) => {
+                    format!("this `{}` panics when `{}` is false", macro_name, condition)
+                }
+                None => format!("this `{}` panics when evaluated", macro_name),
+            };
+            err.span_label(span, label);
+        }
+
+        err.buffer(errors);
+    }
+}
+
+/// Returns the macro (e.g. `unreachable!`) that the call to a panic lang item at `span` was
+/// expanded from, along with the asserted condition if the panic comes from an `assert!`.
+fn panic_origin(item: &Item<'_, '_>, span: Span) -> Option<(String, Option<String>)> {
+    let backtrace = span.macro_backtrace();
+
+    // The panic lang item is called by `panic!`, which is often expanded from another macro in the
+    // standard library. Name the macro that was invoked by the user.
+    let origin = backtrace
+        .iter()
+        .find(|frame| !lint::in_external_macro(item.tcx.sess, frame.call_site))?;
+
+    let condition = backtrace
+        .iter()
+        .find(|frame| frame.macro_decl_name == "assert!")
+        .and_then(|frame| assert_condition(item, frame.call_site));
+
+    Some((origin.macro_decl_name.clone(), condition))
+}
+
+/// Returns the source of the condition of the `assert!` invoked at `assert_span`.
+fn assert_condition(item: &Item<'_, '_>, assert_span: Span) -> Option<String> {
+    let tcx = item.tcx;
+    let hir_id = tcx.hir().as_local_hir_id(item.def_id)?;
+    let body = tcx.hir().body(tcx.hir().maybe_body_owned_by(hir_id)?);
+
+    let mut finder = AssertConditionFinder { assert_span, found: None };
+    finder.visit_body(body);
+    tcx.sess.source_map().span_to_snippet(finder.found?.span).ok()
+}
+
+/// Finds the condition of an `assert!` invoked at `assert_span`.
+///
+/// `assert!(cond)` expands to `if !cond { panic!(..) }`, where the negation has the span of the
+/// whole invocation.
+struct AssertConditionFinder<'tcx> {
+    assert_span: Span,
+    found: Option<&'tcx hir::Expr>,
+}
+
+impl<'tcx> Visitor<'tcx> for AssertConditionFinder<'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if self.found.is_some() {
+            return;
+        }
+
+        if let hir::ExprKind::Unary(hir::UnNot, ref cond) = expr.kind {
+            if expr.span.from_expansion() && expr.span.source_equal(&self.assert_span) {
+                self.found = Some(cond);
+                return;
+            }
+        }
+
+        intravisit::walk_expr(self, expr);
     }
 }
 
 #[derive(Debug)]
 pub struct RawPtrComparison;
 impl NonConstOp for RawPtrComparison {
-    fn feature_gate(tcx: TyCtxt<'_>) -> Option<bool> {
-        Some(tcx.features().const_compare_raw_pointers)
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_compare_raw_pointers)
     }
 
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
         feature_err(
             &item.tcx.sess.parse_sess,
             sym::const_compare_raw_pointers,
             span,
             &format!("comparing raw pointers inside {}", item.const_kind()),
         )
-        .emit();
+        .buffer(errors);
     }
 }
 
 #[derive(Debug)]
 pub struct RawPtrDeref;
 impl NonConstOp for RawPtrDeref {
-    fn feature_gate(tcx: TyCtxt<'_>) -> Option<bool> {
-        Some(tcx.features().const_raw_ptr_deref)
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_raw_ptr_deref)
     }
 
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
         feature_err(
             &item.tcx.sess.parse_sess, sym::const_raw_ptr_deref, span,
             &format!(
@@ -265,38 +844,161 @@ impl NonConstOp for RawPtrDeref {
                 item.const_kind(),
             ),
         )
-        .emit();
+        .buffer(errors);
     }
 }
 
 #[derive(Debug)]
 pub struct RawPtrToIntCast;
 impl NonConstOp for RawPtrToIntCast {
-    fn feature_gate(tcx: TyCtxt<'_>) -> Option<bool> {
-        Some(tcx.features().const_raw_ptr_to_usize_cast)
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_raw_ptr_to_usize_cast)
     }
 
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
-        feature_err(
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        let mut err = feature_err(
             &item.tcx.sess.parse_sess, sym::const_raw_ptr_to_usize_cast, span,
             &format!(
                 "casting pointers to integers in {}s is unstable",
                 item.const_kind(),
             ),
+        );
+        err.note("the address of a value is only decided when the program runs, \
+                  so it is not known at compile time");
+
+        if let Some((comparison_span, comparison)) = ptr_comparison(item, span) {
+            err.span_suggestion_verbose(
+                comparison_span,
+                "compare the pointers directly",
+                comparison,
+                Applicability::MachineApplicable,
+            );
+        }
+
+        err.help(&format!(
+            "if the address is only needed at runtime, move the cast out of the {}",
+            item.const_kind(),
+        ));
+        err.buffer(errors);
+    }
+}
+
+/// If the pointer-to-integer cast at `span` is the left-hand side of a comparison like
+/// `a as usize == b as usize`, where `a` and `b` are raw pointers of the same type, returns the
+/// span of that comparison along with the equivalent comparison of `a` and `b`.
+fn ptr_comparison(item: &Item<'_, '_>, span: Span) -> Option<(Span, String)> {
+    let tcx = item.tcx;
+    let hir_id = tcx.hir().as_local_hir_id(item.def_id)?;
+    let body = tcx.hir().body(tcx.hir().maybe_body_owned_by(hir_id)?);
+
+    let mut finder = PtrComparisonFinder { cast_span: span, found: None };
+    finder.visit_body(body);
+    let comparison = finder.found?;
+
+    let (op, lhs, rhs) = match comparison.kind {
+        hir::ExprKind::Binary(op, ref lhs, ref rhs) => (op, lhs, rhs),
+        _ => bug!("`PtrComparisonFinder` found a non-binary expression"),
+    };
+
+    let (lhs, rhs) = match (&lhs.kind, &rhs.kind) {
+        (hir::ExprKind::Cast(lhs, _), hir::ExprKind::Cast(rhs, _)) => (lhs, rhs),
+        _ => bug!("`PtrComparisonFinder` found a comparison of non-casts"),
+    };
+
+    let tables = tcx.typeck_tables_of(item.def_id);
+    let lhs_ty = tables.expr_ty(lhs);
+    if !lhs_ty.is_unsafe_ptr() || lhs_ty != tables.expr_ty(rhs) {
+        return None;
+    }
+
+    let source_map = tcx.sess.source_map();
+    let lhs = source_map.span_to_snippet(lhs.span).ok()?;
+    let rhs = source_map.span_to_snippet(rhs.span).ok()?;
+    Some((comparison.span, format!("{} {} {}", lhs, op.node.as_str(), rhs)))
+}
+
+/// Finds an `==` or `!=` comparison of two casts whose left-hand side is the cast at `cast_span`.
+struct PtrComparisonFinder<'tcx> {
+    cast_span: Span,
+    found: Option<&'tcx hir::Expr>,
+}
+
+impl<'tcx> Visitor<'tcx> for PtrComparisonFinder<'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if self.found.is_some() {
+            return;
+        }
+
+        if let hir::ExprKind::Binary(op, ref lhs, ref rhs) = expr.kind {
+            let is_cast = |expr: &hir::Expr| match expr.kind {
+                hir::ExprKind::Cast(..) => true,
+                _ => false,
+            };
+
+            let is_eq = op.node == hir::BinOpKind::Eq || op.node == hir::BinOpKind::Ne;
+            if is_eq && lhs.span == self.cast_span && is_cast(lhs) && is_cast(rhs) {
+                self.found = Some(expr);
+                return;
+            }
+        }
+
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// A borrow of a (non-thread-local) `static`.
+#[derive(Debug)]
+pub struct RefToStatic;
+impl NonConstOp for RefToStatic {
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_refs_to_static)
+    }
+
+    fn is_allowed_in_item(&self, item: &Item<'_, '_>) -> bool {
+        item.const_kind().is_static() || item.tcx.features().const_refs_to_static
+    }
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        let mut err = struct_span_err!(item.tcx.sess, span, E0753,
+                                        "{}s cannot refer to statics", item.const_kind());
+        err.span_label(span, "reference to a `static`");
+        err.buffer(errors);
+    }
+}
+
+#[derive(Debug)]
+pub struct SimdVector;
+impl NonConstOp for SimdVector {
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_simd)
+    }
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        feature_err(
+            &item.tcx.sess.parse_sess,
+            sym::const_simd,
+            span,
+            &format!("SIMD vectors in {}s are unstable", item.const_kind()),
         )
-        .emit();
+        .buffer(errors);
     }
 }
 
 /// An access to a (non-thread-local) `static`.
+///
+/// Contains the `static` that is accessed, unless it is read through a reference.
 #[derive(Debug)]
-pub struct StaticAccess;
+pub struct StaticAccess(pub Option<DefId>);
 impl NonConstOp for StaticAccess {
     fn is_allowed_in_item(&self, item: &Item<'_, '_>) -> bool {
         item.const_kind().is_static()
     }
 
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
         let mut err = struct_span_err!(item.tcx.sess, span, E0013,
                                         "{}s cannot refer to statics, use \
                                         a constant instead", item.const_kind());
@@ -309,36 +1011,117 @@ impl NonConstOp for StaticAccess {
                 "To fix this, the value can be extracted as a const and then used."
             );
         }
-        err.emit();
+
+        let StaticAccess(def_id) = *self;
+        if let Some(def_id) = def_id {
+            if let Some(static_span) = static_to_const_suggestion_span(item.tcx, def_id) {
+                err.span_suggestion(
+                    static_span,
+                    &format!("consider making `{}` a `const`", item.tcx.def_path_str(def_id)),
+                    "const".to_owned(),
+                    Applicability::MaybeIncorrect,
+                );
+                err.note("unlike a `static`, a `const` has no fixed address: every use of it \
+                          creates a new copy of its value");
+            }
+        }
+
+        err.buffer(errors);
     }
 }
 
+/// Returns the span of the `static` keyword in the definition of `def_id` if the `static` could
+/// become a `const`.
+///
+/// We only suggest this for immutable `static`s in the current crate whose type has neither
+/// interior mutability nor drop glue, since a `const` is copied into every place it is used.
+fn static_to_const_suggestion_span(tcx: TyCtxt<'_>, def_id: DefId) -> Option<Span> {
+    let hir_id = tcx.hir().as_local_hir_id(def_id)?;
+    let vis = match tcx.hir().get(hir_id) {
+        hir::Node::Item(hir::Item {
+            kind: hir::ItemKind::Static(_, hir::Mutability::Immutable, _),
+            vis,
+            ..
+        }) => vis,
+
+        _ => return None,
+    };
+
+    let param_env = tcx.param_env(def_id);
+    let span = tcx.def_span(def_id);
+    let ty = tcx.type_of(def_id);
+    if !ty.is_freeze(tcx, param_env, span) || ty.needs_drop(tcx, param_env) {
+        return None;
+    }
+
+    // The `static` keyword comes right after the visibility.
+    let source_map = tcx.sess.source_map();
+    let keyword = match vis.node {
+        hir::VisibilityKind::Inherited => span,
+        _ => {
+            let after_vis = vis.span.between(span.shrink_to_hi());
+            let keyword_start = source_map.span_until_non_whitespace(after_vis).shrink_to_hi();
+            keyword_start.to(span.shrink_to_hi())
+        }
+    };
+
+    Some(source_map.span_until_whitespace(keyword))
+}
+
 /// An access to a thread-local `static`.
 #[derive(Debug)]
 pub struct ThreadLocalAccess;
 impl NonConstOp for ThreadLocalAccess {
     const IS_SUPPORTED_IN_MIRI: bool = false;
 
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
-        span_err!(item.tcx.sess, span, E0625,
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_fn_thread_local)
+    }
+
+    fn is_gated_in_item(&self, item: &Item<'_, '_>) -> bool {
+        item.const_kind() == ConstKind::ConstFn
+    }
+
+    fn is_allowed_in_item(&self, item: &Item<'_, '_>) -> bool {
+        // A `const fn` may also be called at runtime, where accessing a thread-local is fine. The
+        // interpreter rejects the access if the `const fn` is actually evaluated at compile-time.
+        item.const_kind() == ConstKind::ConstFn && item.tcx.features().const_fn_thread_local
+    }
+
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
+        if item.const_kind() == ConstKind::ConstFn {
+            feature_err(
+                &item.tcx.sess.parse_sess, sym::const_fn_thread_local, span,
+                "thread-local statics in constant functions are unstable",
+            )
+            .buffer(errors);
+            return;
+        }
+
+        struct_span_err!(item.tcx.sess, span, E0625,
             "thread-local statics cannot be \
-            accessed at compile-time");
+            accessed at compile-time")
+            .note("each thread gets its own copy of a thread-local static when the program runs, \
+                   so there is no copy that could be used during compilation")
+            .help("if the value is known at compile-time, put it in a `const` and use that to \
+                   initialize the thread-local, or pass the value in as a parameter")
+            .buffer(errors);
     }
 }
 
 #[derive(Debug)]
 pub struct Transmute;
 impl NonConstOp for Transmute {
-    fn feature_gate(tcx: TyCtxt<'_>) -> Option<bool> {
-        Some(tcx.features().const_transmute)
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_transmute)
     }
 
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
         feature_err(
             &item.tcx.sess.parse_sess, sym::const_transmute, span,
             &format!("The use of std::mem::transmute() is gated in {}s", item.const_kind())
         )
-        .emit();
+        .buffer(errors);
     }
 }
 
@@ -347,18 +1130,18 @@ pub struct UnionAccess;
 impl NonConstOp for UnionAccess {
     fn is_allowed_in_item(&self, item: &Item<'_, '_>) -> bool {
         // Union accesses are stable in all contexts except `const fn`.
-        item.const_kind() != ConstKind::ConstFn || Self::feature_gate(item.tcx).unwrap()
+        item.const_kind() != ConstKind::ConstFn || item.tcx.features().const_fn_union
     }
 
-    fn feature_gate(tcx: TyCtxt<'_>) -> Option<bool> {
-        Some(tcx.features().const_fn_union)
+    fn feature_gate() -> Option<Symbol> {
+        Some(sym::const_fn_union)
     }
 
-    fn emit_error(&self, item: &Item<'_, '_>, span: Span) {
+    fn emit_error(&self, item: &Item<'_, '_>, span: Span, errors: &mut Vec<Diagnostic>) {
         feature_err(
             &item.tcx.sess.parse_sess, sym::const_fn_union, span,
             "unions in const fn are unstable",
         )
-        .emit();
+        .buffer(errors);
     }
 }
@@ -1,9 +1,13 @@
 //! A copy of the `Qualif` trait in `qualify_consts.rs` that is suitable for the new validator.
 
 use rustc::mir::*;
-use rustc::ty::{self, Ty};
+use rustc::ty::subst::SubstsRef;
+use rustc::ty::{self, Ty, TypeFoldable};
+use rustc::hir::def::DefKind;
 use rustc::hir::def_id::DefId;
-use syntax_pos::DUMMY_SP;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+
+use std::fmt;
 
 use super::Item as ConstCx;
 
@@ -11,6 +15,88 @@ pub fn in_any_value_of_ty(cx: &ConstCx<'_, 'tcx>, ty: Ty<'tcx>) -> ConstQualifs
     ConstQualifs {
         has_mut_interior: HasMutInterior::in_any_value_of_ty(cx, ty),
         needs_drop: NeedsDrop::in_any_value_of_ty(cx, ty),
+        needs_non_const_drop: NeedsNonConstDrop::in_any_value_of_ty(cx, ty),
+        custom_eq: CustomEq::in_any_value_of_ty(cx, ty),
+        contains_ptr: ContainsPtr::in_any_value_of_ty(cx, ty),
+        ref_to_static: RefToStatic::in_any_value_of_ty(cx, ty),
+    }
+}
+
+/// Returns the `DefId` of the impl item that provides the value of the trait associated constant
+/// `def_id`, or `None` if the impl cannot be determined from `substs` or the value comes from a
+/// default in the trait.
+fn resolve_impl_const(
+    cx: &ConstCx<'_, 'tcx>,
+    def_id: DefId,
+    substs: SubstsRef<'tcx>,
+) -> Option<DefId> {
+    // With generic `substs`, a more specialized impl may end up providing the value.
+    if substs.needs_subst() {
+        return None;
+    }
+
+    let substs = cx.tcx.erase_regions(&substs);
+    match ty::Instance::resolve(cx.tcx, cx.param_env, def_id, substs)?.def {
+        ty::InstanceDef::Item(impl_item) if cx.tcx.trait_of_item(impl_item).is_none() => {
+            Some(impl_item)
+        }
+
+        _ => None,
+    }
+}
+
+/// A qualif computed alongside the built-in ones by `mir_const_qualif_with_extra`.
+///
+/// Unlike `Qualif`, this trait is object-safe, so tools (e.g. Clippy) can run their own analyses
+/// over the body of every const item by overriding the `mir_const_qualif` query. An implementation
+/// will usually be backed by a `Qualif` and `validation::in_return_place`.
+pub trait ExtraQualif {
+    /// The name this qualif is registered under. Its result is looked up by this name.
+    fn name(&self) -> &'static str;
+
+    /// Returns `true` if the final value of `item` may have this qualif.
+    fn in_return_place<'tcx>(&self, item: &ConstCx<'_, 'tcx>) -> bool;
+}
+
+/// The set of `ExtraQualif`s to compute for an `Item`.
+#[derive(Default)]
+pub struct ExtraQualifs<'a> {
+    qualifs: Vec<&'a dyn ExtraQualif>,
+}
+
+/// Returned by `ExtraQualifs::register` when a qualif with the same name was already registered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DuplicateExtraQualif(pub &'static str);
+
+impl fmt::Display for DuplicateExtraQualif {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an extra qualif named `{}` is already registered", self.0)
+    }
+}
+
+impl<'a> ExtraQualifs<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `qualif` to the set, or returns an error if its name is already taken.
+    pub fn register(&mut self, qualif: &'a dyn ExtraQualif) -> Result<(), DuplicateExtraQualif> {
+        let name = qualif.name();
+        if self.qualifs.iter().any(|q| q.name() == name) {
+            return Err(DuplicateExtraQualif(name));
+        }
+
+        self.qualifs.push(qualif);
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.qualifs.is_empty()
+    }
+
+    /// Calls `in_return_place` for each registered qualif, and returns the results by name.
+    pub fn in_return_place(&self, item: &ConstCx<'_, '_>) -> FxHashMap<&'static str, bool> {
+        self.qualifs.iter().map(|q| (q.name(), q.in_return_place(item))).collect()
     }
 }
 
@@ -110,11 +196,16 @@ pub trait Qualif {
             Operand::Constant(ref constant) => {
                 if let Some(static_) = constant.check_static_ptr(cx.tcx) {
                     Self::in_static(cx, static_)
-                } else if let ty::ConstKind::Unevaluated(def_id, _) = constant.literal.val {
-                    // Don't peek inside trait associated constants.
-                    if cx.tcx.trait_of_item(def_id).is_some() {
-                        Self::in_any_value_of_ty(cx, constant.literal.ty)
+                } else if let ty::ConstKind::Unevaluated(def_id, substs) = constant.literal.val {
+                    // Don't peek inside trait associated constants unless we know which impl
+                    // provides the value.
+                    let def_id = if cx.tcx.trait_of_item(def_id).is_some() {
+                        resolve_impl_const(cx, def_id, substs)
                     } else {
+                        Some(def_id)
+                    };
+
+                    if let Some(def_id) = def_id {
                         let qualifs = cx.tcx.at(constant.span).mir_const_qualif(def_id);
                         let qualif = Self::in_qualifs(&qualifs);
 
@@ -122,6 +213,8 @@ pub trait Qualif {
                         // the definition, e.g., impl associated const
                         // with type parameters, take it into account.
                         qualif && Self::in_any_value_of_ty(cx, constant.literal.ty)
+                    } else {
+                        Self::in_any_value_of_ty(cx, constant.literal.ty)
                     }
                 } else {
                     false
@@ -139,33 +232,37 @@ pub trait Qualif {
             Rvalue::NullaryOp(..) => false,
 
             Rvalue::Discriminant(ref place) |
-            Rvalue::Len(ref place) => Self::in_place(cx, per_local, place.as_ref()),
+            Rvalue::Len(ref place) |
+            Rvalue::AddressOf(_, ref place) => Self::in_place(cx, per_local, place.as_ref()),
 
             Rvalue::Use(ref operand) |
-            Rvalue::Repeat(ref operand, _) |
             Rvalue::UnaryOp(_, ref operand) |
             Rvalue::Cast(_, ref operand, _) => Self::in_operand(cx, per_local, operand),
 
+            // The operand is duplicated `count` times, so every element of the resulting array has
+            // exactly the qualifs of the operand. An array of length zero has no elements.
+            Rvalue::Repeat(_, 0) => false,
+            Rvalue::Repeat(ref operand, _) => Self::in_operand(cx, per_local, operand),
+
             Rvalue::BinaryOp(_, ref lhs, ref rhs) |
             Rvalue::CheckedBinaryOp(_, ref lhs, ref rhs) => {
                 Self::in_operand(cx, per_local, lhs) || Self::in_operand(cx, per_local, rhs)
             }
 
             Rvalue::Ref(_, _, ref place) => {
-                // Special-case reborrows to be more like a copy of the reference.
-                if let &[ref proj_base @ .., elem] = place.projection.as_ref() {
-                    if ProjectionElem::Deref == elem {
-                        let base_ty = Place::ty_from(&place.base, proj_base, cx.body, cx.tcx).ty;
-                        if let ty::Ref(..) = base_ty.kind {
-                            return Self::in_place(cx, per_local, PlaceRef {
-                                base: &place.base,
-                                projection: proj_base,
-                            });
-                        }
+                // Special-case reborrows to be more like a copy of the reference. Like in
+                // `place_as_reborrow`, every dereference of a reference at the end of the place is
+                // peeled off, so `&**r` is treated like a copy of `r`.
+                let mut projection = place.projection.as_ref();
+                while let &[ref proj_base @ .., ProjectionElem::Deref] = projection {
+                    let base_ty = Place::ty_from(&place.base, proj_base, cx.body, cx.tcx).ty;
+                    match base_ty.kind {
+                        ty::Ref(..) => projection = proj_base,
+                        _ => break,
                     }
                 }
 
-                Self::in_place(cx, per_local, place.as_ref())
+                Self::in_place(cx, per_local, PlaceRef { base: &place.base, projection })
             }
 
             Rvalue::Aggregate(_, ref operands) => {
@@ -185,10 +282,34 @@ pub trait Qualif {
     fn in_call(
         cx: &ConstCx<'_, 'tcx>,
         _per_local: &impl Fn(Local) -> bool,
-        _callee: &Operand<'tcx>,
+        callee: &Operand<'tcx>,
         _args: &[Operand<'tcx>],
         return_ty: Ty<'tcx>,
     ) -> bool {
+        // If the callee is a `const fn` from another crate, use the qualifs in its return place.
+        // These were computed assuming the worst about every argument, so they hold for any call.
+        //
+        // We don't do this for local `const fn`s, since mutually recursive ones would cause a
+        // query cycle.
+        if let ty::FnDef(def_id, _) = callee.ty(cx.body, cx.tcx).kind {
+            let is_fn_item = match cx.tcx.def_kind(def_id) {
+                Some(DefKind::Fn) | Some(DefKind::Method) => true,
+                _ => false,
+            };
+
+            if !def_id.is_local()
+                && is_fn_item
+                && !cx.tcx.is_foreign_item(def_id)
+                && cx.tcx.is_const_fn(def_id)
+            {
+                let qualifs = cx.tcx.mir_const_qualif(def_id);
+
+                // Just in case the return type is more specific than the one in the signature of
+                // a generic `const fn`, take it into account.
+                return Self::in_qualifs(&qualifs) && Self::in_any_value_of_ty(cx, return_ty);
+            }
+        }
+
         // Be conservative about the returned value of a const fn.
         Self::in_any_value_of_ty(cx, return_ty)
     }
@@ -204,12 +325,17 @@ pub struct HasMutInterior;
 impl Qualif for HasMutInterior {
     const ANALYSIS_NAME: &'static str = "flow_has_mut_interior";
 
+    // A moved-from local cannot be observed until it is reinitialized. The validator is still
+    // conservative for locals that may be reinitialized through a pointer (see
+    // `Qualifs::has_mut_interior` in `validation.rs`), so clearing the qualif here is safe.
+    const IS_CLEARED_ON_MOVE: bool = true;
+
     fn in_qualifs(qualifs: &ConstQualifs) -> bool {
         qualifs.has_mut_interior
     }
 
     fn in_any_value_of_ty(cx: &ConstCx<'_, 'tcx>, ty: Ty<'tcx>) -> bool {
-        !ty.is_freeze(cx.tcx, cx.param_env, DUMMY_SP)
+        !cx.is_freeze(ty)
     }
 
     fn in_rvalue(
@@ -250,7 +376,7 @@ impl Qualif for NeedsDrop {
     }
 
     fn in_any_value_of_ty(cx: &ConstCx<'_, 'tcx>, ty: Ty<'tcx>) -> bool {
-        ty.needs_drop(cx.tcx, cx.param_env)
+        cx.needs_drop(ty)
     }
 
     fn in_rvalue(
@@ -259,13 +385,261 @@ impl Qualif for NeedsDrop {
         rvalue: &Rvalue<'tcx>,
     ) -> bool {
         if let Rvalue::Aggregate(ref kind, _) = *rvalue {
-            if let AggregateKind::Adt(def, ..) = **kind {
+            if let AggregateKind::Adt(def, variant_index, substs, ..) = **kind {
                 if def.has_dtor(cx.tcx) {
                     return true;
                 }
+
+                // Only the fields of the variant being constructed will ever be dropped, so a
+                // fieldless variant (e.g. `None::<String>`) or one whose fields are all drop-free
+                // is not `NeedsDrop`, even if the enum as a whole is.
+                if def.is_enum() {
+                    let variant_needs_drop = def.variants[variant_index]
+                        .fields
+                        .iter()
+                        .any(|field| Self::in_any_value_of_ty(cx, field.ty(cx.tcx, substs)));
+
+                    if !variant_needs_drop {
+                        return false;
+                    }
+                }
             }
         }
 
         Self::in_rvalue_structurally(cx, per_local, rvalue)
     }
 }
+
+/// Constant containing a value whose drop glue calls a `Drop` impl that is not `const`.
+/// Unlike `NeedsDrop`, this allows dropping values whose destructors can be evaluated at
+/// compile-time: those whose `Drop::drop` is a `const fn` (with `#![feature(const_trait_impl)]`)
+/// and whose fields have no non-`const` destructors either.
+pub struct NeedsNonConstDrop;
+
+impl Qualif for NeedsNonConstDrop {
+    const ANALYSIS_NAME: &'static str = "flow_needs_non_const_drop";
+    const IS_CLEARED_ON_MOVE: bool = true;
+
+    fn in_qualifs(qualifs: &ConstQualifs) -> bool {
+        qualifs.needs_non_const_drop
+    }
+
+    fn in_any_value_of_ty(cx: &ConstCx<'_, 'tcx>, ty: Ty<'tcx>) -> bool {
+        if !cx.needs_drop(ty) {
+            return false;
+        }
+
+        match ty.kind {
+            ty::Adt(def, substs) if !def.is_box() => {
+                if has_non_const_dtor(cx, def) {
+                    return true;
+                }
+
+                def.all_fields()
+                    .any(|field| Self::in_any_value_of_ty(cx, field.ty(cx.tcx, substs)))
+            }
+
+            ty::Tuple(tys) => tys.iter().any(|ty| Self::in_any_value_of_ty(cx, ty.expect_ty())),
+            ty::Array(elem, _) | ty::Slice(elem) => Self::in_any_value_of_ty(cx, elem),
+
+            // Be conservative about everything else (e.g. `Box`, closures, type parameters and
+            // trait objects).
+            _ => true,
+        }
+    }
+
+    fn in_rvalue(
+        cx: &ConstCx<'_, 'tcx>,
+        per_local: &impl Fn(Local) -> bool,
+        rvalue: &Rvalue<'tcx>,
+    ) -> bool {
+        if let Rvalue::Aggregate(ref kind, _) = *rvalue {
+            if let AggregateKind::Adt(def, ..) = **kind {
+                if has_non_const_dtor(cx, def) {
+                    return true;
+                }
+            }
+        }
+
+        Self::in_rvalue_structurally(cx, per_local, rvalue)
+    }
+}
+
+fn has_non_const_dtor(cx: &ConstCx<'_, 'tcx>, def: &'tcx ty::AdtDef) -> bool {
+    def.destructor(cx.tcx).map_or(false, |dtor| !cx.tcx.is_const_fn(dtor.did))
+}
+
+/// A constant containing a value whose type has a custom (non-structural) `PartialEq` impl.
+/// Such a constant cannot be used as a pattern, since matching on it would not behave like a call
+/// to `PartialEq::eq`.
+///
+/// This is tracked by value rather than by type, so that a constant like
+/// `None::<NonStructural>` can still be used as a pattern.
+pub struct CustomEq;
+
+impl Qualif for CustomEq {
+    const ANALYSIS_NAME: &'static str = "flow_custom_eq";
+
+    fn in_qualifs(qualifs: &ConstQualifs) -> bool {
+        qualifs.custom_eq
+    }
+
+    fn in_any_value_of_ty(cx: &ConstCx<'_, 'tcx>, ty: Ty<'tcx>) -> bool {
+        // If *any* component of a type is not structural-match, at least some values of that type
+        // are not. Only "some", since the component may be part of an enum variant, as in
+        // `Option::<NonStructural>::Some`.
+        let id = cx.tcx.hir().as_local_hir_id(cx.def_id).unwrap();
+        ty::search_for_structural_match_violation(id, cx.body.span, cx.tcx, ty).is_some()
+    }
+
+    fn in_rvalue(
+        cx: &ConstCx<'_, 'tcx>,
+        per_local: &impl Fn(Local) -> bool,
+        rvalue: &Rvalue<'tcx>,
+    ) -> bool {
+        if let Rvalue::Aggregate(ref kind, _) = *rvalue {
+            if let AggregateKind::Adt(def, _, substs, ..) = **kind {
+                let ty = cx.tcx.mk_adt(def, substs);
+                let id = cx.tcx.hir().as_local_hir_id(cx.def_id).unwrap();
+                let is_structural = cx.tcx.infer_ctxt().enter(|infcx| {
+                    ty::type_marked_structural(id, cx.body.span, &infcx, ty)
+                });
+
+                if !is_structural {
+                    return true;
+                }
+            }
+        }
+
+        Self::in_rvalue_structurally(cx, per_local, rvalue)
+    }
+}
+
+/// A constant containing a raw pointer or a function pointer.
+///
+/// The address of a function or of the allocation behind a raw pointer is not guaranteed to be
+/// the same across uses of a constant (or across codegen units), so comparing such constants for
+/// equality, e.g. by using them as a pattern, may not behave as expected.
+pub struct ContainsPtr;
+
+impl Qualif for ContainsPtr {
+    const ANALYSIS_NAME: &'static str = "flow_contains_ptr";
+
+    fn in_qualifs(qualifs: &ConstQualifs) -> bool {
+        qualifs.contains_ptr
+    }
+
+    fn in_any_value_of_ty(cx: &ConstCx<'_, 'tcx>, ty: Ty<'tcx>) -> bool {
+        ty_may_contain_ptr(cx, ty, false, &mut FxHashSet::default())
+    }
+
+    fn in_rvalue(
+        cx: &ConstCx<'_, 'tcx>,
+        per_local: &impl Fn(Local) -> bool,
+        rvalue: &Rvalue<'tcx>,
+    ) -> bool {
+        // Casts and raw borrows are the only ways to create a raw pointer or a function pointer
+        // from a value that does not already contain one (e.g. `&x as *const _`, `&raw const x`
+        // or `foo as fn()`).
+        match *rvalue {
+            Rvalue::AddressOf(..) => return true,
+            Rvalue::Cast(_, _, cast_ty) => match cast_ty.kind {
+                ty::RawPtr(_) | ty::FnPtr(_) => return true,
+                _ => {}
+            },
+            _ => {}
+        }
+
+        Self::in_rvalue_structurally(cx, per_local, rvalue)
+    }
+}
+
+/// A constant containing a reference (or a raw pointer) to a `static`.
+///
+/// The final value of a `const` may only refer to a `static` with
+/// `#![feature(const_refs_to_static)]`, and even then, reading through such a reference in a
+/// `const` still reads from the `static`.
+pub struct RefToStatic;
+
+impl Qualif for RefToStatic {
+    const ANALYSIS_NAME: &'static str = "flow_ref_to_static";
+
+    fn in_qualifs(qualifs: &ConstQualifs) -> bool {
+        qualifs.ref_to_static
+    }
+
+    fn in_any_value_of_ty(cx: &ConstCx<'_, 'tcx>, ty: Ty<'tcx>) -> bool {
+        ty_may_contain_ptr(cx, ty, true, &mut FxHashSet::default())
+    }
+
+    fn in_static(_cx: &ConstCx<'_, 'tcx>, _def_id: DefId) -> bool {
+        // The operand is a pointer to the `static` itself.
+        true
+    }
+
+    fn in_rvalue(
+        cx: &ConstCx<'_, 'tcx>,
+        per_local: &impl Fn(Local) -> bool,
+        rvalue: &Rvalue<'tcx>,
+    ) -> bool {
+        // Borrowing a place behind a reference to a `static` (e.g. `&(*_1).field`) creates another
+        // reference to (part of) that `static`, whatever the type of the borrowed place.
+        if let Rvalue::Ref(_, _, ref place) | Rvalue::AddressOf(_, ref place) = *rvalue {
+            if let (PlaceBase::Local(local), [ProjectionElem::Deref, ..])
+                = (&place.base, place.projection.as_ref())
+            {
+                if per_local(*local) {
+                    return true;
+                }
+            }
+        }
+
+        Self::in_rvalue_structurally(cx, per_local, rvalue)
+    }
+}
+
+/// Returns `true` if a value of type `ty` may contain a raw pointer or a function pointer, either
+/// directly or behind a reference. If `include_refs` is `true`, references count as pointers as
+/// well.
+fn ty_may_contain_ptr(
+    cx: &ConstCx<'_, 'tcx>,
+    ty: Ty<'tcx>,
+    include_refs: bool,
+    seen: &mut FxHashSet<Ty<'tcx>>,
+) -> bool {
+    // Recursive types are only visited once.
+    if !seen.insert(ty) {
+        return false;
+    }
+
+    match ty.kind {
+        ty::RawPtr(_) | ty::FnPtr(_) => true,
+
+        ty::Bool | ty::Char | ty::Int(_) | ty::Uint(_) | ty::Float(_) | ty::Str | ty::Never |
+        ty::FnDef(..) | ty::Foreign(_) => false,
+
+        ty::Ref(..) if include_refs => true,
+
+        ty::Ref(_, elem, _) | ty::Array(elem, _) | ty::Slice(elem) => {
+            ty_may_contain_ptr(cx, elem, include_refs, seen)
+        }
+
+        ty::Tuple(tys) => {
+            tys.iter().any(|ty| ty_may_contain_ptr(cx, ty.expect_ty(), include_refs, seen))
+        }
+
+        ty::Adt(def, substs) => {
+            def.all_fields()
+                .any(|field| ty_may_contain_ptr(cx, field.ty(cx.tcx, substs), include_refs, seen))
+        }
+
+        ty::Closure(def_id, substs) => {
+            substs.as_closure()
+                .upvar_tys(def_id, cx.tcx)
+                .any(|ty| ty_may_contain_ptr(cx, ty, include_refs, seen))
+        }
+
+        // Be conservative about everything else (e.g. type parameters and trait objects).
+        _ => true,
+    }
+}
@@ -4,22 +4,159 @@
 
 use rustc::mir::visit::Visitor;
 use rustc::mir::{self, BasicBlock, Local, Location};
+use rustc::ty;
 use rustc_index::bit_set::BitSet;
+use rustc_index::vec::{Idx, IndexVec};
 
 use std::marker::PhantomData;
 
-use crate::dataflow::{self as old_dataflow, generic as dataflow};
+use crate::dataflow::generic as dataflow;
 use super::{Item, Qualif};
 
+rustc_index::newtype_index! {
+    /// A `Local`, or a field of a `Local`, whose qualifs are tracked by `FlowSensitiveAnalysis`.
+    pub struct TrackedPlace {
+        DEBUG_FORMAT = "tp{}"
+    }
+}
+
+/// The places whose qualifs are tracked by `FlowSensitiveAnalysis`: every `Local` whose type admits
+/// a value with the qualif, as well as each field of such a `Local` if its type is a struct or a
+/// tuple.
+///
+/// Assigning a qualified value to one field of such a `Local` (e.g. a `Cell` to `x.0`) does not
+/// qualify its other fields, so they can still be borrowed.
+///
+/// A `Local` whose type can never have the qualif is never qualified, so it is not tracked at all.
+/// This keeps the dataflow state small in bodies with many locals (e.g. after macro expansion),
+/// where usually only a few of them can have any given qualif.
+pub(crate) struct TrackedPlaces {
+    /// The `TrackedPlace` of each `Local`, or `None` if it is not tracked.
+    locals: IndexVec<Local, Option<TrackedPlace>>,
+
+    /// For each tracked `Local` whose fields are tracked separately, the `TrackedPlace` of its
+    /// first field and the number of fields. The `TrackedPlace`s of the other fields follow the
+    /// first one.
+    fields: IndexVec<Local, Option<(TrackedPlace, usize)>>,
+    len: usize,
+}
+
+impl TrackedPlaces {
+    pub(crate) fn new<Q: Qualif>(item: &Item<'_, 'tcx>) -> Self {
+        let local_decls = &item.body.local_decls;
+
+        let mut len = 0;
+        let locals: IndexVec<Local, _> = local_decls.iter().map(|decl| {
+            if !Q::in_any_value_of_ty(item, decl.ty) {
+                return None;
+            }
+
+            let tracked = TrackedPlace::new(len);
+            len += 1;
+            Some(tracked)
+        }).collect();
+
+        // Fields come after all tracked locals.
+        let fields = local_decls.iter_enumerated().map(|(local, decl)| {
+            if locals[local].is_none() {
+                return None;
+            }
+
+            let num_fields = match decl.ty.kind {
+                ty::Adt(def, _) if def.is_struct() => def.non_enum_variant().fields.len(),
+                ty::Tuple(tys) => tys.len(),
+                _ => 0,
+            };
+
+            if num_fields == 0 {
+                return None;
+            }
+
+            let first = TrackedPlace::new(len);
+            len += num_fields;
+            Some((first, num_fields))
+        }).collect();
+
+        TrackedPlaces { locals, fields, len }
+    }
+
+    /// Returns `true` if `local` can ever have the qualif.
+    pub(crate) fn is_tracked(&self, local: Local) -> bool {
+        self.locals[local].is_some()
+    }
+
+    fn local(&self, local: Local) -> Option<TrackedPlace> {
+        self.locals[local]
+    }
+
+    fn field(&self, local: Local, field: mir::Field) -> Option<TrackedPlace> {
+        self.fields[local].map(|(first, _)| TrackedPlace::new(first.index() + field.index()))
+    }
+
+    fn fields(&self, local: Local) -> impl Iterator<Item = TrackedPlace> {
+        let (first, num_fields) = self.fields[local].unwrap_or((TrackedPlace::new(0), 0));
+        (first.index()..first.index() + num_fields).map(TrackedPlace::new)
+    }
+
+    /// Returns the `TrackedPlace` that records a qualif assigned to `place`, or `None` if `local`
+    /// is not tracked.
+    fn for_assignment(
+        &self,
+        local: Local,
+        projection: &[mir::PlaceElem<'_>],
+    ) -> Option<TrackedPlace> {
+        let tracked_local = self.local(local)?;
+        match projection {
+            [mir::ProjectionElem::Field(field, _), ..] => {
+                Some(self.field(local, *field).unwrap_or(tracked_local))
+            }
+            _ => Some(tracked_local),
+        }
+    }
+
+    /// Returns `true` if any part of `local` is qualified in `state`.
+    pub(crate) fn contains_local(&self, state: &BitSet<TrackedPlace>, local: Local) -> bool {
+        match self.local(local) {
+            Some(tracked) => {
+                state.contains(tracked) || self.fields(local).any(|f| state.contains(f))
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the given field of `local` is qualified in `state`.
+    pub(crate) fn contains_field(
+        &self,
+        state: &BitSet<TrackedPlace>,
+        local: Local,
+        field: mir::Field,
+    ) -> bool {
+        match (self.local(local), self.field(local, field)) {
+            (Some(tracked), Some(f)) => state.contains(tracked) || state.contains(f),
+            _ => self.contains_local(state, local),
+        }
+    }
+
+    fn remove_local(&self, state: &mut BitSet<TrackedPlace>, local: Local) {
+        if let Some(tracked) = self.local(local) {
+            state.remove(tracked);
+        }
+        for f in self.fields(local) {
+            state.remove(f);
+        }
+    }
+}
+
 /// A `Visitor` that propagates qualifs between locals. This defines the transfer function of
 /// `FlowSensitiveAnalysis`.
 ///
 /// This transfer does nothing when encountering an indirect assignment. Consumers should rely on
-/// the `IndirectlyMutableLocals` dataflow pass to see if a `Local` may have become qualified via
-/// an indirect assignment or function call.
+/// the `MaybeBorrowedLocals` dataflow pass to see if a `Local` may have become qualified via an
+/// indirect assignment or function call.
 struct TransferFunction<'a, 'mir, 'tcx, Q> {
     item: &'a Item<'mir, 'tcx>,
-    qualifs_per_local: &'a mut BitSet<Local>,
+    places: &'a TrackedPlaces,
+    state: &'a mut BitSet<TrackedPlace>,
 
     _qualif: PhantomData<Q>,
 }
@@ -30,32 +167,40 @@ where
 {
     fn new(
         item: &'a Item<'mir, 'tcx>,
-        qualifs_per_local: &'a mut BitSet<Local>,
+        places: &'a TrackedPlaces,
+        state: &'a mut BitSet<TrackedPlace>,
     ) -> Self {
         TransferFunction {
             item,
-            qualifs_per_local,
+            places,
+            state,
             _qualif: PhantomData,
         }
     }
 
     fn initialize_state(&mut self) {
-        self.qualifs_per_local.clear();
+        self.state.clear();
 
+        // Only the arguments whose type admits a value with the qualif are tracked.
         for arg in self.item.body.args_iter() {
-            let arg_ty = self.item.body.local_decls[arg].ty;
-            if Q::in_any_value_of_ty(self.item, arg_ty) {
-                self.qualifs_per_local.insert(arg);
+            if let Some(tracked) = self.places.local(arg) {
+                self.state.insert(tracked);
             }
         }
     }
 
+    fn contains_local(&self, local: Local) -> bool {
+        self.places.contains_local(self.state, local)
+    }
+
     fn assign_qualif_direct(&mut self, place: &mir::Place<'tcx>, value: bool) {
         debug_assert!(!place.is_indirect());
 
         match (value, place.as_ref()) {
-            (true, mir::PlaceRef { base: &mir::PlaceBase::Local(local), .. }) => {
-                self.qualifs_per_local.insert(local);
+            (true, mir::PlaceRef { base: &mir::PlaceBase::Local(local), projection }) => {
+                if let Some(tracked) = self.places.for_assignment(local, projection) {
+                    self.state.insert(tracked);
+                }
             }
 
             // For now, we do not clear the qualif if a local is overwritten in full by
@@ -63,7 +208,7 @@ where
             // with aggregates where we overwrite all fields with assignments, which would not
             // get this feature.
             (false, mir::PlaceRef { base: &mir::PlaceBase::Local(_local), projection: &[] }) => {
-                // self.qualifs_per_local.remove(*local);
+                // self.places.remove_local(self.state, local);
             }
 
             _ => {}
@@ -80,7 +225,7 @@ where
         let return_ty = return_place.ty(self.item.body, self.item.tcx).ty;
         let qualif = Q::in_call(
             self.item,
-            &|l| self.qualifs_per_local.contains(l),
+            &|l| self.contains_local(l),
             func,
             args,
             return_ty,
@@ -103,10 +248,10 @@ where
         }
 
         // If a local with no projections is moved from (e.g. `x` in `y = x`), record that
-        // it no longer needs to be dropped.
+        // it no longer has the qualif (e.g. it no longer needs to be dropped).
         if let mir::Operand::Move(place) = operand {
             if let Some(local) = place.as_local() {
-                self.qualifs_per_local.remove(local);
+                self.places.remove_local(self.state, local);
             }
         }
     }
@@ -117,7 +262,7 @@ where
         rvalue: &mir::Rvalue<'tcx>,
         location: Location,
     ) {
-        let qualif = Q::in_rvalue(self.item, &|l| self.qualifs_per_local.contains(l), rvalue);
+        let qualif = Q::in_rvalue(self.item, &|l| self.contains_local(l), rvalue);
         if !place.is_indirect() {
             self.assign_qualif_direct(place, qualif);
         }
@@ -132,7 +277,7 @@ where
         // here; that occurs in `apply_call_return_effect`.
 
         if let mir::TerminatorKind::DropAndReplace { value, location: dest, .. } = kind {
-            let qualif = Q::in_operand(self.item, &|l| self.qualifs_per_local.contains(l), value);
+            let qualif = Q::in_operand(self.item, &|l| self.contains_local(l), value);
             if !dest.is_indirect() {
                 self.assign_qualif_direct(dest, qualif);
             }
@@ -145,8 +290,9 @@ where
 }
 
 /// The dataflow analysis used to propagate qualifs on arbitrary CFGs.
-pub(super) struct FlowSensitiveAnalysis<'a, 'mir, 'tcx, Q> {
+pub(crate) struct FlowSensitiveAnalysis<'a, 'mir, 'tcx, Q> {
     item: &'a Item<'mir, 'tcx>,
+    places: TrackedPlaces,
     _qualif: PhantomData<Q>,
 }
 
@@ -154,67 +300,84 @@ impl<'a, 'mir, 'tcx, Q> FlowSensitiveAnalysis<'a, 'mir, 'tcx, Q>
 where
     Q: Qualif,
 {
-    pub(super) fn new(_: Q, item: &'a Item<'mir, 'tcx>) -> Self {
+    pub(crate) fn new(_: Q, item: &'a Item<'mir, 'tcx>) -> Self {
         FlowSensitiveAnalysis {
             item,
+            places: TrackedPlaces::new::<Q>(item),
             _qualif: PhantomData,
         }
     }
 
-    fn transfer_function(
-        &self,
-        state: &'a mut BitSet<Local>,
-    ) -> TransferFunction<'a, 'mir, 'tcx, Q> {
-        TransferFunction::<Q>::new(self.item, state)
+    pub(crate) fn places(&self) -> &TrackedPlaces {
+        &self.places
     }
-}
 
-impl<Q> old_dataflow::BottomValue for FlowSensitiveAnalysis<'_, '_, '_, Q> {
-    const BOTTOM_VALUE: bool = false;
+    fn transfer_function<'s>(
+        &'s self,
+        state: &'s mut BitSet<TrackedPlace>,
+    ) -> TransferFunction<'s, 'mir, 'tcx, Q> {
+        TransferFunction::<Q>::new(self.item, &self.places, state)
+    }
 }
 
 impl<Q> dataflow::Analysis<'tcx> for FlowSensitiveAnalysis<'_, '_, 'tcx, Q>
 where
     Q: Qualif,
 {
-    type Idx = Local;
+    type Domain = BitSet<TrackedPlace>;
 
     const NAME: &'static str = Q::ANALYSIS_NAME;
 
-    fn bits_per_block(&self, body: &mir::Body<'tcx>) -> usize {
-        body.local_decls.len()
+    fn bottom_value(&self, _body: &mir::Body<'tcx>) -> Self::Domain {
+        BitSet::new_empty(self.places.len)
     }
 
-    fn initialize_start_block(&self, _body: &mir::Body<'tcx>, state: &mut BitSet<Self::Idx>) {
+    fn initialize_start_block(&self, _body: &mir::Body<'tcx>, state: &mut Self::Domain) {
         self.transfer_function(state).initialize_state();
     }
 
     fn apply_statement_effect(
         &self,
-        state: &mut BitSet<Self::Idx>,
+        state: &mut Self::Domain,
         statement: &mir::Statement<'tcx>,
         location: Location,
     ) {
+        // Blocks that can never be reached must not affect the qualifs of any place, not even
+        // through the state they propagate to their successors.
+        if !self.item.reachable.contains(location.block) {
+            state.clear();
+            return;
+        }
+
         self.transfer_function(state).visit_statement(statement, location);
     }
 
     fn apply_terminator_effect(
         &self,
-        state: &mut BitSet<Self::Idx>,
+        state: &mut Self::Domain,
         terminator: &mir::Terminator<'tcx>,
         location: Location,
     ) {
+        if !self.item.reachable.contains(location.block) {
+            state.clear();
+            return;
+        }
+
         self.transfer_function(state).visit_terminator(terminator, location);
     }
 
     fn apply_call_return_effect(
         &self,
-        state: &mut BitSet<Self::Idx>,
+        state: &mut Self::Domain,
         block: BasicBlock,
         func: &mir::Operand<'tcx>,
         args: &[mir::Operand<'tcx>],
         return_place: &mir::Place<'tcx>,
     ) {
+        if !self.item.reachable.contains(block) {
+            return;
+        }
+
         self.transfer_function(state).apply_call_return_effect(block, func, args, return_place)
     }
 }
@@ -1,147 +1,470 @@
 //! The `Visitor` responsible for actually checking a `mir::Body` for invalid operations.
 
-use rustc::hir::{HirId, def_id::DefId};
+use rustc::hir::intravisit::{self, NestedVisitorMap};
+use rustc::hir::{self, HirId, def::DefKind, def_id::DefId};
+use rustc::lint::{self, builtin::CONST_ITEM_MUTATION};
 use rustc::middle::lang_items;
 use rustc::mir::visit::{PlaceContext, Visitor, MutatingUseContext, NonMutatingUseContext};
 use rustc::mir::*;
+use rustc::mir::traversal;
+use rustc::session::config;
 use rustc::traits::{self, TraitEngine};
+use rustc::ty::adjustment::PointerCast;
 use rustc::ty::cast::CastTy;
-use rustc::ty::{self, TyCtxt};
+use rustc::ty::{self, Instance, Ty, TyCtxt};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::Diagnostic;
+use rustc_feature::{find_feature_issue, GateIssue};
 use rustc_index::bit_set::BitSet;
 use rustc_target::spec::abi::Abi;
 use rustc_error_codes::*;
-use syntax::symbol::sym;
-use syntax_pos::Span;
+use syntax::symbol::{sym, Symbol};
+use syntax_pos::{DesugaringKind, Span};
 
 use std::borrow::Cow;
+use std::io;
 use std::ops::Deref;
 
 use crate::dataflow::{self as old_dataflow, generic as dataflow};
-use self::old_dataflow::IndirectlyMutableLocals;
+use self::dataflow::{Analysis, DebugState, JoinSemiLattice, ResultsVisitable};
+use crate::transform::promote_consts::{self, Candidate};
+use crate::transform::qualify_min_const_fn::{ViolatingOp, Violation};
+use crate::util::def_use::DefUseAnalysis;
+use self::old_dataflow::{BorrowedLocals, MaybeBorrowedLocals};
 use super::ops::{self, NonConstOp};
-use super::qualifs::{self, HasMutInterior, NeedsDrop};
-use super::resolver::FlowSensitiveAnalysis;
+use super::qualifs::{
+    self, ContainsPtr, CustomEq, HasMutInterior, NeedsDrop, NeedsNonConstDrop, RefToStatic,
+};
+use super::resolver::{FlowSensitiveAnalysis, TrackedPlace, TrackedPlaces};
 use super::{ConstKind, Item, Qualif, is_lang_panic_fn};
 
-pub type IndirectlyMutableResults<'mir, 'tcx> =
-    old_dataflow::DataflowResultsCursor<'mir, 'tcx, IndirectlyMutableLocals<'mir, 'tcx>>;
+type QualifResults<'a, 'mir, 'tcx, Q> =
+    dataflow::Results<'tcx, FlowSensitiveAnalysis<'a, 'mir, 'tcx, Q>>;
 
-struct QualifCursor<'a, 'mir, 'tcx, Q: Qualif> {
-    cursor: dataflow::ResultsCursor<'mir, 'tcx, FlowSensitiveAnalysis<'a, 'mir, 'tcx, Q>>,
-    in_any_value_of_ty: BitSet<Local>,
+fn qualif_results<Q: Qualif>(
+    q: Q,
+    item: &'a Item<'mir, 'tcx>,
+    dead_unwinds: &BitSet<BasicBlock>,
+) -> QualifResults<'a, 'mir, 'tcx, Q> {
+    let analysis = FlowSensitiveAnalysis::new(q, item);
+    dataflow::Engine::new(item.tcx, item.body, item.def_id, dead_unwinds, analysis)
+        .iterate_to_fixpoint()
 }
 
-impl<Q: Qualif> QualifCursor<'a, 'mir, 'tcx, Q> {
-    pub fn new(
-        q: Q,
-        item: &'a Item<'mir, 'tcx>,
-        dead_unwinds: &BitSet<BasicBlock>,
-    ) -> Self {
-        let analysis = FlowSensitiveAnalysis::new(q, item);
-        let results =
-            dataflow::Engine::new(item.tcx, item.body, item.def_id, dead_unwinds, analysis)
-                .iterate_to_fixpoint();
-        let cursor = dataflow::ResultsCursor::new(item.body, results);
+/// One value for each qualif that is tracked while checking the body.
+#[derive(PartialEq, Eq)]
+pub struct PerQualif<T> {
+    has_mut_interior: T,
+    needs_drop: T,
+    needs_non_const_drop: T,
+    ref_to_static: T,
+}
 
-        let mut in_any_value_of_ty = BitSet::new_empty(item.body.local_decls.len());
-        for (local, decl) in item.body.local_decls.iter_enumerated() {
-            if Q::in_any_value_of_ty(item, decl.ty) {
-                in_any_value_of_ty.insert(local);
-            }
+impl<T: Clone> Clone for PerQualif<T> {
+    fn clone(&self) -> Self {
+        PerQualif {
+            has_mut_interior: self.has_mut_interior.clone(),
+            needs_drop: self.needs_drop.clone(),
+            needs_non_const_drop: self.needs_non_const_drop.clone(),
+            ref_to_static: self.ref_to_static.clone(),
         }
+    }
+
+    fn clone_from(&mut self, other: &Self) {
+        self.has_mut_interior.clone_from(&other.has_mut_interior);
+        self.needs_drop.clone_from(&other.needs_drop);
+        self.needs_non_const_drop.clone_from(&other.needs_non_const_drop);
+        self.ref_to_static.clone_from(&other.ref_to_static);
+    }
+}
+
+impl<T: JoinSemiLattice> JoinSemiLattice for PerQualif<T> {
+    fn join(&mut self, other: &Self) -> bool {
+        // Don't short-circuit: every qualif must be joined.
+        let has_mut_interior = self.has_mut_interior.join(&other.has_mut_interior);
+        let needs_drop = self.needs_drop.join(&other.needs_drop);
+        let needs_non_const_drop = self.needs_non_const_drop.join(&other.needs_non_const_drop);
+        let ref_to_static = self.ref_to_static.join(&other.ref_to_static);
+        has_mut_interior || needs_drop || needs_non_const_drop || ref_to_static
+    }
+}
 
-        QualifCursor {
-            cursor,
-            in_any_value_of_ty,
+impl<T: DebugState> DebugState for PerQualif<T> {
+    fn fmt_state(&self, w: &mut impl io::Write) -> io::Result<()> {
+        write!(w, "has_mut_interior: ")?;
+        self.has_mut_interior.fmt_state(w)?;
+        write!(w, " needs_drop: ")?;
+        self.needs_drop.fmt_state(w)?;
+        write!(w, " needs_non_const_drop: ")?;
+        self.needs_non_const_drop.fmt_state(w)?;
+        write!(w, " ref_to_static: ")?;
+        self.ref_to_static.fmt_state(w)
+    }
+}
+
+/// The state of `FlowSensitiveQualifs` at a given point in the body.
+#[derive(PartialEq, Eq)]
+pub struct QualifState {
+    /// The qualifs of each `TrackedPlace`.
+    qualifs: PerQualif<BitSet<TrackedPlace>>,
+
+    /// The locals that may have been borrowed. These may have become qualified indirectly.
+    borrowed_locals: BorrowedLocals,
+}
+
+impl Clone for QualifState {
+    fn clone(&self) -> Self {
+        QualifState {
+            qualifs: self.qualifs.clone(),
+            borrowed_locals: self.borrowed_locals.clone(),
         }
     }
+
+    fn clone_from(&mut self, other: &Self) {
+        self.qualifs.clone_from(&other.qualifs);
+        self.borrowed_locals.clone_from(&other.borrowed_locals);
+    }
+}
+
+impl JoinSemiLattice for QualifState {
+    fn join(&mut self, other: &Self) -> bool {
+        let qualifs = self.qualifs.join(&other.qualifs);
+        let borrowed_locals = self.borrowed_locals.join(&other.borrowed_locals);
+        qualifs || borrowed_locals
+    }
+}
+
+impl DebugState for QualifState {
+    fn fmt_state(&self, w: &mut impl io::Write) -> io::Result<()> {
+        self.qualifs.fmt_state(w)?;
+        write!(w, " borrowed_locals: ")?;
+        self.borrowed_locals.fmt_state(w)
+    }
+}
+
+/// The flow-sensitive analysis of each qualif in `PerQualif`, along with `MaybeBorrowedLocals`.
+///
+/// All of these are needed to check a body, so a single fixpoint iteration computes them together
+/// instead of running one for each analysis.
+struct FlowSensitiveQualifs<'a, 'mir, 'tcx> {
+    has_mut_interior: FlowSensitiveAnalysis<'a, 'mir, 'tcx, HasMutInterior>,
+    needs_drop: FlowSensitiveAnalysis<'a, 'mir, 'tcx, NeedsDrop>,
+    needs_non_const_drop: FlowSensitiveAnalysis<'a, 'mir, 'tcx, NeedsNonConstDrop>,
+    ref_to_static: FlowSensitiveAnalysis<'a, 'mir, 'tcx, RefToStatic>,
+    borrowed_locals: MaybeBorrowedLocals,
+}
+
+impl FlowSensitiveQualifs<'a, 'mir, 'tcx> {
+    fn new(item: &'a Item<'mir, 'tcx>) -> Self {
+        FlowSensitiveQualifs {
+            has_mut_interior: FlowSensitiveAnalysis::new(HasMutInterior, item),
+            needs_drop: FlowSensitiveAnalysis::new(NeedsDrop, item),
+            needs_non_const_drop: FlowSensitiveAnalysis::new(NeedsNonConstDrop, item),
+            ref_to_static: FlowSensitiveAnalysis::new(RefToStatic, item),
+            borrowed_locals: MaybeBorrowedLocals,
+        }
+    }
+}
+
+impl dataflow::Analysis<'tcx> for FlowSensitiveQualifs<'_, '_, 'tcx> {
+    type Domain = QualifState;
+
+    const NAME: &'static str = "flow_qualifs";
+
+    fn bottom_value(&self, body: &Body<'tcx>) -> Self::Domain {
+        QualifState {
+            qualifs: PerQualif {
+                has_mut_interior: self.has_mut_interior.bottom_value(body),
+                needs_drop: self.needs_drop.bottom_value(body),
+                needs_non_const_drop: self.needs_non_const_drop.bottom_value(body),
+                ref_to_static: self.ref_to_static.bottom_value(body),
+            },
+            borrowed_locals: self.borrowed_locals.bottom_value(body),
+        }
+    }
+
+    fn initialize_start_block(&self, body: &Body<'tcx>, state: &mut Self::Domain) {
+        let qualifs = &mut state.qualifs;
+        self.has_mut_interior.initialize_start_block(body, &mut qualifs.has_mut_interior);
+        self.needs_drop.initialize_start_block(body, &mut qualifs.needs_drop);
+        self.needs_non_const_drop.initialize_start_block(body, &mut qualifs.needs_non_const_drop);
+        self.ref_to_static.initialize_start_block(body, &mut qualifs.ref_to_static);
+        self.borrowed_locals.initialize_start_block(body, &mut state.borrowed_locals);
+    }
+
+    fn apply_statement_effect(
+        &self,
+        state: &mut Self::Domain,
+        stmt: &Statement<'tcx>,
+        loc: Location,
+    ) {
+        let qualifs = &mut state.qualifs;
+        self.has_mut_interior.apply_statement_effect(&mut qualifs.has_mut_interior, stmt, loc);
+        self.needs_drop.apply_statement_effect(&mut qualifs.needs_drop, stmt, loc);
+        self.needs_non_const_drop
+            .apply_statement_effect(&mut qualifs.needs_non_const_drop, stmt, loc);
+        self.ref_to_static.apply_statement_effect(&mut qualifs.ref_to_static, stmt, loc);
+        self.borrowed_locals.apply_statement_effect(&mut state.borrowed_locals, stmt, loc);
+    }
+
+    fn apply_terminator_effect(
+        &self,
+        state: &mut Self::Domain,
+        term: &Terminator<'tcx>,
+        loc: Location,
+    ) {
+        let qualifs = &mut state.qualifs;
+        self.has_mut_interior.apply_terminator_effect(&mut qualifs.has_mut_interior, term, loc);
+        self.needs_drop.apply_terminator_effect(&mut qualifs.needs_drop, term, loc);
+        self.needs_non_const_drop
+            .apply_terminator_effect(&mut qualifs.needs_non_const_drop, term, loc);
+        self.ref_to_static.apply_terminator_effect(&mut qualifs.ref_to_static, term, loc);
+        self.borrowed_locals.apply_terminator_effect(&mut state.borrowed_locals, term, loc);
+    }
+
+    fn apply_call_return_effect(
+        &self,
+        state: &mut Self::Domain,
+        block: BasicBlock,
+        func: &Operand<'tcx>,
+        args: &[Operand<'tcx>],
+        return_place: &Place<'tcx>,
+    ) {
+        let qualifs = &mut state.qualifs;
+        self.has_mut_interior.apply_call_return_effect(
+            &mut qualifs.has_mut_interior,
+            block,
+            func,
+            args,
+            return_place,
+        );
+        self.needs_drop
+            .apply_call_return_effect(&mut qualifs.needs_drop, block, func, args, return_place);
+        self.needs_non_const_drop.apply_call_return_effect(
+            &mut qualifs.needs_non_const_drop,
+            block,
+            func,
+            args,
+            return_place,
+        );
+        self.ref_to_static
+            .apply_call_return_effect(&mut qualifs.ref_to_static, block, func, args, return_place);
+        self.borrowed_locals.apply_call_return_effect(
+            &mut state.borrowed_locals,
+            block,
+            func,
+            args,
+            return_place,
+        );
+    }
 }
 
 pub struct Qualifs<'a, 'mir, 'tcx> {
-    has_mut_interior: QualifCursor<'a, 'mir, 'tcx, HasMutInterior>,
-    needs_drop: QualifCursor<'a, 'mir, 'tcx, NeedsDrop>,
-    indirectly_mutable: IndirectlyMutableResults<'mir, 'tcx>,
+    item: &'a Item<'mir, 'tcx>,
+
+    /// The results of the flow-sensitive analysis of each qualif.
+    ///
+    /// The `Validator` visits these results with itself as the visitor, so they are moved out of
+    /// here while `check_body` runs.
+    results: Option<dataflow::Results<'tcx, FlowSensitiveQualifs<'a, 'mir, 'tcx>>>,
+
+    /// The qualifs of each `TrackedPlace`, as well as the locals that may have been borrowed,
+    /// immediately before the location being checked.
+    state: QualifState,
+
+    /// The places tracked by the flow-sensitive analysis of each qualif. A local that is not
+    /// tracked can never have that qualif.
+    places: PerQualif<TrackedPlaces>,
 }
 
 impl Qualifs<'a, 'mir, 'tcx> {
-    fn indirectly_mutable(&mut self, local: Local, location: Location) -> bool {
-        self.indirectly_mutable.seek(location);
-        self.indirectly_mutable.get().contains(local)
+    /// Returns `true` if `local` may have been mutated through a `&mut` (or a raw pointer derived
+    /// from one).
+    fn maybe_mut_borrowed(&self, local: Local) -> bool {
+        self.state.borrowed_locals.mutable.contains(local)
     }
 
-    /// Returns `true` if `local` is `NeedsDrop` at the given `Location`.
+    /// Returns `true` if `local` may have been mutated indirectly.
     ///
-    /// Only updates the cursor if absolutely necessary
-    fn needs_drop_lazy_seek(&mut self, local: Local, location: Location) -> bool {
-        if !self.needs_drop.in_any_value_of_ty.contains(local) {
+    /// Besides a mutable borrow, this includes a shared borrow if the type of `local` has interior
+    /// mutability.
+    fn indirectly_mutable(&self, local: Local) -> bool {
+        let borrowed = &self.state.borrowed_locals;
+
+        borrowed.mutable.contains(local)
+            || (borrowed.shared.contains(local)
+                && !self.item.is_freeze(self.item.body.local_decls[local].ty))
+    }
+
+    /// Returns `true` if `local` is `NeedsDrop` before the location being checked.
+    fn needs_drop(&self, local: Local) -> bool {
+        if !self.places.needs_drop.is_tracked(local) {
             return false;
         }
 
-        self.needs_drop.cursor.seek_before(location);
-        self.needs_drop.cursor.get().contains(local)
-            || self.indirectly_mutable(local, location)
+        self.places.needs_drop.contains_local(&self.state.qualifs.needs_drop, local)
+            || self.indirectly_mutable(local)
     }
 
-    /// Returns `true` if `local` is `HasMutInterior` at the given `Location`.
-    ///
-    /// Only updates the cursor if absolutely necessary.
-    fn has_mut_interior_lazy_seek(&mut self, local: Local, location: Location) -> bool {
-        if !self.has_mut_interior.in_any_value_of_ty.contains(local) {
+    /// Returns `true` if `local` is `NeedsNonConstDrop` before the location being checked.
+    fn needs_non_const_drop(&self, local: Local) -> bool {
+        if !self.places.needs_non_const_drop.is_tracked(local) {
             return false;
         }
 
-        self.has_mut_interior.cursor.seek_before(location);
-        self.has_mut_interior.cursor.get().contains(local)
-            || self.indirectly_mutable(local, location)
+        let state = &self.state.qualifs.needs_non_const_drop;
+        self.places.needs_non_const_drop.contains_local(state, local)
+            || self.indirectly_mutable(local)
     }
 
-    /// Returns `true` if `local` is `HasMutInterior`, but requires the `has_mut_interior` and
-    /// `indirectly_mutable` cursors to be updated beforehand.
-    fn has_mut_interior_eager_seek(&self, local: Local) -> bool {
-        if !self.has_mut_interior.in_any_value_of_ty.contains(local) {
+    /// Returns `true` if `local` is `RefToStatic` before the location being checked.
+    fn ref_to_static(&self, local: Local) -> bool {
+        if !self.places.ref_to_static.is_tracked(local) {
             return false;
         }
 
-        self.has_mut_interior.cursor.get().contains(local)
-            || self.indirectly_mutable.get().contains(local)
+        self.places.ref_to_static.contains_local(&self.state.qualifs.ref_to_static, local)
+    }
+
+    /// Returns `true` if `local` is `HasMutInterior` before the location being checked.
+    fn has_mut_interior(&self, local: Local) -> bool {
+        if !self.places.has_mut_interior.is_tracked(local) {
+            return false;
+        }
+
+        // A shared borrow only allows mutation inside an `UnsafeCell`, and any value that contains
+        // one is already `HasMutInterior`. Only a mutable borrow can make `local` qualified.
+        self.places.has_mut_interior.contains_local(&self.state.qualifs.has_mut_interior, local)
+            || self.maybe_mut_borrowed(local)
+    }
+
+    /// Like `has_mut_interior`, but only considers the given field of `local`.
+    fn has_mut_interior_field(&self, local: Local, field: Field) -> bool {
+        if !self.places.has_mut_interior.is_tracked(local) {
+            return false;
+        }
+
+        let state = &self.state.qualifs.has_mut_interior;
+        self.places.has_mut_interior.contains_field(state, local, field)
+            || self.maybe_mut_borrowed(local)
+    }
+
+    /// Overwrites `state` with its value immediately before the primary effect of the statement or
+    /// terminator at `location`, like `ResultsCursor::seek_before_primary_effect`.
+    fn seek_before_primary_effect(&mut self, body: &Body<'tcx>, location: Location) {
+        let results = self.results.as_ref().expect("qualif results are being visited");
+        let Location { block, statement_index } = location;
+
+        results.reset_to_block_start(&mut self.state, block);
+        for (i, stmt) in body[block].statements[..statement_index].iter().enumerate() {
+            let loc = Location { block, statement_index: i };
+            results.reconstruct_statement_effect(&mut self.state, stmt, loc);
+        }
     }
 
     fn in_return_place(&mut self, item: &Item<'_, 'tcx>) -> ConstQualifs {
-        // Find the `Return` terminator if one exists.
-        //
         // If no `Return` terminator exists, this MIR is divergent. Just return the conservative
         // qualifs for the return type.
-        let return_block = item.body
-            .basic_blocks()
-            .iter_enumerated()
-            .find(|(_, block)| {
-                match block.terminator().kind {
-                    TerminatorKind::Return => true,
-                    _ => false,
-                }
-            })
-            .map(|(bb, _)| bb);
-
-        let return_block = match return_block {
+        let return_loc = match return_location(item.body) {
             None => return qualifs::in_any_value_of_ty(item, item.body.return_ty()),
-            Some(bb) => bb,
+            Some(loc) => loc,
         };
 
-        let return_loc = item.body.terminator_loc(return_block);
+        self.seek_before_primary_effect(item.body, return_loc);
 
         ConstQualifs {
-            needs_drop: self.needs_drop_lazy_seek(RETURN_PLACE, return_loc),
-            has_mut_interior: self.has_mut_interior_lazy_seek(RETURN_PLACE, return_loc),
+            needs_drop: self.needs_drop(RETURN_PLACE),
+            needs_non_const_drop: self.needs_non_const_drop(RETURN_PLACE),
+            has_mut_interior: self.has_mut_interior(RETURN_PLACE),
+            custom_eq: in_final_value_of_const(CustomEq, item),
+            contains_ptr: in_final_value_of_const(ContainsPtr, item),
+            ref_to_static: self.ref_to_static(RETURN_PLACE),
         }
     }
 }
 
+/// Returns the location of the `Return` terminator of `body`, or `None` if `body` is divergent.
+fn return_location(body: &Body<'_>) -> Option<Location> {
+    body.basic_blocks()
+        .iter_enumerated()
+        .find(|(_, block)| {
+            match block.terminator().kind {
+                TerminatorKind::Return => true,
+                _ => false,
+            }
+        })
+        .map(|(bb, _)| body.terminator_loc(bb))
+}
+
+/// Returns `true` if the return place of `item` may have the qualif `Q` when its body returns.
+///
+/// This runs the flow-sensitive analysis for `Q` on its own, so it can be used for qualifs that are
+/// not computed by the `Validator`, including an `ExtraQualif` defined outside this module.
+pub fn in_return_place<Q: Qualif>(q: Q, item: &Item<'_, 'tcx>) -> bool {
+    let return_ty = item.body.return_ty();
+
+    // If no value of the return type has the qualif, there is no need to run dataflow.
+    if !Q::in_any_value_of_ty(item, return_ty) {
+        return false;
+    }
+
+    let return_loc = match return_location(item.body) {
+        None => return true,
+        Some(loc) => loc,
+    };
+
+    let dead_unwinds = old_dataflow::dead_unwinds(item.tcx, item.body, item.def_id, item.param_env);
+    let mut cursor = qualif_results(q, item, &dead_unwinds).into_results_cursor(item.body);
+    cursor.seek_before_primary_effect(return_loc);
+    cursor.analysis().places().contains_local(cursor.get(), RETURN_PLACE)
+}
+
+/// Returns `true` if the final value of a `const` may have the qualif `Q`.
+///
+/// This is for qualifs that only matter for the final value of a `const` (e.g. when it is used as
+/// a pattern), so they are not tracked while checking the body.
+fn in_final_value_of_const<Q: Qualif>(q: Q, item: &Item<'_, 'tcx>) -> bool {
+    match item.const_kind() {
+        // Calls to a `const fn` always use type-based qualification, so the value here is never
+        // used.
+        ConstKind::ConstFn | ConstKind::Static | ConstKind::StaticMut => true,
+
+        ConstKind::Const => in_return_place(q, item),
+    }
+}
+
 pub struct Validator<'a, 'mir, 'tcx> {
     item: &'a Item<'mir, 'tcx>,
     qualifs: Qualifs<'a, 'mir, 'tcx>,
 
     /// The span of the current statement.
     span: Span,
+
+    /// If `true`, no errors are emitted. `found_error` is set instead when one would have been.
+    silent: bool,
+    found_error: bool,
+
+    /// The errors found so far. They are emitted at the end of `check_body`.
+    errors: Vec<BufferedError>,
+
+    /// The number of times each operation was checked at each span. Only the first time is
+    /// reported.
+    occurrences: FxHashMap<(&'static str, Span), usize>,
+
+    /// The operations that were allowed only because of `-Zunleash-the-miri-inside-of-you`, along
+    /// with their spans.
+    skipped_checks: Vec<(&'static str, Span)>,
+}
+
+/// An error for an operation that is not allowed in the current item.
+struct BufferedError {
+    /// The name of the `NonConstOp` that produced this error.
+    op: &'static str,
+    span: Span,
+    diag: Diagnostic,
+
+    /// The spans of later operations of the same kind that produced an identical error.
+    other_spans: Vec<Span>,
 }
 
 impl Deref for Validator<'_, 'mir, 'tcx> {
@@ -156,48 +479,50 @@ impl Validator<'a, 'mir, 'tcx> {
     pub fn new(
         item: &'a Item<'mir, 'tcx>,
     ) -> Self {
-        let dead_unwinds = BitSet::new_empty(item.body.basic_blocks().len());
-
-        let needs_drop = QualifCursor::new(
-            NeedsDrop,
-            item,
-            &dead_unwinds,
-        );
-
-        let has_mut_interior = QualifCursor::new(
-            HasMutInterior,
-            item,
-            &dead_unwinds,
-        );
+        let dead_unwinds =
+            old_dataflow::dead_unwinds(item.tcx, item.body, item.def_id, item.param_env);
 
-        let indirectly_mutable = old_dataflow::do_dataflow(
-            item.tcx,
-            item.body,
-            item.def_id,
-            &item.tcx.get_attrs(item.def_id),
-            &dead_unwinds,
-            old_dataflow::IndirectlyMutableLocals::new(item.tcx, item.body, item.param_env),
-            |_, local| old_dataflow::DebugFormatted::new(&local),
-        );
-
-        let indirectly_mutable = old_dataflow::DataflowResultsCursor::new(
-            indirectly_mutable,
-            item.body,
-        );
+        let analysis = FlowSensitiveQualifs::new(item);
+        let results =
+            dataflow::Engine::new(item.tcx, item.body, item.def_id, &dead_unwinds, analysis)
+                .iterate_to_fixpoint();
 
         let qualifs = Qualifs {
-            needs_drop,
-            has_mut_interior,
-            indirectly_mutable,
+            item,
+            state: results.new_flow_state(item.body),
+            results: Some(results),
+            places: PerQualif {
+                has_mut_interior: TrackedPlaces::new::<HasMutInterior>(item),
+                needs_drop: TrackedPlaces::new::<NeedsDrop>(item),
+                needs_non_const_drop: TrackedPlaces::new::<NeedsNonConstDrop>(item),
+                ref_to_static: TrackedPlaces::new::<RefToStatic>(item),
+            },
         };
 
         Validator {
             span: item.body.span,
             item,
             qualifs,
+            silent: false,
+            found_error: false,
+            errors: vec![],
+            occurrences: Default::default(),
+            skipped_checks: vec![],
         }
     }
 
+    /// Creates a `Validator` that does not emit any errors.
+    ///
+    /// Call `found_error` after `check_body` to learn whether the body is valid.
+    pub fn new_silent(item: &'a Item<'mir, 'tcx>) -> Self {
+        Validator { silent: true, ..Validator::new(item) }
+    }
+
+    /// Returns `true` if a silent `Validator` found an operation that is not allowed in its item.
+    pub fn found_error(&self) -> bool {
+        self.found_error
+    }
+
     pub fn check_body(&mut self) {
         let Item { tcx, body, def_id, const_kind, ..  } = *self.item;
 
@@ -208,8 +533,11 @@ impl Validator<'a, 'mir, 'tcx> {
         if use_min_const_fn_checks {
             // Enforce `min_const_fn` for stable `const fn`s.
             use crate::transform::qualify_min_const_fn::is_min_const_fn;
-            if let Err((span, err)) = is_min_const_fn(tcx, def_id, body) {
-                error_min_const_fn_violation(tcx, span, err);
+            if let Err(violations) = is_min_const_fn(tcx, def_id, body) {
+                for (span, violation) in violations {
+                    self.check_min_const_fn_violation(span, violation);
+                }
+                self.emit_errors();
                 return;
             }
         }
@@ -217,12 +545,46 @@ impl Validator<'a, 'mir, 'tcx> {
         check_short_circuiting_in_const_local(self.item);
 
         if body.is_cfg_cyclic() {
-            // We can't provide a good span for the error here, but this should be caught by the
-            // HIR const-checker anyways.
-            self.check_op_spanned(ops::Loop, body.span);
+            // This should be caught by the HIR const-checker anyways, but point at each loop if we
+            // can find it.
+            let loop_spans = loop_head_spans(self.tcx, body);
+            if loop_spans.is_empty() {
+                self.check_op_spanned(ops::Loop, body.span);
+            }
+
+            for span in loop_spans {
+                self.check_op_spanned(ops::Loop, span);
+            }
+        }
+
+        // Blocks on the unwind path are not checked, just as the old checker did. These blocks
+        // often drop locals that would otherwise be returned from the function.
+        //
+        // FIXME: This shouldn't be unsound since a panic at compile time will cause a compiler
+        // error anyway, but maybe we should do more here?
+        //
+        // Operations in blocks that can never be reached (e.g. the body of `if false { ... }`) are
+        // never evaluated, so they don't need to be checked either.
+        let item = self.item;
+        let blocks = body.basic_blocks()
+            .iter_enumerated()
+            .filter(|(bb, block)| !block.is_cleanup && item.reachable.contains(*bb))
+            .map(|(bb, _)| bb);
+
+        // The `Validator` is the visitor of the qualif results it holds, so move them out while
+        // visiting.
+        let results = self.qualifs.results.take().expect("`check_body` called twice");
+        dataflow::visit_results(body, blocks, &results, self);
+        self.qualifs.results = Some(results);
+
+        // Outside of a const context, promotion already reports arguments that are not
+        // constants, so there is nothing more for a silent `Validator` to learn here.
+        if !self.silent {
+            self.check_args_required_const();
         }
 
-        self.visit_body(body);
+        self.warn_skipped_checks();
+        self.emit_errors();
 
         // Ensure that the end result is `Sync` in a non-thread local `static`.
         let should_check_for_sync = const_kind == Some(ConstKind::Static)
@@ -238,6 +600,62 @@ impl Validator<'a, 'mir, 'tcx> {
         self.qualifs.in_return_place(self.item)
     }
 
+    /// Reports a violation of the `min_const_fn` rules.
+    ///
+    /// If the rest of the const checker rejects the same operation, its error for that operation
+    /// is used instead of the generic `min_const_fn` one.
+    fn check_min_const_fn_violation(&mut self, span: Span, violation: Violation) {
+        let Violation { msg, op } = violation;
+        let op = match op {
+            Some(op) => op,
+            None => {
+                error_min_const_fn_violation(self.tcx, span, msg);
+                return;
+            }
+        };
+
+        match op {
+            ViolatingOp::Call(def_id) => {
+                let is_trait_method = self.tcx.trait_of_item(def_id).is_some();
+                let is_for_loop_call = is_trait_method
+                    && span.desugaring_kind() == Some(DesugaringKind::ForLoop)
+                    && matches!(self.tcx.item_name(def_id), sym::into_iter | sym::next);
+
+                if is_for_loop_call {
+                    self.check_min_const_fn_op(ops::ForLoopIterator(def_id), span, msg)
+                } else if is_trait_method {
+                    self.check_min_const_fn_op(ops::FnCallTraitMethod(def_id), span, msg)
+                } else if is_lang_panic_fn(self.tcx, def_id) {
+                    self.check_min_const_fn_op(ops::Panic, span, msg)
+                } else {
+                    self.check_min_const_fn_op(ops::FnCallNonConst(def_id), span, msg)
+                }
+            }
+
+            ViolatingOp::HeapAllocation => {
+                self.check_min_const_fn_op(ops::HeapAllocation, span, msg)
+            }
+            ViolatingOp::InlineAsm => self.check_min_const_fn_op(ops::InlineAsm, span, msg),
+            ViolatingOp::RawPtrToIntCast => {
+                self.check_min_const_fn_op(ops::RawPtrToIntCast, span, msg)
+            }
+            ViolatingOp::StaticAccess(def_id) => {
+                self.check_min_const_fn_op(ops::StaticAccess(Some(def_id)), span, msg)
+            }
+            ViolatingOp::UnionAccess => self.check_min_const_fn_op(ops::UnionAccess, span, msg),
+        }
+    }
+
+    /// Emits the error for `op`, or the `min_const_fn` error `msg` if `op` is allowed in a
+    /// `const fn` that does not have to follow the `min_const_fn` rules.
+    fn check_min_const_fn_op(&mut self, op: impl NonConstOp, span: Span, msg: Cow<'_, str>) {
+        if op.is_allowed_in_item(self) {
+            error_min_const_fn_violation(self.tcx, span, msg);
+        } else {
+            self.check_op_spanned(op, span);
+        }
+    }
+
     /// Emits an error at the given `span` if an expression cannot be evaluated in the current
     /// context.
     pub fn check_op_spanned<O>(&mut self, op: O, span: Span)
@@ -250,17 +668,124 @@ impl Validator<'a, 'mir, 'tcx> {
             return;
         }
 
+        if self.silent {
+            self.found_error = true;
+            return;
+        }
+
         // If an operation is supported in miri (and is not already controlled by a feature gate) it
         // can be turned on with `-Zunleash-the-miri-inside-of-you`.
         let is_unleashable = O::IS_SUPPORTED_IN_MIRI
-            && O::feature_gate(self.tcx).is_none();
+            && O::feature_gate().is_none();
 
         if is_unleashable && self.tcx.sess.opts.debugging_opts.unleash_the_miri_inside_of_you {
-            self.tcx.sess.span_warn(span, "skipping const checks");
+            let skipped = (O::name(), span);
+            if !self.skipped_checks.contains(&skipped) {
+                self.skipped_checks.push(skipped);
+            }
+
             return;
         }
 
-        op.emit_error(self, span);
+        // Macros that repeat an argument can make us check the same operation at the same span
+        // many times. Report it once, with a note saying how often it occurred.
+        let occurrences = self.occurrences.entry((O::name(), span)).or_insert(0);
+        *occurrences += 1;
+        if *occurrences > 1 {
+            return;
+        }
+
+        let mut diags = vec![];
+        op.emit_error(self, span, &mut diags);
+
+        if let Some(gate) = O::feature_gate() {
+            if op.is_gated_in_item(self) {
+                for diag in &mut diags {
+                    add_feature_gate_help(self.tcx, gate, diag);
+                }
+            }
+        }
+
+        // `-Zconst-check-level` can downgrade the errors for operations that miri can evaluate to
+        // warnings. If an operation is given more than once, the last level wins.
+        let op = O::name();
+        debug_assert!(
+            config::CONST_CHECK_OPS.contains(&(op, O::IS_SUPPORTED_IN_MIRI)),
+            "`{}` is missing from `CONST_CHECK_OPS`",
+            op,
+        );
+        let level = self.tcx.sess.opts.debugging_opts.const_check_level
+            .iter()
+            .rev()
+            .find(|(name, _)| name == op)
+            .map(|&(_, level)| level);
+
+        if O::IS_SUPPORTED_IN_MIRI && level == Some(lint::Level::Warn) {
+            for diag in &mut diags {
+                diag.level = rustc_errors::Level::Warning;
+                diag.note(&format!(
+                    "this error was downgraded to a warning by `-Zconst-check-level={}=warn`",
+                    op,
+                ));
+            }
+        }
+
+        // A body often contains several operations of the same kind, e.g. a call to a
+        // non-`const fn` on each line. Report those in a single error that points at each of them.
+        for mut diag in diags {
+            // Lets tools consuming `--error-format=json` tell apart errors that share a code.
+            diag.set_metadata("const_checking.op", op);
+
+            let existing = self.errors.iter_mut().find(|error| {
+                error.op == op
+                    && error.diag.code == diag.code
+                    && error.diag.message() == diag.message()
+            });
+
+            match existing {
+                Some(error) => error.other_spans.push(span),
+                None => self.errors.push(BufferedError { op, span, diag, other_spans: vec![] }),
+            }
+        }
+    }
+
+    /// Emits a single warning that lists every check skipped in this item.
+    fn warn_skipped_checks(&mut self) {
+        if self.skipped_checks.is_empty() {
+            return;
+        }
+
+        let spans: Vec<_> = self.skipped_checks.iter().map(|&(_, span)| span).collect();
+        let mut warning = self.tcx.sess.struct_span_warn(spans, "skipping const checks");
+        for (op, span) in self.skipped_checks.drain(..) {
+            warning.span_label(span, format!("skipped `{}` check", op));
+        }
+
+        warning.emit();
+    }
+
+    /// Emits the errors found while checking the body.
+    fn emit_errors(&mut self) {
+        let const_kind = self.const_kind();
+        let occurrences = &self.occurrences;
+        for BufferedError { op, span, mut diag, other_spans } in self.errors.drain(..) {
+            let occurrences = |span| occurrences.get(&(op, span)).copied().unwrap_or(1);
+
+            for span in other_spans {
+                let label = match occurrences(span) {
+                    1 => format!("also not allowed in {}s", const_kind),
+                    n => format!("also not allowed in {}s ({} times)", const_kind, n),
+                };
+                diag.span_label(span, label);
+            }
+
+            let n = occurrences(span);
+            if n > 1 {
+                diag.note(&format!("this operation occurs {} times at this location", n));
+            }
+
+            self.item.tcx.sess.diagnostic().emit_diagnostic(&diag);
+        }
     }
 
     /// Emits an error if an expression cannot be evaluated in the current context.
@@ -269,36 +794,301 @@ impl Validator<'a, 'mir, 'tcx> {
         self.check_op_spanned(op, span)
     }
 
+    /// Emits an error if an expression cannot be evaluated in the current context, pointing at the
+    /// sub-expression of the current statement for which `is_op` returns `true`.
+    ///
+    /// Projections don't have a span of their own, so e.g. a dereference on the left-hand side of
+    /// an assignment would otherwise underline the whole assignment. If `is_op` matches no
+    /// sub-expression or more than one, the error points at the whole statement instead.
+    fn check_op_in_subexpr<O>(
+        &mut self,
+        op: O,
+        is_op: impl Fn(&ty::TypeckTables<'tcx>, &hir::Expr) -> bool,
+    )
+    where
+        O: NonConstOp
+    {
+        // Finding the sub-expression requires walking the HIR, so only do it if we will actually
+        // report an error.
+        if self.silent || op.is_allowed_in_item(self) {
+            self.check_op(op);
+            return;
+        }
+
+        let span = self.subexpr_span(&is_op).unwrap_or(self.span);
+        self.check_op_spanned(op, span)
+    }
+
+    /// Returns the span of the only expression inside the current statement for which `is_op`
+    /// returns `true`.
+    fn subexpr_span(
+        &self,
+        is_op: &dyn Fn(&ty::TypeckTables<'tcx>, &hir::Expr) -> bool,
+    ) -> Option<Span> {
+        let tcx = self.tcx;
+        let hir_id = tcx.hir().as_local_hir_id(self.def_id)?;
+        let body = tcx.hir().body(tcx.hir().maybe_body_owned_by(hir_id)?);
+
+        let mut finder = SubexprFinder {
+            within: self.span,
+            tables: tcx.typeck_tables_of(self.def_id),
+            is_op,
+            found: vec![],
+        };
+        intravisit::walk_body(&mut finder, body);
+
+        match finder.found[..] {
+            [span] => Some(span),
+            _ => None,
+        }
+    }
+
+    /// Checks an operation that is only allowed in `unsafe` code.
+    ///
+    /// Outside of `unsafe` code in a `const fn`, the unsafety checker already reports an error for
+    /// the operation, so we don't emit a second one for the same expression.
+    fn check_unsafe_op<O>(
+        &mut self,
+        op: O,
+        location: Location,
+        is_op: impl Fn(&ty::TypeckTables<'tcx>, &hir::Expr) -> bool,
+    )
+    where
+        O: NonConstOp
+    {
+        if self.const_kind() == ConstKind::ConstFn && !self.is_in_unsafe_scope(location) {
+            return;
+        }
+
+        self.check_op_in_subexpr(op, is_op);
+    }
+
+    /// Returns `true` if `location` is inside an `unsafe` block or the body of an `unsafe fn`.
+    fn is_in_unsafe_scope(&self, location: Location) -> bool {
+        let scope = self.body.source_info(location).scope;
+        let safety = self.body.source_scopes[scope]
+            .local_data
+            .as_ref()
+            .assert_crate_local()
+            .safety;
+
+        !matches!(safety, Safety::Safe)
+    }
+
+    /// Emits an error for every argument to a `#[rustc_args_required_const]` function (or SIMD
+    /// shuffle) that cannot be promoted to a constant.
+    ///
+    /// Whether an argument is promotable is decided by the same logic that will later promote it.
+    fn check_args_required_const(&mut self) {
+        let Item { tcx, body, def_id, .. } = *self.item;
+
+        let mut rpo = traversal::reverse_postorder(body);
+        let (temps, candidates) = promote_consts::collect_temps_and_candidates(tcx, body, &mut rpo);
+
+        let arg_candidates: Vec<_> = candidates
+            .into_iter()
+            .filter(|candidate| match candidate {
+                Candidate::Argument { .. } => true,
+                _ => false,
+            })
+            .collect();
+
+        if arg_candidates.is_empty() {
+            return;
+        }
+
+        let promotable =
+            promote_consts::validate_candidates(tcx, body, def_id, &temps, &arg_candidates);
+
+        for candidate in arg_candidates {
+            if let Candidate::Argument { bb, index } = candidate {
+                if !promotable.contains(&candidate) {
+                    let span = body[bb].terminator().source_info.span;
+                    self.check_op_spanned(ops::NonConstArgument(index), span);
+                }
+            }
+        }
+    }
+
+    /// Finds the type that makes `ty` interior mutable, for use in diagnostics.
+    ///
+    /// This looks through tuples, arrays and the fields of ADTs that are visible from the current
+    /// item, so we report e.g. the `Cell` inside an `Option<Cell<u8>>`. `field_span` is the field
+    /// of a local type through which `ty` was reached.
+    fn find_interior_mut(
+        &self,
+        ty: Ty<'tcx>,
+        field_span: Option<Span>,
+    ) -> Option<ops::InteriorMut> {
+        if self.is_freeze(ty) {
+            return None;
+        }
+
+        let tcx = self.tcx;
+        let components: Vec<_> = match ty.kind {
+            ty::Adt(def, substs) => {
+                let hir_id = tcx.hir().as_local_hir_id(self.def_id).unwrap();
+                let module = tcx.hir().get_module_parent(hir_id);
+                if def.all_fields().all(|field| field.vis.is_accessible_from(module, tcx)) {
+                    def.all_fields()
+                        .map(|field| {
+                            let span = if field.did.is_local() {
+                                Some(tcx.def_span(field.did))
+                            } else {
+                                field_span
+                            };
+                            (field.ty(tcx, substs), span)
+                        })
+                        .collect()
+                } else {
+                    vec![]
+                }
+            }
+
+            ty::Tuple(_) => ty.tuple_fields().map(|ty| (ty, field_span)).collect(),
+            ty::Array(elem, _) | ty::Slice(elem) => vec![(elem, field_span)],
+            _ => vec![],
+        };
+
+        let component = components
+            .into_iter()
+            .find_map(|(ty, field_span)| self.find_interior_mut(ty, field_span));
+
+        component.or_else(|| Some(ops::InteriorMut { ty: ty.to_string(), field_span }))
+    }
+
+    /// Returns `true` if `place` is the local holding the iterator in the desugaring of a `for`
+    /// loop.
+    fn is_for_loop_iterator(&self, place: &Place<'tcx>) -> bool {
+        match place.as_local() {
+            Some(local) => {
+                let span = self.body.local_decls[local].source_info.span;
+                span.desugaring_kind() == Some(DesugaringKind::ForLoop)
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if `place` may be borrowed mutably (or have a `*mut` pointer taken to it)
+    /// because the item being checked is a `static mut`.
+    fn is_mut_borrow_allowed_in_static_mut(&self, place: &Place<'tcx>) -> bool {
+        if self.const_kind() != ConstKind::StaticMut {
+            return false;
+        }
+
+        let ty = place.ty(self.body, self.tcx).ty;
+        let is_borrow_of_static = match place.base {
+            PlaceBase::Local(local) => self.body.local_decls[local].is_ref_to_static(),
+            PlaceBase::Static(_) => true,
+        };
+
+        match ty.kind {
+            // Inside a `static mut`, `&mut [...]` is allowed.
+            ty::Array(..) | ty::Slice(_) => true,
+
+            // Inside a `static mut`, `&mut` is also allowed for any value without drop glue that
+            // does not live in another `static`. Such a value ends up in the mutable memory of the
+            // `static mut` and is never dropped.
+            _ => !is_borrow_of_static && !self.needs_drop(ty),
+        }
+    }
+
+    /// Returns `true` if `kind` and `place` are those of a two-phase borrow of a place behind a
+    /// `&mut` reference.
+    ///
+    /// Two-phase borrows are only created for autorefs, such as the `&mut self` receiver of
+    /// `v.field.push(x)` where `v: &mut _`. Unlike an explicit reborrow (see `place_as_reborrow`),
+    /// the borrowed place may have projections after the `Deref`.
+    fn is_two_phase_reborrow(&self, kind: BorrowKind, place: &Place<'tcx>) -> bool {
+        if kind != (BorrowKind::Mut { allow_two_phase_borrow: true }) {
+            return false;
+        }
+
+        if let PlaceBase::Local(local) = place.base {
+            if self.body.local_decls[local].is_ref_to_static() {
+                return false;
+            }
+        }
+
+        place.projection.iter().enumerate().any(|(i, elem)| {
+            if *elem != ProjectionElem::Deref {
+                return false;
+            }
+
+            let proj_base = &place.projection[..i];
+            let base_ty = Place::ty_from(&place.base, proj_base, self.body, self.tcx).ty;
+            match base_ty.kind {
+                ty::Ref(_, _, hir::Mutability::Mutable) => true,
+                _ => false,
+            }
+        })
+    }
+
     fn check_static(&mut self, def_id: DefId, span: Span) {
         let is_thread_local = self.tcx.has_attr(def_id, sym::thread_local);
         if is_thread_local {
             self.check_op_spanned(ops::ThreadLocalAccess, span)
+        } else if self.const_kind() == ConstKind::ConstFn
+            && self.is_immutable_freeze_static(def_id)
+        {
+            // Reads through a reference to an immutable `static` without interior mutability
+            // always observe the same value, so they are fine inside a `const fn`.
         } else {
-            self.check_op_spanned(ops::StaticAccess, span)
+            self.check_op_spanned(ops::StaticAccess(Some(def_id)), span)
         }
     }
+
+    /// Checks a borrow of (part of) a `static` that does not read from the `static`.
+    fn check_static_borrow(&mut self, def_id: DefId, span: Span) {
+        let is_thread_local = self.tcx.has_attr(def_id, sym::thread_local);
+        if is_thread_local {
+            self.check_op_spanned(ops::ThreadLocalAccess, span)
+        } else if self.const_kind() == ConstKind::ConstFn
+            && self.is_immutable_freeze_static(def_id)
+        {
+            // See `check_static`.
+        } else {
+            self.check_op_spanned(ops::RefToStatic, span)
+        }
+    }
+
+    /// Returns `true` if `def_id` refers to a `static` (not a `static mut`) whose type does not
+    /// contain an `UnsafeCell`.
+    fn is_immutable_freeze_static(&self, def_id: DefId) -> bool {
+        if self.tcx.static_mutability(def_id) != Some(hir::Mutability::Immutable) {
+            return false;
+        }
+
+        let span = self.tcx.def_span(def_id);
+        self.tcx.type_of(def_id).is_freeze(self.tcx, self.param_env, span)
+    }
 }
 
-impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
-    fn visit_basic_block_data(
+impl dataflow::ResultsVisitor<'mir, 'tcx> for Validator<'_, 'mir, 'tcx> {
+    type FlowState = QualifState;
+
+    fn visit_statement(
         &mut self,
-        bb: BasicBlock,
-        block: &BasicBlockData<'tcx>,
+        state: &Self::FlowState,
+        statement: &'mir Statement<'tcx>,
+        location: Location,
     ) {
-        trace!("visit_basic_block_data: bb={:?} is_cleanup={:?}", bb, block.is_cleanup);
-
-        // Just as the old checker did, we skip const-checking basic blocks on the unwind path.
-        // These blocks often drop locals that would otherwise be returned from the function.
-        //
-        // FIXME: This shouldn't be unsound since a panic at compile time will cause a compiler
-        // error anyway, but maybe we should do more here?
-        if block.is_cleanup {
-            return;
-        }
+        self.qualifs.state.clone_from(state);
+        Visitor::visit_statement(self, statement, location);
+    }
 
-        self.super_basic_block_data(bb, block);
+    fn visit_terminator(
+        &mut self,
+        state: &Self::FlowState,
+        terminator: &'mir Terminator<'tcx>,
+        location: Location,
+    ) {
+        self.qualifs.state.clone_from(state);
+        Visitor::visit_terminator(self, terminator, location);
     }
+}
 
+impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
     fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
         trace!("visit_rvalue: rvalue={:?} location={:?}", rvalue, location);
 
@@ -328,25 +1118,40 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
         self.super_rvalue(rvalue, location);
 
         match *rvalue {
+            // Unsizing to a slice only attaches a length, and unsizing to a trait object only
+            // attaches a vtable, which can be created at compile-time.
+            Rvalue::Cast(CastKind::Pointer(PointerCast::Unsize), ..) => {}
+
             Rvalue::Use(_) |
-            Rvalue::Repeat(..) |
-            Rvalue::UnaryOp(UnOp::Neg, _) |
-            Rvalue::UnaryOp(UnOp::Not, _) |
             Rvalue::NullaryOp(NullOp::SizeOf, _) |
             Rvalue::CheckedBinaryOp(..) |
             Rvalue::Cast(CastKind::Pointer(_), ..) |
             Rvalue::Discriminant(..) |
-            Rvalue::Len(_) |
-            Rvalue::Aggregate(..) => {}
+            Rvalue::Len(_) => {}
+
+            Rvalue::Aggregate(ref kind, _) => {
+                if let AggregateKind::Adt(def, ..) = **kind {
+                    if def.repr.simd() {
+                        self.check_op(ops::SimdVector);
+                    }
+                }
+            }
 
             | Rvalue::Ref(_, kind @ BorrowKind::Mut { .. }, ref place)
             | Rvalue::Ref(_, kind @ BorrowKind::Unique, ref place)
             => {
                 let ty = place.ty(self.body, self.tcx).ty;
                 let is_allowed = match ty.kind {
-                    // Inside a `static mut`, `&mut [...]` is allowed.
-                    ty::Array(..) | ty::Slice(_) if self.const_kind() == ConstKind::StaticMut
-                        => true,
+                    _ if self.is_mut_borrow_allowed_in_static_mut(place) => true,
+
+                    // The `&mut` borrow of the iterator that a `for` loop passes to
+                    // `Iterator::next` cannot escape, since `Iterator::Item` cannot borrow from
+                    // the iterator.
+                    _ if self.is_for_loop_iterator(place) => true,
+
+                    // An autoref of a place behind a `&mut` only reborrows data that can already be
+                    // mutated through that reference.
+                    _ if self.is_two_phase_reborrow(kind, place) => true,
 
                     // FIXME(ecstaticmorse): We could allow `&mut []` inside a const context given
                     // that this is merely a ZST and it is already eligible for promotion.
@@ -360,32 +1165,57 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
                 };
 
                 if !is_allowed {
-                    self.check_op(ops::MutBorrow(kind));
+                    self.check_op(ops::MutBorrow(kind, None));
+                }
+            }
+
+            Rvalue::AddressOf(Mutability::Mut, ref place) => {
+                if !self.is_mut_borrow_allowed_in_static_mut(place) {
+                    self.check_op(ops::MutAddressOf);
                 }
             }
 
             // At the moment, `PlaceBase::Static` is only used for promoted MIR.
             | Rvalue::Ref(_, BorrowKind::Shared, ref place)
             | Rvalue::Ref(_, BorrowKind::Shallow, ref place)
+            | Rvalue::AddressOf(Mutability::Not, ref place)
             if matches!(place.base, PlaceBase::Static(_))
             => bug!("Saw a promoted during const-checking, which must run before promotion"),
 
-            | Rvalue::Ref(_, kind @ BorrowKind::Shared, ref place)
-            | Rvalue::Ref(_, kind @ BorrowKind::Shallow, ref place)
+            // The shallow borrows of the scrutinee that keep a match guard from modifying it are
+            // only read by the borrow checker and removed before const-eval, so interior
+            // mutability in the scrutinee doesn't matter.
+            Rvalue::Ref(_, BorrowKind::Shallow, _) => {}
+
+            // A `*const` pointer to a place with interior mutability can be used to mutate it just
+            // like a shared reference, so `&raw const` is checked like a shared borrow.
+            | Rvalue::Ref(_, BorrowKind::Shared, ref place)
+            | Rvalue::AddressOf(Mutability::Not, ref place)
             => {
-                // FIXME: Change the `in_*` methods to take a `FnMut` so we don't have to manually
-                // seek the cursors beforehand.
-                self.qualifs.has_mut_interior.cursor.seek_before(location);
-                self.qualifs.indirectly_mutable.seek(location);
+                // Qualifs are tracked separately for each field of a struct or tuple local, so
+                // borrowing a field is only affected by what was assigned to that field.
+                let borrowed_field = match (&place.base, place.projection.first()) {
+                    (&PlaceBase::Local(base), Some(&ProjectionElem::Field(field, _))) => {
+                        Some((base, field))
+                    }
+                    _ => None,
+                };
 
                 let borrowed_place_has_mut_interior = HasMutInterior::in_place(
                     &self.item,
-                    &|local| self.qualifs.has_mut_interior_eager_seek(local),
+                    &|local| match borrowed_field {
+                        Some((base, field)) if base == local => {
+                            self.qualifs.has_mut_interior_field(local, field)
+                        }
+                        _ => self.qualifs.has_mut_interior(local),
+                    },
                     place.as_ref(),
                 );
 
                 if borrowed_place_has_mut_interior {
-                    self.check_op(ops::MutBorrow(kind));
+                    let ty = place.ty(self.body, self.tcx).ty;
+                    let interior_mut = self.find_interior_mut(ty, None);
+                    self.check_op(ops::MutBorrow(BorrowKind::Shared, interior_mut));
                 }
             }
 
@@ -398,10 +1228,25 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
                      | (CastTy::FnPtr,  CastTy::Int(_)) = (cast_in, cast_out) {
                     self.check_op(ops::RawPtrToIntCast);
                 }
+
+                if let (CastTy::Float, _) | (_, CastTy::Float) = (cast_in, cast_out) {
+                    self.check_op(ops::FloatingPointOp);
+                }
+            }
+
+            Rvalue::UnaryOp(_, ref operand) => {
+                if operand.ty(self.body, self.tcx).is_floating_point() {
+                    self.check_op(ops::FloatingPointOp);
+                }
             }
 
             Rvalue::BinaryOp(op, ref lhs, _) => {
-                if let ty::RawPtr(_) | ty::FnPtr(..) = lhs.ty(self.body, self.tcx).kind {
+                let lhs_ty = lhs.ty(self.body, self.tcx);
+                if lhs_ty.is_floating_point() {
+                    self.check_op(ops::FloatingPointOp);
+                }
+
+                if let ty::RawPtr(_) | ty::FnPtr(..) = lhs_ty.kind {
                     assert!(op == BinOp::Eq || op == BinOp::Ne ||
                             op == BinOp::Le || op == BinOp::Lt ||
                             op == BinOp::Ge || op == BinOp::Gt ||
@@ -415,6 +1260,28 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
             Rvalue::NullaryOp(NullOp::Box, _) => {
                 self.check_op(ops::HeapAllocation);
             }
+
+            Rvalue::Repeat(ref operand, count) => {
+                // In a `const fn`, the element may be duplicated at runtime as well, so the usual
+                // `Copy` requirement is enforced during borrowck instead.
+                if count <= 1 || self.const_kind() == ConstKind::ConstFn {
+                    return;
+                }
+
+                // Duplicating a moved value bitwise is only sound if it is a constant, so only an
+                // element that can be promoted needs just the feature gate. Borrowck requires every
+                // other element to be `Copy`.
+                if let Operand::Move(_) = operand {
+                    let ty = operand.ty(self.body, self.tcx);
+                    if !ty.is_copy_modulo_regions(self.tcx, self.param_env, self.span)
+                        && promote_consts::is_promotable_repeat_operand(
+                            self.tcx, self.def_id, self.body, operand,
+                        )
+                    {
+                        self.check_op(ops::NonCopyRepeat);
+                    }
+                }
+            }
         }
     }
 
@@ -440,17 +1307,35 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
         }
     }
 
-    fn visit_operand(
-        &mut self,
-        op: &Operand<'tcx>,
-        location: Location,
-    ) {
-        self.super_operand(op, location);
-        if let Operand::Constant(c) = op {
-            if let Some(def_id) = c.check_static_ptr(self.tcx) {
-                self.check_static(def_id, self.span);
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        // Accesses to a `static` go through a local holding a pointer to it (e.g. `*_1` in
+        // `&(*_1).field`). Check them here, where we can tell a borrow of the `static` from a read.
+        if let (&PlaceBase::Local(local), [ProjectionElem::Deref, projection @ ..])
+            = (&place.base, place.projection.as_ref())
+        {
+            // Borrowing a place behind the first `Deref` does not read from memory.
+            let is_borrow_only = (context.is_borrow() || context.is_address_of())
+                && !projection.iter().any(|elem| *elem == ProjectionElem::Deref);
+
+            let decl = &self.body.local_decls[local];
+            if let LocalInfo::StaticRef { def_id, .. } = decl.local_info {
+                if is_borrow_only {
+                    self.check_static_borrow(def_id, self.span);
+                } else {
+                    let span = decl.source_info.span;
+                    self.check_static(def_id, span);
+                }
+            } else if !is_borrow_only
+                && self.const_kind() == ConstKind::Const
+                && self.tcx.features().const_refs_to_static
+                && self.qualifs.ref_to_static(local)
+            {
+                // Reading through a reference to a `static` reads from the `static`.
+                self.check_op(ops::StaticAccess(None));
             }
         }
+
+        self.super_place(place, context, location);
     }
 
     fn visit_projection_elem(
@@ -477,21 +1362,30 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
             ProjectionElem::Deref => {
                 let base_ty = Place::ty_from(place_base, proj_base, self.body, self.tcx).ty;
                 if let ty::RawPtr(_) = base_ty.kind {
-                    if proj_base.is_empty() {
-                        if let (PlaceBase::Local(local), []) = (place_base, proj_base) {
-                            let decl = &self.body.local_decls[*local];
-                            if let LocalInfo::StaticRef { def_id, .. } = decl.local_info {
-                                let span = decl.source_info.span;
-                                self.check_static(def_id, span);
-                                return;
-                            }
+                    if let (PlaceBase::Local(local), []) = (place_base, proj_base) {
+                        // Accesses to a `static` are checked in `visit_place`.
+                        if self.body.local_decls[*local].is_ref_to_static() {
+                            return;
                         }
                     }
-                    self.check_op(ops::RawPtrDeref);
+                    self.check_unsafe_op(ops::RawPtrDeref, location, |tables, expr| {
+                        match expr.kind {
+                            hir::ExprKind::Unary(hir::UnDeref, ref ptr) => {
+                                tables.expr_ty_adjusted(ptr).is_unsafe_ptr()
+                            }
+                            _ => false,
+                        }
+                    });
                 }
 
                 if context.is_mutating_use() {
-                    self.check_op(ops::MutDeref);
+                    self.check_op_in_subexpr(ops::MutDeref, |tables, expr| match expr.kind {
+                        hir::ExprKind::Unary(hir::UnDeref, ref ptr) => {
+                            let ptr_ty = tables.expr_ty_adjusted(ptr);
+                            ptr_ty.is_unsafe_ptr() || ptr_ty.is_region_ptr()
+                        }
+                        _ => false,
+                    });
                 }
             }
 
@@ -502,7 +1396,20 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
                 let base_ty = Place::ty_from(place_base, proj_base, self.body, self.tcx).ty;
                 match base_ty.ty_adt_def() {
                     Some(def) if def.is_union() => {
-                        self.check_op(ops::UnionAccess);
+                        self.check_unsafe_op(ops::UnionAccess, location, |tables, expr| {
+                            match expr.kind {
+                                hir::ExprKind::Field(ref base, _) => {
+                                    tables.expr_ty_adjusted(base)
+                                        .ty_adt_def()
+                                        .map_or(false, |def| def.is_union())
+                                }
+                                _ => false,
+                            }
+                        });
+                    }
+
+                    Some(def) if def.repr.simd() => {
+                        self.check_op(ops::SimdVector);
                     }
 
                     _ => {}
@@ -531,11 +1438,13 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
             StatementKind::FakeRead(FakeReadCause::ForMatchedPlace, _) => {
                 self.check_op(ops::IfOrMatch);
             }
+            StatementKind::InlineAsm { .. } => {
+                self.check_op(ops::InlineAsm);
+            }
             // FIXME(eddyb) should these really do nothing?
             StatementKind::FakeRead(..) |
             StatementKind::StorageLive(_) |
             StatementKind::StorageDead(_) |
-            StatementKind::InlineAsm {..} |
             StatementKind::Retag { .. } |
             StatementKind::AscribeUserType(..) |
             StatementKind::Nop => {}
@@ -550,8 +1459,8 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
             TerminatorKind::Call { func, .. } => {
                 let fn_ty = func.ty(self.body, self.tcx);
 
-                let def_id = match fn_ty.kind {
-                    ty::FnDef(def_id, _) => def_id,
+                let (def_id, substs) = match fn_ty.kind {
+                    ty::FnDef(def_id, substs) => (def_id, substs),
 
                     ty::FnPtr(_) => {
                         self.check_op(ops::FnCallIndirect);
@@ -568,9 +1477,30 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
                 if let Abi::RustIntrinsic | Abi::PlatformIntrinsic = self.tcx.fn_sig(def_id).abi() {
                     assert!(!self.tcx.is_const_fn(def_id));
 
-                    if self.tcx.item_name(def_id) == sym::transmute {
-                        self.check_op(ops::Transmute);
-                        return;
+                    match self.tcx.item_name(def_id) {
+                        sym::transmute => {
+                            self.check_op(ops::Transmute);
+                            return;
+                        }
+
+                        // `abort` diverges and is reported as an error by const eval, so it is
+                        // always allowed as a hard failure path.
+                        sym::abort => return,
+
+                        // Const eval calls the first of the two functions, so only that one
+                        // needs to be a `const fn`.
+                        sym::const_eval_select => {
+                            match substs.type_at(1).kind {
+                                ty::FnDef(callee, _) if self.tcx.is_const_fn(callee) => {}
+                                ty::FnDef(callee, _) => {
+                                    self.check_op(ops::FnCallNonConst(callee))
+                                }
+                                _ => self.check_op(ops::FnCallOther),
+                            }
+                            return;
+                        }
+
+                        _ => {}
                     }
 
                     // To preserve the current semantics, we return early, allowing all
@@ -585,6 +1515,44 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
                     return;
                 }
 
+                // A trait method called on a `dyn Trait` is dispatched through the vtable, so we
+                // cannot know statically which impl will be called.
+                let is_dyn_trait_call = self.tcx.trait_of_item(def_id).is_some()
+                    && matches!(substs.type_at(0).kind, ty::Dynamic(..));
+                if is_dyn_trait_call {
+                    self.check_op(ops::FnCallDynTrait);
+                    return;
+                }
+
+                // Under `const_trait_impl`, a trait method is callable if the impl it resolves to
+                // is `const`. This is what allows the `Try` and `From` calls in the desugaring of
+                // `?` inside a const fn.
+                let is_trait_method = self.tcx.trait_of_item(def_id).is_some();
+                if self.tcx.features().const_trait_impl && is_trait_method {
+                    let substs = self.tcx.erase_regions(&substs);
+                    let instance = Instance::resolve(self.tcx, self.param_env, def_id, substs);
+                    if let Some(ty::InstanceDef::Item(callee)) = instance.map(|i| i.def) {
+                        if self.tcx.is_const_fn(callee) {
+                            return;
+                        }
+                    }
+                }
+
+                // Explain the non-const `into_iter` and `next` calls of a `for` loop in terms of
+                // the loop itself.
+                let is_for_loop_call = is_trait_method
+                    && self.span.desugaring_kind() == Some(DesugaringKind::ForLoop)
+                    && matches!(self.tcx.item_name(def_id), sym::into_iter | sym::next);
+                if is_for_loop_call {
+                    self.check_op(ops::ForLoopIterator(def_id));
+                    return;
+                }
+
+                if is_trait_method {
+                    self.check_op(ops::FnCallTraitMethod(def_id));
+                    return;
+                }
+
                 if is_lang_panic_fn(self.tcx, def_id) {
                     self.check_op(ops::Panic);
                 } else if let Some(feature) = self.tcx.is_unstable_const_fn(def_id) {
@@ -600,41 +1568,233 @@ impl Visitor<'tcx> for Validator<'_, 'mir, 'tcx> {
             }
 
             // Forbid all `Drop` terminators unless the place being dropped is a local with no
-            // projections that cannot be `NeedsDrop`.
+            // projections that cannot be `NeedsNonConstDrop`.
             | TerminatorKind::Drop { location: dropped_place, .. }
             | TerminatorKind::DropAndReplace { location: dropped_place, .. }
             => {
                 let mut err_span = self.span;
 
-                // Check to see if the type of this place can ever have a drop impl. If not, this
-                // `Drop` terminator is frivolous.
-                let ty_needs_drop = dropped_place
-                    .ty(self.body, self.tcx)
-                    .ty
-                    .needs_drop(self.tcx, self.param_env);
-
-                if !ty_needs_drop {
+                // Check to see if the type of this place can ever have a non-`const` drop impl. If
+                // not, this `Drop` terminator is frivolous or can be evaluated at compile-time.
+                let dropped_ty = dropped_place.ty(self.body, self.tcx).ty;
+                if !NeedsNonConstDrop::in_any_value_of_ty(self.item, dropped_ty) {
                     return;
                 }
 
-                let needs_drop = if let Some(local) = dropped_place.as_local() {
+                let mut op = ops::LiveDrop {
+                    dropped_at: None,
+                    manually_drop_init: None,
+                    is_temp: false,
+                };
+                let needs_non_const_drop = if let Some(local) = dropped_place.as_local() {
                     // Use the span where the local was declared as the span of the drop error.
                     err_span = self.body.local_decls[local].source_info.span;
-                    self.qualifs.needs_drop_lazy_seek(local, location)
+
+                    // For a variable, the drop may happen far away from its declaration, so point
+                    // out both.
+                    if self.body.local_decls[local].is_user_variable() {
+                        op.dropped_at = Some(self.span);
+                        op.manually_drop_init = manually_drop_init(self.body, local);
+                    } else if self.body.local_kind(local) == LocalKind::Temp {
+                        // A temporary has no declaration, so point at the expression that created
+                        // its value instead.
+                        if let Some(origin) = temp_origin_span(self.body, local) {
+                            err_span = origin;
+                        }
+                        op.is_temp = true;
+                    }
+
+                    self.qualifs.needs_non_const_drop(local)
                 } else {
                     true
                 };
 
-                if needs_drop {
-                    self.check_op_spanned(ops::LiveDrop, err_span);
+                if needs_non_const_drop {
+                    self.check_op_spanned(op, err_span);
                 }
             }
 
+            // Overflow, division-by-zero and bounds checks are inserted by the compiler and are
+            // not calls. If one fails, const eval reports the arithmetic or bounds failure.
+            TerminatorKind::Assert { .. } => {}
+
             _ => {}
         }
     }
 }
 
+/// Returns `true` if the body of the local, non-const `fn` with the given `DefId` would pass const
+/// checking if it were declared as a `const fn`.
+///
+/// This is the provider for the `passes_as_const_fn` query.
+pub fn passes_as_const_fn(tcx: TyCtxt<'tcx>, def_id: DefId) -> bool {
+    // The `mir_const` of the callee may already have been stolen, and asking for a later phase
+    // could require the `mir_const_qualif` of the caller that is being checked right now.
+    let body = &crate::transform::mir_const_copy(tcx, def_id);
+
+    if body.return_ty().references_error() {
+        return false;
+    }
+
+    // Without `#![feature(const_fn)]`, a new `const fn` would have to follow the `min_const_fn`
+    // rules.
+    if !tcx.features().const_fn {
+        use crate::transform::qualify_min_const_fn::is_min_const_fn;
+        if is_min_const_fn(tcx, def_id, body).is_err() {
+            return false;
+        }
+    }
+
+    let item = Item::with_const_kind(tcx, def_id, body, Some(ConstKind::ConstFn));
+    let mut validator = Validator::new_silent(&item);
+    validator.check_body();
+    !validator.found_error()
+}
+
+/// Returns the span of the initializer of `local` if wrapping it in a `ManuallyDrop` would not
+/// break any other use of `local`.
+///
+/// This is the case for a variable declared by `let x = ...;` with no type annotation that is
+/// never used again, except to be dropped. Any other use would need `ManuallyDrop`'s `Deref`
+/// impl, which cannot be called in a const context.
+fn manually_drop_init(body: &Body<'_>, local: Local) -> Option<Span> {
+    let decl = &body.local_decls[local];
+    let init_span = match decl.local_info {
+        LocalInfo::User(ClearCrossCrate::Set(BindingForm::Var(VarBindingForm {
+            binding_mode: ty::BindingMode::BindByValue(_),
+            opt_ty_info: None,
+            opt_match_place: Some((None, init_span)),
+            pat_span,
+        }))) if pat_span == decl.source_info.span => init_span,
+
+        _ => return None,
+    };
+
+    let mut def_use = DefUseAnalysis::new(body);
+    def_use.analyze(body);
+
+    let mut num_inits = 0;
+    for use_ in &def_use.local_info(local).defs_and_uses {
+        match use_.context {
+            PlaceContext::NonUse(_) => {}
+
+            PlaceContext::MutatingUse(MutatingUseContext::Store)
+            | PlaceContext::MutatingUse(MutatingUseContext::Call) => num_inits += 1,
+
+            // A `DropAndReplace` assigns a new value to `local`.
+            PlaceContext::MutatingUse(MutatingUseContext::Drop) => {
+                let terminator = body[use_.location.block].terminator();
+                if let TerminatorKind::DropAndReplace { .. } = terminator.kind {
+                    return None;
+                }
+            }
+
+            _ => return None,
+        }
+    }
+
+    if num_inits == 1 {
+        Some(init_span)
+    } else {
+        None
+    }
+}
+
+/// Returns the span of the expression that created the value held by the temporary `local`.
+///
+/// Temporaries are sometimes initialized by moving the value out of another temporary, so such
+/// moves are followed back to the expression the value came from. Returns `None` if one of the
+/// temporaries along the way is not initialized exactly once.
+fn temp_origin_span(body: &Body<'_>, local: Local) -> Option<Span> {
+    let mut def_use = DefUseAnalysis::new(body);
+    def_use.analyze(body);
+
+    let mut local = local;
+    let mut seen = BitSet::new_empty(body.local_decls.len());
+    while seen.insert(local) {
+        let mut inits = def_use.local_info(local).defs_and_uses.iter().filter(|use_| {
+            match use_.context {
+                PlaceContext::MutatingUse(MutatingUseContext::Store)
+                | PlaceContext::MutatingUse(MutatingUseContext::Call) => true,
+                _ => false,
+            }
+        });
+
+        let location = match (inits.next(), inits.next()) {
+            (Some(init), None) => init.location,
+            _ => return None,
+        };
+
+        let block = &body[location.block];
+        let statement = match block.statements.get(location.statement_index) {
+            Some(statement) => statement,
+            None => return Some(block.terminator().source_info.span),
+        };
+
+        let source = match statement.kind {
+            StatementKind::Assign(box(_, Rvalue::Use(Operand::Move(ref source)))) => {
+                source.as_local()
+            }
+            _ => None,
+        };
+
+        match source {
+            Some(source) if body.local_kind(source) == LocalKind::Temp => local = source,
+            _ => return Some(statement.source_info.span),
+        }
+    }
+
+    None
+}
+
+/// Collects the spans of the expressions inside `within` for which `is_op` returns `true`.
+struct SubexprFinder<'a, 'tcx> {
+    within: Span,
+    tables: &'tcx ty::TypeckTables<'tcx>,
+    is_op: &'a dyn Fn(&ty::TypeckTables<'tcx>, &hir::Expr) -> bool,
+    found: Vec<Span>,
+}
+
+impl<'tcx> intravisit::Visitor<'tcx> for SubexprFinder<'_, 'tcx> {
+    fn nested_visit_map<'this>(&'this mut self) -> NestedVisitorMap<'this, 'tcx> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr) {
+        if self.within.contains(expr.span) && (self.is_op)(self.tables, expr) {
+            self.found.push(expr.span);
+        }
+
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Adds the tracking issue for `gate` and how to enable it to `diag`, unless `diag` already
+/// mentions them (e.g. because it was created by `feature_err`).
+fn add_feature_gate_help(tcx: TyCtxt<'_>, gate: Symbol, diag: &mut Diagnostic) {
+    let has_child = |diag: &Diagnostic, msg: &str| {
+        diag.children.iter().any(|child| child.message() == msg)
+    };
+
+    if let Some(issue) = find_feature_issue(gate, GateIssue::Language) {
+        let note = format!(
+            "for more information, see https://github.com/rust-lang/rust/issues/{}",
+            issue,
+        );
+        if !has_child(diag, &note) {
+            diag.note(&note);
+        }
+    }
+
+    // Don't suggest enabling the feature on beta or stable, where it can't be.
+    if tcx.sess.parse_sess.unstable_features.is_nightly_build() {
+        let help = format!("add `#![feature({})]` to the crate attributes to enable", gate);
+        if !has_child(diag, &help) {
+            diag.help(&help);
+        }
+    }
+}
+
 fn error_min_const_fn_violation(tcx: TyCtxt<'_>, span: Span, msg: Cow<'_, str>) {
     struct_span_err!(tcx.sess, span, E0723, "{}", msg)
         .note("for more information, see issue https://github.com/rust-lang/rust/issues/57563")
@@ -642,9 +1802,40 @@ fn error_min_const_fn_violation(tcx: TyCtxt<'_>, span: Span, msg: Cow<'_, str>)
         .emit();
 }
 
+/// Returns the span of the head of each natural loop in `body`, in the order the loops start.
+///
+/// A loop head is the target of a back-edge, an edge to a block that dominates its source. Its
+/// terminator is the `FalseUnwind` created for the loop expression, whose span covers the whole
+/// loop, so we only keep the part before the body (e.g. `while i < n`). If `body` only contains
+/// cycles that are not natural loops, this is empty.
+fn loop_head_spans(tcx: TyCtxt<'_>, body: &Body<'_>) -> Vec<Span> {
+    let dominators = body.dominators();
+    let mut heads = BitSet::new_empty(body.basic_blocks().len());
+    let mut spans = vec![];
+
+    for (bb, block) in traversal::reverse_postorder(body) {
+        for &succ in block.terminator().successors() {
+            if dominators.is_dominated_by(bb, succ) && heads.insert(succ) {
+                let loop_span = body[succ].terminator().source_info.span;
+                spans.push(tcx.sess.source_map().span_until_char(loop_span, '{'));
+            }
+        }
+    }
+
+    spans
+}
+
 fn check_short_circuiting_in_const_local(item: &Item<'_, 'tcx>) {
     let body = item.body;
 
+    // With `#![feature(const_if_match)]` or `#![feature(const_loop)]`, `&&` and `||` are lowered
+    // to `SwitchInt`s and actually short-circuit, so there is nothing to report.
+    let features = item.tcx.features();
+    if features.const_if_match || features.const_loop {
+        debug_assert!(body.control_flow_destroyed.is_empty());
+        return;
+    }
+
     if body.control_flow_destroyed.is_empty() {
         return;
     }
@@ -677,6 +1868,117 @@ fn check_short_circuiting_in_const_local(item: &Item<'_, 'tcx>) {
     }
 }
 
+/// Lints mutations of the temporary that is created each time a `const` item is used, e.g.
+/// `CONST_ARRAY[0] = 1`. These never affect the `const` item itself.
+///
+/// Unlike the rest of the validation, this runs on every body, not just on those of const items.
+pub fn lint_const_item_mutation(tcx: TyCtxt<'tcx>, body: &Body<'tcx>) {
+    // A `const` item used as a place is first copied into a temp, which is then mutated.
+    let mut const_temps = FxHashMap::default();
+    for block in body.basic_blocks() {
+        for statement in &block.statements {
+            let (place, constant) = match &statement.kind {
+                StatementKind::Assign(box(place, Rvalue::Use(Operand::Constant(constant)))) => {
+                    (place, constant)
+                }
+                _ => continue,
+            };
+
+            let local = match place.as_local() {
+                Some(local) if body.local_kind(local) == LocalKind::Temp => local,
+                _ => continue,
+            };
+
+            if let ty::ConstKind::Unevaluated(def_id, _) = constant.literal.val {
+                match tcx.def_kind(def_id) {
+                    Some(DefKind::Const) | Some(DefKind::AssocConst) => {
+                        const_temps.insert(local, def_id);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    if const_temps.is_empty() {
+        return;
+    }
+
+    ConstItemMutationVisitor { tcx, body, const_temps }.visit_body(body);
+}
+
+struct ConstItemMutationVisitor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+
+    /// The temps that hold a copy of a `const` item, along with the `DefId` of that item.
+    const_temps: FxHashMap<Local, DefId>,
+}
+
+impl Visitor<'tcx> for ConstItemMutationVisitor<'_, 'tcx> {
+    fn visit_place(&mut self, place: &Place<'tcx>, context: PlaceContext, location: Location) {
+        let def_id = match place.base {
+            PlaceBase::Local(local) => match self.const_temps.get(&local) {
+                Some(&def_id) => def_id,
+                None => return,
+            },
+            PlaceBase::Static(_) => return,
+        };
+
+        // Writing through a pointer stored in a `const` doesn't modify the temp.
+        if place.projection.contains(&ProjectionElem::Deref) {
+            return;
+        }
+
+        let (msg, note) = match context {
+            // Assigning to the temp itself is how it gets initialized.
+            PlaceContext::MutatingUse(MutatingUseContext::Store) |
+            PlaceContext::MutatingUse(MutatingUseContext::AsmOutput) |
+            PlaceContext::MutatingUse(MutatingUseContext::Call)
+                if !place.projection.is_empty() =>
+            {
+                (
+                    "attempting to modify a `const` item",
+                    "each usage of a `const` item creates a new temporary, and only that \
+                     temporary is modified",
+                )
+            }
+
+            PlaceContext::MutatingUse(MutatingUseContext::Borrow) => (
+                "taking a mutable reference to a `const` item",
+                "each usage of a `const` item creates a new temporary, and the mutable \
+                 reference refers to that temporary",
+            ),
+
+            PlaceContext::MutatingUse(MutatingUseContext::AddressOf) => (
+                "taking a mutable raw pointer to a `const` item",
+                "each usage of a `const` item creates a new temporary, and the raw pointer \
+                 points to that temporary",
+            ),
+
+            _ => return,
+        };
+
+        let source_info = self.body.source_info(location);
+        let lint_root = self.body.source_scopes[source_info.scope]
+            .local_data
+            .as_ref()
+            .assert_crate_local()
+            .lint_root;
+
+        let mut lint = self.tcx.struct_span_lint_hir(
+            CONST_ITEM_MUTATION,
+            lint_root,
+            source_info.span,
+            msg,
+        );
+        lint.note(note);
+        lint.note("the original `const` item is not modified");
+        lint.span_note(self.tcx.def_span(def_id), "`const` item defined here");
+        lint.emit();
+    }
+}
+
 fn check_return_ty_is_sync(tcx: TyCtxt<'tcx>, body: &Body<'tcx>, hir_id: HirId) {
     let ty = body.return_ty();
     tcx.infer_ctxt().enter(|infcx| {
@@ -695,30 +1997,31 @@ fn place_as_reborrow(
     body: &Body<'tcx>,
     place: &'a Place<'tcx>,
 ) -> Option<&'a [PlaceElem<'tcx>]> {
-    place
-        .projection
-        .split_last()
-        .and_then(|(outermost, inner)| {
-            if outermost != &ProjectionElem::Deref {
-                return None;
-            }
-
-            // A borrow of a `static` also looks like `&(*_1)` in the MIR, but `_1` is a `const`
-            // that points to the allocation for the static. Don't treat these as reborrows.
-            if let PlaceBase::Local(local) = place.base {
-                if body.local_decls[local].is_ref_to_static() {
-                    return None;
-                }
-            }
+    // A borrow of a `static` also looks like `&(*_1)` in the MIR, but `_1` is a `const` that
+    // points to the allocation for the static. Don't treat these as reborrows.
+    if let PlaceBase::Local(local) = place.base {
+        if body.local_decls[local].is_ref_to_static() {
+            return None;
+        }
+    }
 
-            // Ensure the type being derefed is a reference and not a raw pointer.
-            //
-            // This is sufficient to prevent an access to a `static mut` from being marked as a
-            // reborrow, even if the check above were to disappear.
-            let inner_ty = Place::ty_from(&place.base, inner, body, tcx).ty;
-            match inner_ty.kind {
-                ty::Ref(..) => Some(inner),
-                _ => None,
-            }
-        })
+    // Peel off every `Deref` at the end of the place, so `&**r` is a reborrow of `r` as well.
+    //
+    // Ensure the type being derefed is a reference and not a raw pointer. This is sufficient to
+    // prevent an access to a `static mut` from being marked as a reborrow, even if the check above
+    // were to disappear.
+    let mut reborrowed = place.projection.as_ref();
+    while let &[ref inner @ .., ProjectionElem::Deref] = reborrowed {
+        let inner_ty = Place::ty_from(&place.base, inner, body, tcx).ty;
+        match inner_ty.kind {
+            ty::Ref(..) => reborrowed = inner,
+            _ => break,
+        }
+    }
+
+    if reborrowed.len() < place.projection.len() {
+        Some(reborrowed)
+    } else {
+        None
+    }
 }
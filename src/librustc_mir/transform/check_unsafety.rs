@@ -230,7 +230,7 @@ impl<'a, 'tcx> Visitor<'tcx> for UnsafetyChecker<'a, 'tcx> {
                     }], &[]);
                 }
             }
-            let is_borrow_of_interior_mut = context.is_borrow() &&
+            let is_borrow_of_interior_mut = (context.is_borrow() || context.is_address_of()) &&
                 !Place::ty_from(&place.base, proj_base, self.body, self.tcx)
                 .ty
                 .is_freeze(self.tcx, self.param_env, self.source_info.span);
@@ -277,10 +277,11 @@ impl<'a, 'tcx> Visitor<'tcx> for UnsafetyChecker<'a, 'tcx> {
             let base_ty = Place::ty_from(&place.base, proj_base, self.body, self.tcx).ty;
             match base_ty.kind {
                 ty::RawPtr(..) => {
+                    // Whether this is allowed in a `const fn` is decided during const-checking.
                     self.require_unsafe("dereference of raw pointer",
                         "raw pointers may be NULL, dangling or unaligned; they can violate \
                          aliasing rules and cause data races: all of these are undefined \
-                         behavior", UnsafetyViolationKind::General)
+                         behavior", UnsafetyViolationKind::GeneralAndConstFn)
                 }
                 ty::Adt(adt, _) => {
                     if adt.is_union() {
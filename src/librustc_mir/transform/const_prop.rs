@@ -305,7 +305,7 @@ impl<'mir, 'tcx> ConstPropagator<'mir, 'tcx> {
                 // or for large values.
                 .filter(|ret_layout| !ret_layout.is_zst() &&
                                      ret_layout.size < Size::from_bytes(MAX_ALLOC_LIMIT))
-                .map(|ret_layout| ecx.allocate(ret_layout, MemoryKind::Stack));
+                .and_then(|ret_layout| ecx.allocate(ret_layout, MemoryKind::Stack).ok());
 
         ecx.push_stack_frame(
             Instance::new(def_id, substs),
@@ -550,8 +550,8 @@ impl<'mir, 'tcx> ConstPropagator<'mir, 'tcx> {
             // (e.g. for CTFE) it can never happen. But here in const_prop
             // unknown data is uninitialized, so if e.g. a function argument is unsized
             // and has a reference taken, we get an ICE.
-            Rvalue::Ref(_, _, place_ref) => {
-                trace!("checking Ref({:?})", place_ref);
+            Rvalue::Ref(_, _, place_ref) | Rvalue::AddressOf(_, place_ref) => {
+                trace!("checking Ref/AddressOf({:?})", place_ref);
 
                 if let Some(local) = place_ref.as_local() {
                     let alive =
@@ -661,6 +661,7 @@ impl<'mir, 'tcx> ConstPropagator<'mir, 'tcx> {
                 intern_const_alloc_recursive(
                     &mut self.ecx,
                     None,
+                    true,
                     op.assert_mem_place()
                 ).expect("failed to intern alloc");
                 true
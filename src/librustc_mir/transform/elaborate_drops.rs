@@ -3,7 +3,7 @@ use crate::dataflow::{MaybeInitializedPlaces, MaybeUninitializedPlaces};
 use crate::dataflow::{DataflowResults};
 use crate::dataflow::{on_all_children_bits, on_all_drop_children_bits};
 use crate::dataflow::{drop_flag_effects_for_location, on_lookup_result_bits};
-use crate::dataflow::MoveDataParamEnv;
+use crate::dataflow::{find_dead_unwinds, MoveDataParamEnv};
 use crate::dataflow::{self, do_dataflow, DebugFormatted};
 use crate::transform::{MirPass, MirSource};
 use crate::util::patch::MirPatch;
@@ -11,7 +11,6 @@ use crate::util::elaborate_drops::{DropFlagState, Unwind, elaborate_drop};
 use crate::util::elaborate_drops::{DropElaborator, DropStyle, DropFlagMode};
 use rustc::ty::{self, TyCtxt};
 use rustc::ty::layout::VariantIdx;
-use rustc::hir;
 use rustc::mir::*;
 use rustc::util::nodemap::FxHashMap;
 use rustc_index::bit_set::BitSet;
@@ -60,66 +59,6 @@ impl<'tcx> MirPass<'tcx> for ElaborateDrops {
     }
 }
 
-/// Returns the set of basic blocks whose unwind edges are known
-/// to not be reachable, because they are `drop` terminators
-/// that can't drop anything.
-fn find_dead_unwinds<'tcx>(
-    tcx: TyCtxt<'tcx>,
-    body: &Body<'tcx>,
-    def_id: hir::def_id::DefId,
-    env: &MoveDataParamEnv<'tcx>,
-) -> BitSet<BasicBlock> {
-    debug!("find_dead_unwinds({:?})", body.span);
-    // We only need to do this pass once, because unwind edges can only
-    // reach cleanup blocks, which can't have unwind edges themselves.
-    let mut dead_unwinds = BitSet::new_empty(body.basic_blocks().len());
-    let flow_inits =
-        do_dataflow(tcx, body, def_id, &[], &dead_unwinds,
-                    MaybeInitializedPlaces::new(tcx, body, &env),
-                    |bd, p| DebugFormatted::new(&bd.move_data().move_paths[p]));
-    for (bb, bb_data) in body.basic_blocks().iter_enumerated() {
-        let location = match bb_data.terminator().kind {
-            TerminatorKind::Drop { ref location, unwind: Some(_), .. } |
-            TerminatorKind::DropAndReplace { ref location, unwind: Some(_), .. } => location,
-            _ => continue,
-        };
-
-        let mut init_data = InitializationData {
-            live: flow_inits.sets().entry_set_for(bb.index()).to_owned(),
-            dead: BitSet::new_empty(env.move_data.move_paths.len()),
-        };
-        debug!("find_dead_unwinds @ {:?}: {:?}; init_data={:?}",
-               bb, bb_data, init_data.live);
-        for stmt in 0..bb_data.statements.len() {
-            let loc = Location { block: bb, statement_index: stmt };
-            init_data.apply_location(tcx, body, env, loc);
-        }
-
-        let path = match env.move_data.rev_lookup.find(location.as_ref()) {
-            LookupResult::Exact(e) => e,
-            LookupResult::Parent(..) => {
-                debug!("find_dead_unwinds: has parent; skipping");
-                continue
-            }
-        };
-
-        debug!("find_dead_unwinds @ {:?}: path({:?})={:?}", bb, location, path);
-
-        let mut maybe_live = false;
-        on_all_drop_children_bits(tcx, body, &env, path, |child| {
-            let (child_maybe_live, _) = init_data.state(child);
-            maybe_live |= child_maybe_live;
-        });
-
-        debug!("find_dead_unwinds @ {:?}: maybe_live={}", bb, maybe_live);
-        if !maybe_live {
-            dead_unwinds.insert(bb);
-        }
-    }
-
-    dead_unwinds
-}
-
 struct InitializationData {
     live: BitSet<MovePathIndex>,
     dead: BitSet<MovePathIndex>
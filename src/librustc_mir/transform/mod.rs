@@ -1,5 +1,6 @@
-use crate::{build, shim};
+use crate::{build, lints, shim};
 use rustc_index::vec::IndexVec;
+use rustc_data_structures::fx::FxHashMap;
 use rustc::hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
 use rustc::mir::{Body, MirPhase, Promoted, ConstQualifs};
 use rustc::ty::{TyCtxt, InstanceDef, TypeFoldable};
@@ -45,6 +46,7 @@ pub(crate) fn provide(providers: &mut Providers<'_>) {
         mir_built,
         mir_const,
         mir_const_qualif,
+        passes_as_const_fn: check_consts::validation::passes_as_const_fn,
         mir_validated,
         optimized_mir,
         is_mir_available,
@@ -99,6 +101,7 @@ fn mir_keys(tcx: TyCtxt<'_>, krate: CrateNum) -> &DefIdSet {
 
 fn mir_built(tcx: TyCtxt<'_>, def_id: DefId) -> &Steal<Body<'_>> {
     let mir = build::mir_build(tcx, def_id);
+    lints::check(tcx, &mir, def_id);
     tcx.alloc_steal_mir(mir)
 }
 
@@ -186,12 +189,20 @@ pub fn run_passes(
 }
 
 fn mir_const_qualif(tcx: TyCtxt<'_>, def_id: DefId) -> ConstQualifs {
-    let const_kind = check_consts::ConstKind::for_item(tcx, def_id);
+    mir_const_qualif_with_extra(tcx, def_id, &Default::default()).0
+}
 
-    // No need to const-check a non-const `fn`.
-    if const_kind.is_none() {
-        return Default::default();
-    }
+/// Const-checks the body of `def_id` and returns the qualifs in its return place, along with the
+/// results of the `extra` qualifs keyed by name.
+///
+/// Tools that want to run additional qualifs can call this from an override of the
+/// `mir_const_qualif` query. No `extra` qualifs are computed for a non-const `fn`.
+pub fn mir_const_qualif_with_extra(
+    tcx: TyCtxt<'_>,
+    def_id: DefId,
+    extra: &check_consts::qualifs::ExtraQualifs<'_>,
+) -> (ConstQualifs, FxHashMap<&'static str, bool>) {
+    let const_kind = check_consts::ConstKind::for_item(tcx, def_id);
 
     // N.B., this `borrow()` is guaranteed to be valid (i.e., the value
     // cannot yet be stolen), because `mir_validated()`, which steals
@@ -199,25 +210,30 @@ fn mir_const_qualif(tcx: TyCtxt<'_>, def_id: DefId) -> ConstQualifs {
     // performing the steal.
     let body = &tcx.mir_const(def_id).borrow();
 
+    // Mutations of `const` items are linted in every body, not just in const contexts.
+    if !body.return_ty().references_error() {
+        check_consts::validation::lint_const_item_mutation(tcx, body);
+    }
+
+    // No need to const-check a non-const `fn`.
+    if const_kind.is_none() {
+        return Default::default();
+    }
+
     if body.return_ty().references_error() {
         tcx.sess.delay_span_bug(body.span, "mir_const_qualif: MIR had errors");
         return Default::default();
     }
 
-    let item = check_consts::Item {
-        body,
-        tcx,
-        def_id,
-        const_kind,
-        param_env: tcx.param_env(def_id),
-    };
+    let item = check_consts::Item::new(tcx, def_id, body).with_extra_qualifs(extra);
 
     let mut validator = check_consts::validation::Validator::new(&item);
     validator.check_body();
 
     // We return the qualifs in the return place for every MIR body, even though it is only used
     // when deciding to promote a reference to a `const` for now.
-    validator.qualifs_in_return_place().into()
+    let qualifs = validator.qualifs_in_return_place();
+    (qualifs, item.extra_qualifs_in_return_place())
 }
 
 fn mir_const(tcx: TyCtxt<'_>, def_id: DefId) -> &Steal<Body<'_>> {
@@ -225,13 +241,28 @@ fn mir_const(tcx: TyCtxt<'_>, def_id: DefId) -> &Steal<Body<'_>> {
     let _ = tcx.unsafety_check_result(def_id);
 
     let mut body = tcx.mir_built(def_id).steal();
-    run_passes(tcx, &mut body, InstanceDef::Item(def_id), None, MirPhase::Const, &[
+    run_mir_const_passes(tcx, &mut body, def_id);
+    tcx.alloc_steal_mir(body)
+}
+
+/// Builds the MIR for `def_id` from scratch and brings it to the same phase as `mir_const`.
+///
+/// Unlike `mir_const`, this never steals anything, so it can be used to inspect the body of an
+/// item whose `mir_const` may already have been consumed. The lints emitted by `mir_built` are
+/// not emitted again for the copy.
+pub fn mir_const_copy(tcx: TyCtxt<'_>, def_id: DefId) -> Body<'_> {
+    let mut body = build::mir_build(tcx, def_id);
+    run_mir_const_passes(tcx, &mut body, def_id);
+    body
+}
+
+fn run_mir_const_passes<'tcx>(tcx: TyCtxt<'tcx>, body: &mut Body<'tcx>, def_id: DefId) {
+    run_passes(tcx, body, InstanceDef::Item(def_id), None, MirPhase::Const, &[
         // What we need to do constant evaluation.
         &simplify::SimplifyCfg::new("initial"),
         &rustc_peek::SanityCheck,
         &uniform_array_move_out::UniformArrayMoveOut,
     ]);
-    tcx.alloc_steal_mir(body)
 }
 
 fn mir_validated(
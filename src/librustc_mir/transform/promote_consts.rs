@@ -21,10 +21,11 @@ use rustc::ty::subst::InternalSubsts;
 use rustc::ty::cast::CastTy;
 use syntax::ast::LitKind;
 use syntax::symbol::sym;
-use syntax_pos::{Span, DUMMY_SP};
+use syntax_pos::Span;
 
 use rustc_index::vec::{IndexVec, Idx};
 use rustc_target::spec::abi::Abi;
+use rustc_error_codes::*;
 
 use std::cell::Cell;
 use std::{iter, mem, usize};
@@ -195,7 +196,9 @@ impl<'tcx> Visitor<'tcx> for Collector<'_, 'tcx> {
         } else if let TempState::Defined { ref mut uses, .. } = *temp {
             // We always allow borrows, even mutable ones, as we need
             // to promote mutable borrows of some ZSTs e.g., `&mut []`.
-            let allowed_use = context.is_borrow() || context.is_nonmutating_use();
+            // Raw pointers to a temporary keep it unpromotable, though.
+            let allowed_use = context.is_borrow()
+                || (context.is_nonmutating_use() && !context.is_address_of());
             debug!("visit_local: allowed_use={:?}", allowed_use);
             if allowed_use {
                 *uses += 1;
@@ -350,7 +353,7 @@ impl<'tcx> Validator<'_, 'tcx> {
                                     Place::ty_from(&place.base, proj_base, self.body, self.tcx)
                                         .projection_ty(self.tcx, elem)
                                         .ty;
-                                if ty.is_freeze(self.tcx, self.param_env, DUMMY_SP) {
+                                if self.is_freeze(ty) {
                                     has_mut_interior = false;
                                     break;
                                 }
@@ -602,6 +605,8 @@ impl<'tcx> Validator<'_, 'tcx> {
             Rvalue::Discriminant(place) |
             Rvalue::Len(place) => self.validate_place(place.as_ref()),
 
+            Rvalue::AddressOf(..) => Err(Unpromotable),
+
             Rvalue::Use(operand) |
             Rvalue::Repeat(operand, _) |
             Rvalue::UnaryOp(_, operand) |
@@ -671,7 +676,7 @@ impl<'tcx> Validator<'_, 'tcx> {
                         let ty = Place::ty_from(place.base, proj_base, self.body, self.tcx)
                             .projection_ty(self.tcx, elem)
                             .ty;
-                        if ty.is_freeze(self.tcx, self.param_env, DUMMY_SP) {
+                        if self.is_freeze(ty) {
                             has_mut_interior = false;
                             break;
                         }
@@ -756,10 +761,13 @@ pub fn validate_candidates(
 
         let is_promotable = validator.validate_candidate(candidate).is_ok();
         match candidate {
-            Candidate::Argument { bb, index } if !is_promotable => {
+            // In a const context, these errors are emitted during const-checking.
+            Candidate::Argument { bb, index }
+                if !is_promotable && validator.const_kind.is_none() =>
+            {
                 let span = body[bb].terminator().source_info.span;
-                let msg = format!("argument {} is required to be a constant", index + 1);
-                tcx.sess.span_err(span, &msg);
+                span_err!(tcx.sess, span, E0752,
+                          "argument {} is required to be a constant", index + 1);
             }
             _ => ()
         }
@@ -1138,11 +1146,10 @@ pub fn promote_candidates<'tcx>(
     promotions
 }
 
-/// This function returns `true` if the `const_in_array_repeat_expressions` feature attribute should
-/// be suggested. This function is probably quite expensive, it shouldn't be run in the happy path.
-/// Feature attribute should be suggested if `operand` can be promoted and the feature is not
-/// enabled.
-crate fn should_suggest_const_in_array_repeat_expressions_attribute<'tcx>(
+/// Returns `true` if the element `operand` of a repeat expression could be promoted to a constant,
+/// which is what `const_in_array_repeat_expressions` allows to be repeated without being `Copy`.
+/// This function is probably quite expensive, it shouldn't be run in the happy path.
+crate fn is_promotable_repeat_operand<'tcx>(
     tcx: TyCtxt<'tcx>,
     mir_def_id: DefId,
     body: &Body<'tcx>,
@@ -1156,7 +1163,20 @@ crate fn should_suggest_const_in_array_repeat_expressions_attribute<'tcx>(
         explicit: false,
     };
 
-    let should_promote = validator.validate_operand(operand).is_ok();
+    validator.validate_operand(operand).is_ok()
+}
+
+/// This function returns `true` if the `const_in_array_repeat_expressions` feature attribute should
+/// be suggested. This function is probably quite expensive, it shouldn't be run in the happy path.
+/// Feature attribute should be suggested if `operand` can be promoted and the feature is not
+/// enabled.
+crate fn should_suggest_const_in_array_repeat_expressions_attribute<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    mir_def_id: DefId,
+    body: &Body<'tcx>,
+    operand: &Operand<'tcx>,
+) -> bool {
+    let should_promote = is_promotable_repeat_operand(tcx, mir_def_id, body, operand);
     let feature_flag = tcx.features().const_in_array_repeat_expressions;
     debug!("should_suggest_const_in_array_repeat_expressions_flag: mir_def_id={:?} \
             should_promote={:?} feature_flag={:?}", mir_def_id, should_promote, feature_flag);
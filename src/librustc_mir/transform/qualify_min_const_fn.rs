@@ -8,9 +8,61 @@ use syntax_pos::Span;
 use syntax::symbol::{sym, Symbol};
 use syntax::attr;
 
-type McfResult = Result<(), (Span, Cow<'static, str>)>;
+type McfResult = Result<(), (Span, Violation)>;
+
+/// A reason why a `const fn` does not conform to the `min_const_fn` rules.
+#[derive(Debug)]
+pub struct Violation {
+    pub msg: Cow<'static, str>,
+
+    /// The operation that is rejected, if the const checker would reject it as well.
+    ///
+    /// This allows the error for that operation to be emitted instead of the `min_const_fn` one.
+    /// `msg` is still used if the operation is allowed in a `const fn` (e.g. by a feature gate),
+    /// since `min_const_fn` does not honor those.
+    pub op: Option<ViolatingOp>,
+}
+
+impl Violation {
+    fn op(op: ViolatingOp, msg: &'static str) -> Self {
+        Violation { msg: msg.into(), op: Some(op) }
+    }
+}
+
+impl From<&'static str> for Violation {
+    fn from(msg: &'static str) -> Self {
+        Violation { msg: msg.into(), op: None }
+    }
+}
+
+impl From<String> for Violation {
+    fn from(msg: String) -> Self {
+        Violation { msg: msg.into(), op: None }
+    }
+}
+
+/// An operation that violates the `min_const_fn` rules and has a counterpart in
+/// `check_consts::ops`.
+#[derive(Clone, Copy, Debug)]
+pub enum ViolatingOp {
+    /// A call to a function that is not a `const fn` at all.
+    Call(DefId),
+    HeapAllocation,
+    InlineAsm,
+    RawPtrToIntCast,
+    StaticAccess(DefId),
+    UnionAccess,
+}
+
+/// Checks that the body of a `const fn` conforms to the `min_const_fn` rules, returning every
+/// violation that was found.
+pub fn is_min_const_fn(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    body: &'a Body<'tcx>,
+) -> Result<(), Vec<(Span, Violation)>> {
+    let mut violations = Violations::default();
 
-pub fn is_min_const_fn(tcx: TyCtxt<'tcx>, def_id: DefId, body: &'a Body<'tcx>) -> McfResult {
     let mut current = def_id;
     loop {
         let predicates = tcx.predicates_of(current);
@@ -32,17 +84,23 @@ pub fn is_min_const_fn(tcx: TyCtxt<'tcx>, def_id: DefId, body: &'a Body<'tcx>) -
                     if Some(pred.def_id()) == tcx.lang_items().sized_trait() {
                         continue;
                     }
+                    // The implicit `Self: Trait` bound of a default method body always holds.
+                    if Some(pred.def_id()) == tcx.trait_of_item(def_id)
+                        && pred.skip_binder().self_ty().is_param(0)
+                    {
+                        continue;
+                    }
                     match pred.skip_binder().self_ty().kind {
                         ty::Param(ref p) => {
                             let generics = tcx.generics_of(current);
                             let def = generics.type_param(p, tcx);
                             let span = tcx.def_span(def.def_id);
-                            return Err((
+                            violations.add(Err((
                                 span,
                                 "trait bounds other than `Sized` \
                                  on const fn parameters are unstable"
                                     .into(),
-                            ));
+                            )));
                         }
                         // other kinds of bounds are either tautologies
                         // or cause errors in other passes
@@ -57,24 +115,57 @@ pub fn is_min_const_fn(tcx: TyCtxt<'tcx>, def_id: DefId, body: &'a Body<'tcx>) -
         }
     }
 
+    // The same type usually appears in many locals, so only its first occurrence is reported.
     for local in &body.local_decls {
-        check_ty(tcx, local.ty, local.source_info.span, def_id)?;
+        violations.add_ty(check_ty(tcx, local.ty, local.source_info.span, def_id));
     }
     // impl trait is gone in MIR, so check the return type manually
-    check_ty(
+    violations.add_ty(check_ty(
         tcx,
         tcx.fn_sig(def_id).output().skip_binder(),
         body.local_decls.iter().next().unwrap().source_info.span,
         def_id,
-    )?;
+    ));
 
     for bb in body.basic_blocks() {
-        check_terminator(tcx, body, def_id, bb.terminator())?;
         for stmt in &bb.statements {
-            check_statement(tcx, body, def_id, stmt)?;
+            violations.add(check_statement(tcx, body, def_id, stmt));
         }
+        violations.add(check_terminator(tcx, body, def_id, bb.terminator()));
+    }
+
+    if violations.0.is_empty() {
+        Ok(())
+    } else {
+        Err(violations.0)
+    }
+}
+
+/// The violations found in a `const fn`, without duplicates.
+#[derive(Default)]
+struct Violations(Vec<(Span, Violation)>);
+
+impl Violations {
+    fn add(&mut self, result: McfResult) {
+        if let Err((span, violation)) = result {
+            let is_duplicate = self.0.iter().any(|(prev_span, prev)| {
+                *prev_span == span && prev.msg == violation.msg
+            });
+            if !is_duplicate {
+                self.0.push((span, violation));
+            }
+        }
+    }
+
+    /// Like `add`, but also skips a violation whose message was already reported elsewhere.
+    fn add_ty(&mut self, result: McfResult) {
+        if let Err((_, ref violation)) = result {
+            if self.0.iter().any(|(_, prev)| prev.msg == violation.msg) {
+                return;
+            }
+        }
+        self.add(result);
     }
-    Ok(())
 }
 
 fn check_ty(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>, span: Span, fn_def_id: DefId) -> McfResult {
@@ -132,7 +223,10 @@ fn check_rvalue(
         Rvalue::Repeat(operand, _) | Rvalue::Use(operand) => {
             check_operand(tcx, operand, span, def_id, body)
         }
-        Rvalue::Len(place) | Rvalue::Discriminant(place) | Rvalue::Ref(_, _, place) => {
+        Rvalue::Len(place)
+        | Rvalue::Discriminant(place)
+        | Rvalue::Ref(_, _, place)
+        | Rvalue::AddressOf(_, place) => {
             check_place(tcx, place, span, def_id, body)
         }
         Rvalue::Cast(CastKind::Misc, operand, cast_ty) => {
@@ -142,7 +236,10 @@ fn check_rvalue(
             match (cast_in, cast_out) {
                 (CastTy::Ptr(_), CastTy::Int(_)) | (CastTy::FnPtr, CastTy::Int(_)) => Err((
                     span,
-                    "casting pointers to ints is unstable in const fn".into(),
+                    Violation::op(
+                        ViolatingOp::RawPtrToIntCast,
+                        "casting pointers to ints is unstable in const fn",
+                    ),
                 )),
                 (CastTy::RPtr(_), CastTy::Float) => bug!(),
                 (CastTy::RPtr(_), CastTy::Int(_)) => bug!(),
@@ -181,7 +278,10 @@ fn check_rvalue(
         Rvalue::NullaryOp(NullOp::SizeOf, _) => Ok(()),
         Rvalue::NullaryOp(NullOp::Box, _) => Err((
             span,
-            "heap allocations are not allowed in const fn".into(),
+            Violation::op(
+                ViolatingOp::HeapAllocation,
+                "heap allocations are not allowed in const fn",
+            ),
         )),
         Rvalue::UnaryOp(_, operand) => {
             let ty = operand.ty(body, tcx);
@@ -227,9 +327,10 @@ fn check_statement(
         // just an assignment
         StatementKind::SetDiscriminant { place, .. } => check_place(tcx, place, span, def_id, body),
 
-        | StatementKind::InlineAsm { .. } => {
-            Err((span, "cannot use inline assembly in const fn".into()))
-        }
+        | StatementKind::InlineAsm { .. } => Err((
+            span,
+            Violation::op(ViolatingOp::InlineAsm, "cannot use inline assembly in const fn"),
+        )),
 
         // These are all NOPs
         | StatementKind::StorageLive(_)
@@ -252,7 +353,13 @@ fn check_operand(
             check_place(tcx, place, span, def_id, body)
         }
         Operand::Constant(c) => match c.check_static_ptr(tcx) {
-            Some(_) => Err((span, "cannot access `static` items in const fn".into())),
+            Some(def_id) => Err((
+                span,
+                Violation::op(
+                    ViolatingOp::StaticAccess(def_id),
+                    "cannot access `static` items in const fn",
+                ),
+            )),
             None => Ok(()),
         },
     }
@@ -279,7 +386,13 @@ fn check_place(
                     // No union field accesses in `const fn`
                     if def.is_union() {
                         if !feature_allowed(tcx, def_id, sym::const_fn_union) {
-                            return Err((span, "accessing union fields is unstable".into()));
+                            return Err((
+                                span,
+                                Violation::op(
+                                    ViolatingOp::UnionAccess,
+                                    "accessing union fields is unstable",
+                                ),
+                            ));
                         }
                     }
                 }
@@ -357,6 +470,13 @@ fn check_terminator(
             let fn_ty = func.ty(body, tcx);
             if let ty::FnDef(def_id, _) = fn_ty.kind {
 
+                // Calls to a `const fn` that is not `min_const_fn` are only rejected here.
+                let call_op = if tcx.is_const_fn_raw(def_id) {
+                    None
+                } else {
+                    Some(ViolatingOp::Call(def_id))
+                };
+
                 // some intrinsics are waved through if called inside the
                 // standard library. Users never need to call them directly
                 match tcx.fn_sig(def_id).abi() {
@@ -366,22 +486,30 @@ fn check_terminator(
                             "can only call a curated list of intrinsics in `min_const_fn`".into(),
                         ))
                     },
-                    abi::Abi::Rust if tcx.is_min_const_fn(def_id) => {},
+                    // A `const extern fn` is called like any other `const fn`, regardless of
+                    // its ABI.
+                    _ if tcx.is_min_const_fn(def_id) => {},
                     abi::Abi::Rust => return Err((
                         span,
-                        format!(
-                            "can only call other `const fn` within a `const fn`, \
-                             but `{:?}` is not stable as `const fn`",
-                            func,
-                        )
-                        .into(),
+                        Violation {
+                            msg: format!(
+                                "can only call other `const fn` within a `const fn`, \
+                                 but `{:?}` is not stable as `const fn`",
+                                func,
+                            )
+                            .into(),
+                            op: call_op,
+                        },
                     )),
                     abi => return Err((
                         span,
-                        format!(
-                            "cannot call functions with `{}` abi in `min_const_fn`",
-                            abi,
-                        ).into(),
+                        Violation {
+                            msg: format!(
+                                "cannot call functions with `{}` abi in `min_const_fn`",
+                                abi,
+                            ).into(),
+                            op: call_op,
+                        },
                     )),
                 }
 
@@ -419,6 +547,7 @@ fn is_intrinsic_whitelisted(tcx: TyCtxt<'tcx>, def_id: DefId) -> bool {
         | "size_of"
         | "min_align_of"
         | "needs_drop"
+        | "discriminant_value" // ~> mem::discriminant
         // Arithmetic:
         | "add_with_overflow" // ~> .overflowing_add
         | "sub_with_overflow" // ~> .overflowing_sub
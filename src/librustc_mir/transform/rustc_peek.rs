@@ -8,12 +8,18 @@ use rustc::hir::def_id::DefId;
 use rustc::mir::{self, Body, Location, Local};
 use rustc_index::bit_set::BitSet;
 use crate::transform::{MirPass, MirSource};
+use crate::transform::check_consts::{Item, Qualif};
+use crate::transform::check_consts::qualifs::{
+    HasMutInterior, NeedsDrop, NeedsNonConstDrop, RefToStatic,
+};
+use crate::transform::check_consts::resolver::FlowSensitiveAnalysis;
 
 use crate::dataflow::{do_dataflow, DebugFormatted};
 use crate::dataflow::MoveDataParamEnv;
 use crate::dataflow::BitDenotation;
 use crate::dataflow::DataflowResults;
 use crate::dataflow::DataflowResultsCursor;
+use crate::dataflow::generic as dataflow;
 use crate::dataflow::{
     DefinitelyInitializedPlaces, MaybeInitializedPlaces, MaybeUninitializedPlaces
 };
@@ -69,6 +75,18 @@ impl<'tcx> MirPass<'tcx> for SanityCheck {
         if has_rustc_mir_with(&attributes, sym::rustc_peek_indirectly_mutable).is_some() {
             sanity_check_via_rustc_peek(tcx, body, def_id, &attributes, &flow_indirectly_mut);
         }
+        if has_rustc_mir_with(&attributes, sym::rustc_peek_has_mut_interior).is_some() {
+            sanity_check_qualif_via_rustc_peek(HasMutInterior, tcx, body, def_id);
+        }
+        if has_rustc_mir_with(&attributes, sym::rustc_peek_needs_drop).is_some() {
+            sanity_check_qualif_via_rustc_peek(NeedsDrop, tcx, body, def_id);
+        }
+        if has_rustc_mir_with(&attributes, sym::rustc_peek_needs_non_const_drop).is_some() {
+            sanity_check_qualif_via_rustc_peek(NeedsNonConstDrop, tcx, body, def_id);
+        }
+        if has_rustc_mir_with(&attributes, sym::rustc_peek_ref_to_static).is_some() {
+            sanity_check_qualif_via_rustc_peek(RefToStatic, tcx, body, def_id);
+        }
         if has_rustc_mir_with(&attributes, sym::stop_after_dataflow).is_some() {
             tcx.sess.fatal("stop_after_dataflow ended compilation");
         }
@@ -102,6 +120,51 @@ pub fn sanity_check_via_rustc_peek<'tcx, O>(
 
     let mut cursor = DataflowResultsCursor::new(results, body);
 
+    for_each_peek(tcx, body, |loc, place, call| {
+        cursor.seek(loc);
+        let state = cursor.get();
+        results.operator().peek_at(tcx, place, state, call);
+    });
+}
+
+/// Like `sanity_check_via_rustc_peek`, but checks the flow-sensitive analysis for the qualif `Q`
+/// that is used during const-checking. The argument of `rustc_peek` must be a local.
+fn sanity_check_qualif_via_rustc_peek<'tcx, Q: Qualif>(
+    q: Q,
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    def_id: DefId,
+) {
+    debug!("sanity_check_qualif_via_rustc_peek def_id: {:?}", def_id);
+
+    let item = Item::new(tcx, def_id, body);
+    let dead_unwinds = crate::dataflow::dead_unwinds(tcx, body, def_id, item.param_env);
+    let analysis = FlowSensitiveAnalysis::new(q, &item);
+    let results = dataflow::Engine::new(tcx, body, def_id, &dead_unwinds, analysis)
+        .iterate_to_fixpoint();
+    let mut cursor = dataflow::ResultsCursor::new(body, results);
+
+    for_each_peek(tcx, body, |loc, place, call| {
+        let local = if let Some(l) = place.as_local() {
+            l
+        } else {
+            tcx.sess.span_err(call.span, "rustc_peek: argument was not a local");
+            return;
+        };
+
+        cursor.seek_before_primary_effect(loc);
+        if !cursor.analysis().places().contains_local(cursor.get(), local) {
+            tcx.sess.span_err(call.span, "rustc_peek: bit not set");
+        }
+    });
+}
+
+/// Calls `f` with the location and the argument of every call to `rustc_peek` in `body`.
+fn for_each_peek<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &Body<'tcx>,
+    mut f: impl FnMut(Location, &mir::Place<'tcx>, PeekCall),
+) {
     let peek_calls = body
             .basic_blocks()
             .iter_enumerated()
@@ -134,9 +197,7 @@ pub fn sanity_check_via_rustc_peek<'tcx, O>(
             | (PeekCallKind::ByVal, mir::Rvalue::Use(mir::Operand::Copy(place)))
             => {
                 let loc = Location { block: bb, statement_index };
-                cursor.seek(loc);
-                let state = cursor.get();
-                results.operator().peek_at(tcx, place, state, call);
+                f(loc, place, call);
             }
 
             _ => {
@@ -16,7 +16,7 @@ use rustc::ty::query::Providers;
 use rustc_feature::Features;
 use syntax::ast::Mutability;
 use syntax::feature_gate::feature_err;
-use syntax::span_err;
+use syntax::{span_err, struct_span_err};
 use syntax_pos::{sym, Span};
 use rustc_error_codes::*;
 
@@ -47,6 +47,23 @@ impl NonConstExpr {
             | Self::Match(IfLetDesugar { .. })
             => Some(features.const_if_match),
 
+            // The `Try` and `From` calls in the desugaring are checked by the MIR const-checker,
+            // which accepts them if they resolve to `const` impls.
+            Self::Match(TryDesugar) => Some(features.const_if_match && features.const_trait_impl),
+
+            Self::Loop(hir::LoopSource::Loop) => Some(features.const_loop),
+
+            // The condition of a `while` loop is lowered to a `match`.
+            | Self::Loop(hir::LoopSource::While)
+            | Self::Loop(hir::LoopSource::WhileLet)
+            => Some(features.const_loop && features.const_if_match),
+
+            // A `for` loop additionally calls `IntoIterator::into_iter` and `Iterator::next`. The
+            // MIR const-checker accepts these calls if they resolve to `const` impls.
+            Self::Loop(hir::LoopSource::ForLoop) => {
+                Some(features.const_loop && features.const_if_match && features.const_trait_impl)
+            }
+
             _ => None,
         }
     }
@@ -133,6 +150,14 @@ impl<'tcx> CheckConstVisitor<'tcx> {
             _ => {}
         }
 
+        // With `-Zunleash-the-miri-inside-of-you`, loops are left to the MIR const-checker, which
+        // reports skipping the check at the head of each loop.
+        if let NonConstExpr::Loop(_) = expr {
+            if self.tcx.sess.opts.debugging_opts.unleash_the_miri_inside_of_you {
+                return;
+            }
+        }
+
         let const_kind = self.const_kind
             .expect("`const_check_violated` may only be called inside a const context");
 
@@ -143,10 +168,48 @@ impl<'tcx> CheckConstVisitor<'tcx> {
             | NonConstExpr::Match(hir::MatchSource::IfLetDesugar { .. })
             => feature_err(&self.tcx.sess.parse_sess, sym::const_if_match, span, &msg).emit(),
 
+            NonConstExpr::Loop(hir::LoopSource::ForLoop) => self.for_loop_violated(span, &msg),
+
+            NonConstExpr::Loop(_) => {
+                let gate = if !self.tcx.features().const_loop {
+                    sym::const_loop
+                } else {
+                    sym::const_if_match
+                };
+                feature_err(&self.tcx.sess.parse_sess, gate, span, &msg).emit()
+            }
+
             _ => span_err!(self.tcx.sess, span, E0744, "{}", msg),
         }
     }
 
+    /// Emits a single error for a `for` loop explaining everything it needs to be allowed in a
+    /// const context, instead of one error per piece of its desugaring.
+    fn for_loop_violated(&self, span: Span, msg: &str) {
+        let features = self.tcx.features();
+        let gates = [
+            (features.const_loop, sym::const_loop),
+            (features.const_if_match, sym::const_if_match),
+            (features.const_trait_impl, sym::const_trait_impl),
+        ];
+        let missing: Vec<_> = gates.iter()
+            .filter(|(enabled, _)| !enabled)
+            .map(|(_, gate)| gate.to_string())
+            .collect();
+
+        let mut err = struct_span_err!(self.tcx.sess, span, E0744, "{}", msg);
+        err.note("a `for` loop calls `IntoIterator::into_iter` and `Iterator::next`, and matches \
+                  on the result of `next` in a loop");
+        if self.tcx.sess.parse_sess.unstable_features.is_nightly_build() {
+            err.help(&format!(
+                "add `#![feature({})]` to the crate attributes and use `const` impls of \
+                 `IntoIterator` and `Iterator` to enable",
+                missing.join(", "),
+            ));
+        }
+        err.emit();
+    }
+
     /// Saves the parent `const_kind` before calling `f` and restores it afterwards.
     fn recurse_into(&mut self, kind: Option<ConstKind>, f: impl FnOnce(&mut Self)) {
         let parent_kind = self.const_kind;
@@ -180,7 +243,7 @@ impl<'tcx> Visitor<'tcx> for CheckConstVisitor<'tcx> {
                 self.const_check_violated(NonConstExpr::Loop(*source), e.span);
             }
 
-            hir::ExprKind::Match(_, _, source) => {
+            hir::ExprKind::Match(scrutinee, _, source) => {
                 let non_const_expr = match source {
                     // These are handled by `ExprKind::Loop` above.
                     | hir::MatchSource::WhileDesugar
@@ -192,7 +255,14 @@ impl<'tcx> Visitor<'tcx> for CheckConstVisitor<'tcx> {
                 };
 
                 if let Some(expr) = non_const_expr {
-                    self.const_check_violated(expr, e.span);
+                    // Point at the value being matched on (the condition of an `if`) instead of
+                    // the whole expression, which may span many lines. The scrutinee of a `?` is
+                    // the expression it is applied to, so point at the `?` expression instead.
+                    let span = match source {
+                        hir::MatchSource::TryDesugar => e.span,
+                        _ => scrutinee.span,
+                    };
+                    self.const_check_violated(expr, span);
                 }
             }
 
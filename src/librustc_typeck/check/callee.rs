@@ -12,6 +12,7 @@ use rustc::{infer, traits};
 use rustc::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
 use rustc_target::spec::abi;
 use syntax::ast::Ident;
+use syntax::symbol::sym;
 use syntax_pos::Span;
 
 use rustc::hir;
@@ -418,9 +419,37 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             def_span,
         );
 
+        if let ty::FnDef(def_id, _) = callee_ty.kind {
+            if fn_sig.abi == abi::Abi::RustIntrinsic
+                && self.tcx.item_name(def_id) == sym::const_eval_select
+            {
+                self.check_const_eval_select_args(arg_exprs);
+            }
+        }
+
         fn_sig.output()
     }
 
+    /// Checks that the functions passed to `const_eval_select` are function items. The chosen one
+    /// is called directly, both by the interpreter and in codegen, so closures and function
+    /// pointers are not supported.
+    fn check_const_eval_select_args(&self, arg_exprs: &'tcx [hir::Expr]) {
+        for arg in arg_exprs.iter().skip(1) {
+            let ty = self.resolve_vars_with_obligations(self.node_ty(arg.hir_id));
+            match ty.kind {
+                ty::FnDef(..) | ty::Infer(_) | ty::Error => {}
+                _ => {
+                    self.tcx.sess.struct_span_err(
+                        arg.span,
+                        "this argument to `const_eval_select` must be a function item",
+                    )
+                    .note(&format!("expected a function item, found `{}`", ty))
+                    .emit();
+                }
+            }
+        }
+    }
+
     fn confirm_deferred_closure_call(
         &self,
         call_expr: &'tcx hir::Expr,
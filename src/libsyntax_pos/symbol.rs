@@ -120,6 +120,7 @@ symbols! {
         abi_unadjusted,
         abi_vectorcall,
         abi_x86_interrupt,
+        abort,
         aborts,
         advanced_slice_patterns,
         adx_target_feature,
@@ -201,17 +202,26 @@ symbols! {
         console,
         const_compare_raw_pointers,
         const_constructor,
+        const_eval_limit,
+        const_eval_select,
         const_extern_fn,
         const_fn,
+        const_fn_floating_point_arithmetic,
+        const_fn_thread_local,
         const_fn_union,
         const_generics,
         const_if_match,
         const_indexing,
         const_in_array_repeat_expressions,
         const_let,
+        const_loop,
         const_panic,
+        const_raw_mut_ptr,
         const_raw_ptr_deref,
         const_raw_ptr_to_usize_cast,
+        const_refs_to_static,
+        const_simd,
+        const_trait_impl,
         const_transmute,
         contents,
         context,
@@ -238,6 +248,7 @@ symbols! {
         Decodable,
         Default,
         default_lib_allocator,
+        default_method_body_is_const,
         default_type_parameter_fallback,
         default_type_params,
         delay_span_bug_from_inside_query,
@@ -615,9 +626,13 @@ symbols! {
         rustc_partition_reused,
         rustc_peek,
         rustc_peek_definite_init,
+        rustc_peek_has_mut_interior,
         rustc_peek_maybe_init,
         rustc_peek_maybe_uninit,
         rustc_peek_indirectly_mutable,
+        rustc_peek_needs_drop,
+        rustc_peek_needs_non_const_drop,
+        rustc_peek_ref_to_static,
         rustc_private,
         rustc_proc_macro_decls,
         rustc_promotable,
@@ -0,0 +1,121 @@
+#![feature(rustc_private)]
+
+extern crate rustc;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate rustc_mir;
+extern crate syntax;
+
+use rustc::hir::def_id::{DefId, LOCAL_CRATE};
+use rustc::mir::ConstQualifs;
+use rustc::session::{DiagnosticOutput, Session};
+use rustc::session::config::{Input, Options};
+use rustc::ty::TyCtxt;
+use rustc::ty::query::Providers;
+use rustc_interface::interface;
+use rustc_mir::transform::check_consts::Item;
+use rustc_mir::transform::check_consts::qualifs::{
+    DuplicateExtraQualif, ExtraQualif, ExtraQualifs,
+};
+use syntax::source_map::FileName;
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static WITH_QUALIF: AtomicUsize = AtomicUsize::new(0);
+static WITHOUT_QUALIF: AtomicUsize = AtomicUsize::new(0);
+
+struct ReturnsBool;
+
+impl ExtraQualif for ReturnsBool {
+    fn name(&self) -> &'static str {
+        "returns_bool"
+    }
+
+    fn in_return_place<'tcx>(&self, item: &Item<'_, 'tcx>) -> bool {
+        item.body.return_ty().is_bool()
+    }
+}
+
+fn mir_const_qualif(tcx: TyCtxt<'_>, def_id: DefId) -> ConstQualifs {
+    let mut extra = ExtraQualifs::new();
+    extra.register(&ReturnsBool).unwrap();
+
+    let (qualifs, results) =
+        rustc_mir::transform::mir_const_qualif_with_extra(tcx, def_id, &extra);
+
+    match results.get("returns_bool") {
+        Some(true) => WITH_QUALIF.fetch_add(1, Ordering::SeqCst),
+        Some(false) => WITHOUT_QUALIF.fetch_add(1, Ordering::SeqCst),
+        None => 0,
+    };
+
+    qualifs
+}
+
+fn override_queries(_: &Session, providers: &mut Providers<'_>, _: &mut Providers<'_>) {
+    providers.mir_const_qualif = mir_const_qualif;
+}
+
+fn main() {
+    let src = r#"
+    const A: bool = true;
+    const B: bool = !A;
+    const C: u8 = 0;
+
+    fn main() {}
+    "#;
+
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.len() < 3 {
+        panic!("expected rustc path");
+    }
+
+    let mut sysroot = PathBuf::from(&args[2]);
+    sysroot.pop();
+    sysroot.pop();
+
+    // Registering two qualifs with the same name is an error, not a panic.
+    let mut extra = ExtraQualifs::new();
+    assert_eq!(extra.register(&ReturnsBool), Ok(()));
+    assert_eq!(extra.register(&ReturnsBool), Err(DuplicateExtraQualif("returns_bool")));
+
+    analyze(src.to_string(), sysroot);
+
+    assert_eq!(WITH_QUALIF.load(Ordering::SeqCst), 2);
+    assert_eq!(WITHOUT_QUALIF.load(Ordering::SeqCst), 1);
+}
+
+fn analyze(code: String, sysroot: PathBuf) {
+    let mut opts = Options::default();
+    opts.maybe_sysroot = Some(sysroot);
+
+    let name = FileName::anon_source_code(&code);
+    let input = Input::Str { name, input: code };
+
+    let config = interface::Config {
+        opts,
+        crate_cfg: Default::default(),
+        input,
+        input_path: None,
+        output_file: None,
+        output_dir: None,
+        file_loader: None,
+        diagnostic_output: DiagnosticOutput::Default,
+        stderr: None,
+        crate_name: None,
+        lint_caps: Default::default(),
+        register_lints: None,
+        override_queries: Some(override_queries),
+        registry: rustc_driver::diagnostics_registry(),
+    };
+
+    interface::run_compiler(config, |compiler| {
+        let result = compiler.enter(|queries| {
+            let result = queries.global_ctxt()?.peek_mut().enter(|tcx| tcx.analysis(LOCAL_CRATE));
+            result
+        });
+        assert!(result.is_ok());
+    });
+}
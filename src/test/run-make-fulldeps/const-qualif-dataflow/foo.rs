@@ -0,0 +1,181 @@
+#![feature(rustc_private)]
+
+extern crate rustc;
+extern crate rustc_driver;
+extern crate rustc_index;
+extern crate rustc_interface;
+extern crate rustc_mir;
+extern crate syntax;
+
+use rustc::hir::def_id::DefId;
+use rustc::mir::{BasicBlock, Body, Local, Operand, Place, Rvalue, Statement, StatementKind};
+use rustc::mir::{RETURN_PLACE, START_BLOCK};
+use rustc::session::DiagnosticOutput;
+use rustc::session::config::{Input, Options};
+use rustc::ty::{Ty, TyCtxt};
+use rustc_index::vec::Idx;
+use rustc_interface::interface;
+use rustc_mir::transform::check_consts::qualifs::{HasMutInterior, NeedsDrop};
+use rustc_mir::transform::check_consts::validation::in_return_place;
+use rustc_mir::transform::check_consts::{ConstKind, Item, Qualif};
+use syntax::source_map::FileName;
+
+use std::path::PathBuf;
+
+use mir_builder::MirBuilder;
+
+mod mir_builder;
+
+fn main() {
+    let src = r#"
+    const ITEM: () = ();
+
+    fn main() {}
+    "#;
+
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.len() < 3 {
+        panic!("expected rustc path");
+    }
+
+    let mut sysroot = PathBuf::from(&args[2]);
+    sysroot.pop();
+    sysroot.pop();
+
+    run(src.to_string(), sysroot);
+}
+
+/// Returns the locals that may have the qualif `Q` on entry to each block of `body`.
+///
+/// `body` must not assign to the return place, and all of its blocks must reach its `Return`.
+/// A local is qualified on entry to a block if the return place is qualified after copying the
+/// local into it at the start of that block.
+fn qualified_at_block_entry<'tcx, Q: Qualif>(
+    q: impl Fn() -> Q,
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    body: &Body<'tcx>,
+) -> Vec<Vec<usize>> {
+    body.basic_blocks().indices().map(|bb| {
+        body.local_decls.indices().skip(1).filter(|&local| {
+            let probe = copy_to_return_place(body, bb, local);
+            let item = Item::with_const_kind(tcx, def_id, &probe, Some(ConstKind::Const));
+            in_return_place(q(), &item)
+        }).map(|local| local.index()).collect()
+    }).collect()
+}
+
+/// Returns a copy of `body` with `_0 = copy local` at the start of `bb`.
+fn copy_to_return_place<'tcx>(body: &Body<'tcx>, bb: BasicBlock, local: Local) -> Body<'tcx> {
+    let mut body = body.clone();
+    let source_info = *body.source_info(body.terminator_loc(bb));
+    let rvalue = Rvalue::Use(Operand::Copy(Place::from(local)));
+    let kind = StatementKind::Assign(Box::new((Place::from(RETURN_PLACE), rvalue)));
+    body.basic_blocks_mut()[bb].statements.insert(0, Statement { source_info, kind });
+    body
+}
+
+/// ```text
+/// fn(_1: ty) -> ty {
+///     bb0: { _2 = move _1; goto -> bb1; }
+///     bb1: { return; }
+/// }
+/// ```
+fn move_arg<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Body<'tcx> {
+    let mut b = MirBuilder::new(tcx, ty, &[ty]);
+    let (arg, tmp) = (b.arg(0), b.temp(ty));
+    let bb1 = b.block();
+    let moved = b.move_(arg);
+    b.assign(START_BLOCK, tmp, Rvalue::Use(moved));
+    b.goto(START_BLOCK, bb1);
+    b.return_(bb1);
+    b.finish()
+}
+
+/// ```text
+/// fn(_1: ty, _2: bool) -> ty {
+///     bb0: { switchInt(copy _2) -> [false: bb2, otherwise: bb1]; }
+///     bb1: { _3 = move _1; goto -> bb3; }
+///     bb2: { goto -> bb3; }
+///     bb3: { return; }
+/// }
+/// ```
+fn move_arg_in_branch<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Body<'tcx> {
+    let mut b = MirBuilder::new(tcx, ty, &[ty, tcx.types.bool]);
+    let (arg, cond, tmp) = (b.arg(0), b.arg(1), b.temp(ty));
+    let (bb1, bb2, bb3) = (b.block(), b.block(), b.block());
+    let cond = b.copy(cond);
+    b.if_(START_BLOCK, cond, bb1, bb2);
+    let moved = b.move_(arg);
+    b.assign(bb1, tmp, Rvalue::Use(moved));
+    b.goto(bb1, bb3);
+    b.goto(bb2, bb3);
+    b.return_(bb3);
+    b.finish()
+}
+
+fn check(tcx: TyCtxt<'_>, def_id: DefId) {
+    let boxed = tcx.mk_box(tcx.types.i32);
+    let unsafe_cell = tcx.lang_items().unsafe_cell_type().unwrap();
+    let cell = tcx.mk_adt(tcx.adt_def(unsafe_cell), tcx.intern_substs(&[tcx.types.i32.into()]));
+
+    // Moving out of a local clears `NeedsDrop`...
+    let body = move_arg(tcx, boxed);
+    assert_eq!(qualified_at_block_entry(|| NeedsDrop, tcx, def_id, &body), [vec![1], vec![2]]);
+
+    // ...but not `HasMutInterior`.
+    let body = move_arg(tcx, cell);
+    assert_eq!(
+        qualified_at_block_entry(|| HasMutInterior, tcx, def_id, &body),
+        [vec![1], vec![1, 2]],
+    );
+
+    // A local that is not qualified on some path into a block may still be qualified in it.
+    let body = move_arg_in_branch(tcx, boxed);
+    assert_eq!(
+        qualified_at_block_entry(|| NeedsDrop, tcx, def_id, &body),
+        [vec![1], vec![1], vec![1], vec![1, 3]],
+    );
+
+    // Locals whose type can never have the qualif are never qualified.
+    let body = move_arg_in_branch(tcx, tcx.types.i32);
+    let unqualified: [Vec<usize>; 4] = Default::default();
+    assert_eq!(qualified_at_block_entry(|| NeedsDrop, tcx, def_id, &body), unqualified);
+}
+
+fn run(code: String, sysroot: PathBuf) {
+    let mut opts = Options::default();
+    opts.maybe_sysroot = Some(sysroot);
+
+    let name = FileName::anon_source_code(&code);
+    let input = Input::Str { name, input: code };
+
+    let config = interface::Config {
+        opts,
+        crate_cfg: Default::default(),
+        input,
+        input_path: None,
+        output_file: None,
+        output_dir: None,
+        file_loader: None,
+        diagnostic_output: DiagnosticOutput::Default,
+        stderr: None,
+        crate_name: None,
+        lint_caps: Default::default(),
+        register_lints: None,
+        override_queries: None,
+        registry: rustc_driver::diagnostics_registry(),
+    };
+
+    interface::run_compiler(config, |compiler| {
+        compiler.enter(|queries| {
+            queries.global_ctxt().unwrap().peek_mut().enter(|tcx| {
+                let def_id = tcx.body_owners()
+                    .find(|&def_id| tcx.def_path_str(def_id) == "ITEM")
+                    .unwrap();
+                check(tcx, def_id);
+            })
+        });
+    });
+}
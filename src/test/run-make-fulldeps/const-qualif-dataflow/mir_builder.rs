@@ -0,0 +1,169 @@
+//! A small DSL for constructing MIR bodies by hand.
+//!
+//! This is used to test the qualif dataflow of `check_consts` on synthetic bodies, without having
+//! to find a Rust program that lowers to exactly the right MIR:
+//!
+//! ```ignore (needs a `TyCtxt`)
+//! let mut b = MirBuilder::new(tcx, return_ty, &[arg_ty]);
+//! let (arg, tmp) = (b.arg(0), b.temp(arg_ty));
+//! let (bb0, bb1) = (START_BLOCK, b.block());
+//! b.assign(bb0, tmp, Rvalue::Use(b.move_(arg)));
+//! b.goto(bb0, bb1);
+//! b.assign(bb1, RETURN_PLACE, Rvalue::Use(b.move_(tmp)));
+//! b.return_(bb1);
+//! let body = b.finish();
+//! ```
+
+use rustc::mir::*;
+use rustc::ty::{self, Ty, TyCtxt};
+use rustc_index::vec::{Idx, IndexVec};
+use syntax_pos::{Span, DUMMY_SP};
+
+pub struct MirBuilder<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    span: Span,
+    local_decls: IndexVec<Local, LocalDecl<'tcx>>,
+    basic_blocks: IndexVec<BasicBlock, BasicBlockData<'tcx>>,
+    arg_count: usize,
+}
+
+impl<'tcx> MirBuilder<'tcx> {
+    /// Starts a body with the given signature. It has a single, empty block: `START_BLOCK`.
+    pub fn new(tcx: TyCtxt<'tcx>, return_ty: Ty<'tcx>, arg_tys: &[Ty<'tcx>]) -> Self {
+        let span = DUMMY_SP;
+        let mut local_decls = IndexVec::new();
+        local_decls.push(LocalDecl::new_return_place(return_ty, span));
+        for &ty in arg_tys {
+            local_decls.push(LocalDecl::new_temp(ty, span));
+        }
+
+        let mut builder = MirBuilder {
+            tcx,
+            span,
+            local_decls,
+            basic_blocks: IndexVec::new(),
+            arg_count: arg_tys.len(),
+        };
+        builder.block();
+        builder
+    }
+
+    /// Returns the local of the `index`-th argument.
+    pub fn arg(&self, index: usize) -> Local {
+        assert!(index < self.arg_count, "there is no argument {}", index);
+        Local::new(index + 1)
+    }
+
+    /// Declares a new temporary of type `ty`.
+    pub fn temp(&mut self, ty: Ty<'tcx>) -> Local {
+        self.local_decls.push(LocalDecl::new_temp(ty, self.span))
+    }
+
+    /// Adds a new block. It must be given a terminator before calling `finish`.
+    pub fn block(&mut self) -> BasicBlock {
+        self.basic_blocks.push(BasicBlockData::new(None))
+    }
+
+    pub fn copy(&self, place: impl Into<Place<'tcx>>) -> Operand<'tcx> {
+        Operand::Copy(place.into())
+    }
+
+    pub fn move_(&self, place: impl Into<Place<'tcx>>) -> Operand<'tcx> {
+        Operand::Move(place.into())
+    }
+
+    pub fn constant(&self, literal: &'tcx ty::Const<'tcx>) -> Operand<'tcx> {
+        Operand::Constant(Box::new(Constant { span: self.span, user_ty: None, literal }))
+    }
+
+    pub fn const_bool(&self, value: bool) -> Operand<'tcx> {
+        self.constant(ty::Const::from_bool(self.tcx, value))
+    }
+
+    pub fn const_usize(&self, value: u64) -> Operand<'tcx> {
+        self.constant(ty::Const::from_usize(self.tcx, value))
+    }
+
+    /// Appends a statement to `block`.
+    pub fn statement(&mut self, block: BasicBlock, kind: StatementKind<'tcx>) -> &mut Self {
+        let source_info = self.source_info();
+        self.basic_blocks[block].statements.push(Statement { source_info, kind });
+        self
+    }
+
+    pub fn assign(
+        &mut self,
+        block: BasicBlock,
+        place: impl Into<Place<'tcx>>,
+        rvalue: Rvalue<'tcx>,
+    ) -> &mut Self {
+        self.statement(block, StatementKind::Assign(Box::new((place.into(), rvalue))))
+    }
+
+    pub fn storage_live(&mut self, block: BasicBlock, local: Local) -> &mut Self {
+        self.statement(block, StatementKind::StorageLive(local))
+    }
+
+    pub fn storage_dead(&mut self, block: BasicBlock, local: Local) -> &mut Self {
+        self.statement(block, StatementKind::StorageDead(local))
+    }
+
+    /// Sets the terminator of `block`, which must not have one yet.
+    pub fn terminate(&mut self, block: BasicBlock, kind: TerminatorKind<'tcx>) {
+        let source_info = self.source_info();
+        let data = &mut self.basic_blocks[block];
+        assert!(data.terminator.is_none(), "{:?} already has a terminator", block);
+        data.terminator = Some(Terminator { source_info, kind });
+    }
+
+    pub fn goto(&mut self, block: BasicBlock, target: BasicBlock) {
+        self.terminate(block, TerminatorKind::Goto { target });
+    }
+
+    pub fn if_(&mut self, block: BasicBlock, cond: Operand<'tcx>, t: BasicBlock, f: BasicBlock) {
+        let kind = TerminatorKind::if_(self.tcx, cond, t, f);
+        self.terminate(block, kind);
+    }
+
+    pub fn drop(
+        &mut self,
+        block: BasicBlock,
+        location: impl Into<Place<'tcx>>,
+        target: BasicBlock,
+    ) {
+        let kind = TerminatorKind::Drop { location: location.into(), target, unwind: None };
+        self.terminate(block, kind);
+    }
+
+    pub fn return_(&mut self, block: BasicBlock) {
+        self.terminate(block, TerminatorKind::Return);
+    }
+
+    /// Returns the finished body. Panics if a block has no terminator.
+    pub fn finish(self) -> Body<'tcx> {
+        for (bb, data) in self.basic_blocks.iter_enumerated() {
+            assert!(data.terminator.is_some(), "{:?} has no terminator", bb);
+        }
+
+        let source_scope = SourceScopeData {
+            span: self.span,
+            parent_scope: None,
+            local_data: ClearCrossCrate::Clear,
+        };
+        Body::new(
+            self.basic_blocks,
+            IndexVec::from_elem_n(source_scope, 1),
+            self.local_decls,
+            IndexVec::new(),
+            self.arg_count,
+            vec![],
+            self.span,
+            vec![],
+            None,
+        )
+    }
+
+    fn source_info(&self) -> SourceInfo {
+        SourceInfo { span: self.span, scope: OUTERMOST_SOURCE_SCOPE }
+    }
+}
@@ -1,5 +1,6 @@
 fn main() {
     &{[1, 2, 3][4]};
     //~^ ERROR index out of bounds
-    //~| ERROR reaching this expression at runtime will panic or abort
+    //~| WARN reaching this expression at runtime will panic or abort
+    //~| WARN this was previously accepted by the compiler but is being phased out
 }
@@ -0,0 +1,5 @@
+use std::cell::Cell;
+
+pub const fn no_cell() -> Option<Cell<i32>> {
+    None
+}
@@ -0,0 +1,16 @@
+// check-pass
+
+// With `const_raw_mut_ptr`, `&raw mut` is allowed in every const context.
+
+#![feature(raw_ref_op, const_raw_mut_ptr)]
+
+const A: () = { let mut x = 2; &raw mut x; };
+
+static B: () = { let mut x = 2; &raw mut x; };
+
+const fn foo() {
+    let mut x = 0;
+    let _y = &raw mut x;
+}
+
+fn main() {}
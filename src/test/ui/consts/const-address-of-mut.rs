@@ -0,0 +1,14 @@
+#![feature(raw_ref_op)]
+
+const A: () = { let mut x = 2; &raw mut x; };   //~ ERROR `&raw mut` in constants is unstable
+
+static B: () = { let mut x = 2; &raw mut x; };  //~ ERROR `&raw mut` in statics is unstable
+
+static mut C: () = { let mut x = 2; &raw mut x; };
+
+const fn foo() {
+    let mut x = 0;
+    let y = &raw mut x;                         //~ ERROR `&raw mut` in constant functions is
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// check-pass
+
+#![feature(raw_ref_op)]
+
+const A: *const i32 = &raw const *&2;
+static B: () = { &raw const *&2; };
+static mut C: *mut i32 = &raw mut *&mut 3;
+
+const fn foo() {
+    let x = 0;
+    let y = &raw const x;
+}
+
+fn main() {}
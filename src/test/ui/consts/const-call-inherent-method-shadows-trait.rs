@@ -0,0 +1,25 @@
+// Check that calling a non-const inherent method that shadows a trait method of the same name
+// points out which of the two was called.
+
+struct Counter;
+
+impl Counter {
+    fn get(&self) -> u32 {
+        0
+    }
+}
+
+trait Get {
+    fn get(&self) -> u32;
+}
+
+impl Get for Counter {
+    fn get(&self) -> u32 {
+        1
+    }
+}
+
+const ZERO: u32 = Counter.get();
+//~^ ERROR calls in constants are limited to constant functions
+
+fn main() {}
@@ -0,0 +1,6 @@
+// Check that `-Zconst-check-level` rejects operations that do not exist.
+
+// compile-flags: -Zconst-check-level=MutBorow=warn
+// error-pattern: incorrect value `MutBorow=warn` for debugging option `const-check-level`
+
+fn main() {}
@@ -0,0 +1,7 @@
+// Check that `-Zconst-check-level` does not downgrade the errors for operations that miri cannot
+// evaluate.
+
+// compile-flags: -Zconst-check-level=InlineAsm=warn
+// error-pattern: incorrect value `InlineAsm=warn` for debugging option `const-check-level`
+
+fn main() {}
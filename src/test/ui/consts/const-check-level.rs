@@ -0,0 +1,27 @@
+// Check that `-Zconst-check-level` reports the errors for the given operations as warnings, and
+// that the last level given for an operation wins.
+
+// compile-flags: -Zconst-check-level=FnCallNonConst=warn,MutBorrow=warn
+// compile-flags: -Zconst-check-level=MutBorrow=deny
+
+#![feature(const_fn)]
+
+fn count() -> u32 {
+    let mut x = 0;
+    while x < 10 {
+        x += 1;
+    }
+    x
+}
+
+const fn total() -> u32 {
+    count() //~ WARN calls in constant functions are limited to constant functions
+}
+
+const _: i32 = {
+    let mut a = 5;
+    let p = &mut a; //~ ERROR references in constants may only refer to immutable values
+    *p
+};
+
+fn main() {}
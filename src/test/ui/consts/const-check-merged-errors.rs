@@ -0,0 +1,21 @@
+// Check that several operations of the same kind in one item are reported in a single error.
+
+#![allow(const_err)]
+
+fn count() -> u32 {
+    let mut x = 0;
+    while x < 10 {
+        x += 1;
+    }
+    x
+}
+
+const TOTAL: u32 = {
+    let a = count();
+    //~^ ERROR calls in constants are limited to constant functions
+    let b = count();
+    let c = count();
+    a + b + c
+};
+
+fn main() {}
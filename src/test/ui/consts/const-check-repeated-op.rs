@@ -0,0 +1,24 @@
+// Check that an operation that is checked more than once at the same span, e.g. because a macro
+// repeats its argument, is only reported once.
+
+#![allow(const_err)]
+
+macro_rules! twice {
+    ($e:expr) => { $e + $e };
+}
+
+fn count() -> u32 {
+    let mut x = 0;
+    while x < 10 {
+        x += 1;
+    }
+    x
+}
+
+const TWICE: u32 = twice!(count());
+//~^ ERROR calls in constants are limited to constant functions
+
+const MIXED: u32 = count() + twice!(count()) + count();
+//~^ ERROR calls in constants are limited to constant functions
+
+fn main() {}
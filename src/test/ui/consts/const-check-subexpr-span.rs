@@ -0,0 +1,9 @@
+// Errors for projections point at the projection itself rather than at the whole statement.
+
+const PTR: *const u32 = &42;
+
+const WRITE: () = unsafe { *(PTR as *mut u32) = 5; };
+//~^ ERROR dereferencing raw pointers in constants is unstable
+//~| ERROR mutation through a reference is not allowed in constants
+
+fn main() {}
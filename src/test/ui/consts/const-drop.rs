@@ -0,0 +1,32 @@
+// run-pass
+
+// Check that values whose `Drop` impls are `const` can be dropped at compile-time.
+
+#![feature(const_fn, const_trait_impl)]
+#![allow(incomplete_features)]
+
+pub struct ConstDrop(pub i32);
+
+impl Drop for ConstDrop {
+    const fn drop(&mut self) {}
+}
+
+pub struct Wrapper(pub ConstDrop, pub i32);
+
+const fn unwrap(x: i32) -> i32 {
+    let wrapper = Wrapper(ConstDrop(x), x);
+    wrapper.1
+}
+
+const X: i32 = unwrap(3);
+
+const Y: i32 = {
+    let _wrapper = Wrapper(ConstDrop(1), 2);
+    let _tuple = (ConstDrop(3), Some(ConstDrop(4)));
+    5
+};
+
+fn main() {
+    assert_eq!(X, 3);
+    assert_eq!(Y, 5);
+}
@@ -0,0 +1,27 @@
+// run-pass
+
+#![feature(const_fn, const_trait_impl)]
+#![allow(incomplete_features)]
+
+trait Answer {
+    fn answer(&self) -> u32;
+}
+
+struct Foo;
+
+impl Answer for Foo {
+    const fn answer(&self) -> u32 {
+        42
+    }
+}
+
+const fn dispatch(x: &dyn Answer) -> u32 {
+    x.answer()
+}
+
+const ANSWER: u32 = dispatch(&Foo);
+
+fn main() {
+    assert_eq!(ANSWER, 42);
+    assert_eq!(dispatch(&Foo), 42);
+}
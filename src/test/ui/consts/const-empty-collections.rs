@@ -0,0 +1,55 @@
+// run-pass
+
+// Empty heap-backed collections do not allocate, so they can be built and inspected at compile
+// time. Their buffer is a dangling pointer aligned for the element type.
+
+#![feature(const_collection_len)]
+
+use std::collections::LinkedList;
+
+const EMPTY_VEC: Vec<u64> = Vec::new();
+const EMPTY_ZST_VEC: Vec<()> = Vec::new();
+const EMPTY_STRING: String = String::new();
+const EMPTY_LIST: LinkedList<u8> = LinkedList::new();
+
+static NESTED: Vec<Vec<String>> = Vec::new();
+
+// Borrow the collections in the final value, as dropping them is not possible in constants.
+const VEC_REF: &Vec<u64> = &Vec::new();
+const STRING_REF: &String = &String::new();
+
+const VEC_LEN: usize = VEC_REF.len();
+const VEC_IS_EMPTY: bool = VEC_REF.is_empty();
+const STRING_LEN: usize = STRING_REF.len();
+const STRING_IS_EMPTY: bool = STRING_REF.is_empty();
+
+trait Empty {
+    const EMPTY: Self;
+}
+
+impl<T> Empty for Vec<T> {
+    const EMPTY: Self = Vec::new();
+}
+
+fn main() {
+    assert_eq!(VEC_LEN, 0);
+    assert!(VEC_IS_EMPTY);
+    assert_eq!(STRING_LEN, 0);
+    assert!(STRING_IS_EMPTY);
+
+    let mut v = EMPTY_VEC;
+    assert_eq!(v.capacity(), 0);
+    assert_eq!(v.as_ptr() as usize, std::mem::align_of::<u64>());
+    v.push(1);
+    assert_eq!(v, [1]);
+
+    assert_eq!(EMPTY_ZST_VEC.capacity(), usize::max_value());
+
+    let mut s = EMPTY_STRING;
+    s.push_str("hello");
+    assert_eq!(s, "hello");
+
+    assert!(EMPTY_LIST.is_empty());
+    assert!(NESTED.is_empty());
+    assert!(<Vec<&str> as Empty>::EMPTY.is_empty());
+}
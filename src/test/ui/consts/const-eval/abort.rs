@@ -0,0 +1,12 @@
+#![feature(core_intrinsics, const_fn)]
+
+// `abort` is not allowed in a `min_const_fn`, but with `const_fn` it is allowed...
+pub const fn fail() -> ! {
+    unsafe { std::intrinsics::abort() }
+}
+
+// ...but evaluating it is an error.
+const X: u32 = unsafe { std::intrinsics::abort() };
+//~^ ERROR any use of this value will cause an error
+
+fn main() {}
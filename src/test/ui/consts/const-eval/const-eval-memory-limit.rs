@@ -0,0 +1,17 @@
+// compile-flags: -Z const-eval-memory-limit=1024
+
+#![deny(const_err)]
+
+const SMALL: [u8; 512] = [0; 512];
+
+const BIG: [u8; 2048] = [0; 2048];
+//~^ ERROR any use of this value will cause an error
+
+const TWO_LOCALS: u8 = {
+    let a = [0u8; 600];
+    let b = [1u8; 600];
+    //~^ ERROR any use of this value will cause an error
+    a[0] + b[0]
+};
+
+fn main() {}
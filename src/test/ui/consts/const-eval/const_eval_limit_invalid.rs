@@ -0,0 +1,10 @@
+// Check that an item-level `#[const_eval_limit]` that is not a number is an error.
+
+#![feature(const_eval_limit)]
+
+#[const_eval_limit = "abc"] //~ ERROR `#[const_eval_limit]` must be a non-negative integer
+const X: u32 = 1;
+
+fn main() {
+    let _ = X;
+}
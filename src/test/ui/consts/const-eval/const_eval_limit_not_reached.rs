@@ -0,0 +1,29 @@
+// Check that the const evaluation limit can be raised for a single item, and that an evaluation
+// that exceeds it keeps going if `long_running_const_eval` is allowed.
+
+// check-pass
+
+#![feature(const_eval_limit, const_if_match, const_loop)]
+#![const_eval_limit = "1000"]
+
+#[const_eval_limit = "1000000"]
+const RAISED: u32 = {
+    let mut x = 0;
+    while x != 10000 {
+        x += 1;
+    }
+    x
+};
+
+#[allow(long_running_const_eval)]
+const ALLOWED: u32 = {
+    let mut x = 0;
+    while x != 10000 {
+        x += 1;
+    }
+    x
+};
+
+fn main() {
+    assert_eq!(RAISED, ALLOWED);
+}
@@ -0,0 +1,14 @@
+// Check that a const evaluation that takes more steps than the limit allows is reported.
+
+#![feature(const_eval_limit, const_if_match, const_loop)]
+#![const_eval_limit = "1000"]
+
+const X: u32 = { //~ ERROR constant evaluation is taking a long time
+    let mut x = 0;
+    while x != 10000 {
+        x += 1;
+    }
+    x
+};
+
+fn main() {}
@@ -0,0 +1,14 @@
+// Check that calling a function pointer that does not point to a function is a hard error.
+
+// compile-flags: -Zunleash-the-miri-inside-of-you
+#![feature(const_fn, const_transmute)]
+#![allow(const_err)]
+
+const fn call(f: fn() -> u8) -> u8 {
+    f() //~ WARN skipping const checks
+    //~^ ERROR tried to use a pointer that does not point to the start of a function
+}
+
+const FROM_INT: u8 = call(unsafe { std::mem::transmute(8usize) });
+
+fn main() {}
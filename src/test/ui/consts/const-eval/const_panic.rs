@@ -2,10 +2,10 @@
 #![crate_type = "lib"]
 
 pub const Z: () = panic!("cheese");
-//~^ ERROR any use of this value will cause an error
+//~^ ERROR the evaluated program panicked
 
 pub const Y: () = unreachable!();
-//~^ ERROR any use of this value will cause an error
+//~^ ERROR the evaluated program panicked
 
 pub const X: () = unimplemented!();
-//~^ ERROR any use of this value will cause an error
+//~^ ERROR the evaluated program panicked
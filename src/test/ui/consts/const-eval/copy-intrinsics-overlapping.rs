@@ -0,0 +1,13 @@
+#![feature(core_intrinsics)]
+
+use std::intrinsics::copy_nonoverlapping;
+
+static mut OVERLAPPING: [u8; 4] = unsafe {
+    let mut bytes = [1u8, 2, 3, 4];
+    let src = &mut bytes[0] as *mut u8;
+    let dest = &mut bytes[1] as *mut u8;
+    copy_nonoverlapping(src, dest, 2); //~ ERROR could not evaluate static initializer
+    bytes
+};
+
+fn main() {}
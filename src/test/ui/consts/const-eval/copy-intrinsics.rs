@@ -0,0 +1,42 @@
+// run-pass
+
+#![feature(core_intrinsics)]
+
+use std::intrinsics::{copy_nonoverlapping, write_bytes};
+
+// `&mut` borrows of arrays are only allowed inside `static mut` initializers.
+static mut COPIED: [u16; 4] = unsafe {
+    let src = [1u16, 2, 3, 4];
+    let mut dest = [0u16; 4];
+    copy_nonoverlapping(
+        &src as *const [u16; 4] as *const u16,
+        &mut dest as *mut [u16; 4] as *mut u16,
+        4,
+    );
+    dest
+};
+
+static mut FILLED: [u32; 3] = unsafe {
+    let mut dest = [0u32; 3];
+    write_bytes(&mut dest as *mut [u32; 3] as *mut u32, 0xab, 2);
+    dest
+};
+
+static mut EMPTY_COPY: [u8; 2] = unsafe {
+    let src = [5u8, 6];
+    let mut dest = [7u8, 8];
+    copy_nonoverlapping(
+        &src as *const [u8; 2] as *const u8,
+        &mut dest as *mut [u8; 2] as *mut u8,
+        0,
+    );
+    dest
+};
+
+fn main() {
+    unsafe {
+        assert_eq!(COPIED, [1, 2, 3, 4]);
+        assert_eq!(FILLED, [0xabababab, 0xabababab, 0]);
+        assert_eq!(EMPTY_COPY, [7, 8]);
+    }
+}
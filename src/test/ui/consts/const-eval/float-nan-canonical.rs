@@ -0,0 +1,32 @@
+// run-pass
+
+#![feature(const_transmute)]
+#![allow(nonportable_const_float)]
+
+use std::mem::transmute;
+
+const CANONICAL_F32: u32 = 0x7fc0_0000;
+const CANONICAL_F64: u64 = 0x7ff8_0000_0000_0000;
+
+// NaNs with the sign bit set and a payload.
+const NEG_PAYLOAD_F32: f32 = unsafe { transmute(0xffc0_1234u32) };
+const NEG_PAYLOAD_F64: f64 = unsafe { transmute(0xfff8_0000_0000_1234u64) };
+
+const DIV: u32 = unsafe { transmute(0.0f32 / 0.0) };
+const REM: u64 = unsafe { transmute(1.0f64 % 0.0) };
+const PROPAGATED_F32: u32 = unsafe { transmute(NEG_PAYLOAD_F32 + 1.0) };
+const PROPAGATED_F64: u64 = unsafe { transmute(2.0 * NEG_PAYLOAD_F64) };
+const CAST_UP: u64 = unsafe { transmute(NEG_PAYLOAD_F32 as f64) };
+const CAST_DOWN: u32 = unsafe { transmute(NEG_PAYLOAD_F64 as f32) };
+// Negation only flips the sign bit, at runtime as well.
+const NEG: u32 = unsafe { transmute(-NEG_PAYLOAD_F32) };
+
+fn main() {
+    assert_eq!(DIV, CANONICAL_F32);
+    assert_eq!(REM, CANONICAL_F64);
+    assert_eq!(PROPAGATED_F32, CANONICAL_F32);
+    assert_eq!(PROPAGATED_F64, CANONICAL_F64);
+    assert_eq!(CAST_UP, CANONICAL_F64);
+    assert_eq!(CAST_DOWN, CANONICAL_F32);
+    assert_eq!(NEG, 0x7fc0_1234);
+}
@@ -1,6 +1,6 @@
 fn main() {
     let _ = [(); {
-        //~^ WARNING Constant evaluating a complex constant, this might take some time
+        // The loop detector stops this before `long_running_const_eval` is reported.
         let mut x = &0;
         let mut n = 0;
         while n < 5 {
@@ -0,0 +1,19 @@
+// run-pass
+
+// Big repeat expressions of scalars are filled in one go by the interpreter.
+
+const BYTES: [u8; 1_000_000] = [0x2a; 1_000_000];
+const WORDS: [u32; 100_000] = [0x1234_5678; 100_000];
+const FLAGS: [bool; 4096] = [true; 4096];
+// Not a scalar, so this is copied element by element.
+const PAIRS: [(u8, u16); 1024] = [(1, 2); 1024];
+
+static REFS: [&u8; 1024] = [&7; 1024];
+
+fn main() {
+    assert!(BYTES.iter().all(|&b| b == 0x2a));
+    assert!(WORDS.iter().all(|&w| w == 0x1234_5678));
+    assert!(FLAGS.iter().all(|&f| f));
+    assert!(PAIRS.iter().all(|&p| p == (1, 2)));
+    assert!(REFS.iter().all(|&r| *r == 7));
+}
@@ -0,0 +1,13 @@
+// Check that an error inside nested `const fn` calls lists the calls that led to it.
+
+const fn inner(x: u32) -> u32 {
+    x - 1 //~ ERROR any use of this value will cause an error
+}
+
+const fn middle(x: u32) -> u32 {
+    inner(x)
+}
+
+const ZERO: u32 = middle(0);
+
+fn main() {}
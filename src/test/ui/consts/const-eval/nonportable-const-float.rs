@@ -0,0 +1,26 @@
+// check-pass
+
+#![feature(const_fn)]
+
+const NAN_DIV: f32 = 0.0 / 0.0;
+//~^ WARN floating-point operation produces NaN during constant evaluation
+
+const fn add(a: f64, b: f64) -> f64 {
+    a + b
+    //~^ WARN floating-point operation produces NaN during constant evaluation
+}
+
+const INF_SUB: f64 = add(std::f64::INFINITY, -std::f64::INFINITY);
+
+// A NaN that only propagates from an input is not reported again, and neither is a cast of one.
+const NAN_ADD: f64 = std::f64::NAN + 1.0;
+const NAN_CAST: f32 = std::f64::NAN as f32;
+
+#[allow(nonportable_const_float)]
+const ALLOWED: f32 = 0.0 / 0.0;
+
+const INFINITY: f32 = 1.0 / 0.0;
+
+fn main() {
+    let _ = (NAN_DIV, INF_SUB, NAN_ADD, NAN_CAST, ALLOWED, INFINITY);
+}
@@ -0,0 +1,33 @@
+// Check that the error for a panic in a const context names the macro that panics and the
+// condition of an `assert!`.
+
+#![feature(const_if_match)]
+
+const fn positive(x: i32) -> i32 {
+    assert!(x > 0);
+    //~^ ERROR panicking in constant functions is unstable
+    x
+}
+
+const fn small(x: i32) -> i32 {
+    assert!(x < 10, "{} is too big", x);
+    //~^ ERROR panicking in constant functions is unstable
+    x
+}
+
+const fn nonzero(x: i32) -> i32 {
+    debug_assert!(x != 0);
+    //~^ ERROR panicking in constant functions is unstable
+    x
+}
+
+const fn parity(x: u32) -> u32 {
+    match x % 2 {
+        0 => 0,
+        1 => 1,
+        _ => unreachable!(),
+        //~^ ERROR panicking in constant functions is unstable
+    }
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+// Promoteds that do not depend on the generic parameters of their function are evaluated once for
+// all instances of it. Failures are still reported at the promoted in the generic function, and
+// use the lint levels of that function rather than those of the function instantiating it.
+
+#![allow(const_err)]
+
+const BAR: usize = [5, 6, 7][42];
+
+fn lint<T>() -> usize {
+    let _div = &(1 / 0);
+    //~^ WARN reaching this expression at runtime will panic or abort
+    //~| WARN this was previously accepted by the compiler but is being phased out
+    std::mem::size_of::<T>()
+}
+
+#[allow(failing_promoted_expressions)]
+fn allowed<T>() -> usize {
+    let _div = &(1 / 0);
+    std::mem::size_of::<T>()
+}
+
+fn error<T>() -> usize {
+    let _bar = &BAR; //~ ERROR evaluation of constant expression failed
+    std::mem::size_of::<T>()
+}
+
+#[deny(failing_promoted_expressions)]
+fn main() {
+    lint::<u8>();
+    lint::<u16>();
+    allowed::<u8>();
+    allowed::<u16>();
+    error::<u8>();
+    error::<u16>();
+}
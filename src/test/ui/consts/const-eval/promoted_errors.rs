@@ -8,13 +8,15 @@ fn main() {
     //~^ ERROR const_err
     println!("{}", 1/(1-1));
     //~^ ERROR attempt to divide by zero [const_err]
-    //~| ERROR reaching this expression at runtime will panic or abort [const_err]
+    //~| WARN reaching this expression at runtime will panic or abort
+    //~| WARN this was previously accepted by the compiler but is being phased out
     let _x = 1/(1-1);
     //~^ ERROR const_err
     //~| ERROR const_err
     println!("{}", 1/(false as u32));
     //~^ ERROR attempt to divide by zero [const_err]
-    //~| ERROR reaching this expression at runtime will panic or abort [const_err]
+    //~| WARN reaching this expression at runtime will panic or abort
+    //~| WARN this was previously accepted by the compiler but is being phased out
     let _x = 1/(false as u32);
     //~^ ERROR const_err
     //~| ERROR const_err
@@ -0,0 +1,29 @@
+// run-pass
+
+#![feature(const_raw_ptr_comparison)]
+
+use std::ptr;
+
+static FOO: i32 = 42;
+
+// Comparisons between integer addresses are always decidable.
+const NULL_EQ: bool = ptr::null::<i32>().guaranteed_eq(ptr::null());
+const NULL_NE: bool = ptr::null::<i32>().guaranteed_ne(ptr::null());
+const INT_NE: bool = (1 as *const i32).guaranteed_ne(2 as *const i32);
+
+// The address of an allocation is not known at compile-time, so neither comparison succeeds.
+const PTR_EQ: bool = (&42 as *const i32).guaranteed_eq(ptr::null());
+const PTR_NE: bool = (&42 as *const i32).guaranteed_ne(ptr::null());
+
+fn main() {
+    assert!(NULL_EQ);
+    assert!(!NULL_NE);
+    assert!(INT_NE);
+    assert!(!PTR_EQ);
+    assert!(!PTR_NE);
+
+    // At runtime, both functions behave like `==` and `!=`.
+    let foo: *const i32 = &FOO;
+    assert!(foo.guaranteed_eq(&FOO));
+    assert!(foo.guaranteed_ne(ptr::null()));
+}
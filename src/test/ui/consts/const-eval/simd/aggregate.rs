@@ -0,0 +1,30 @@
+// run-pass
+#![feature(const_fn)]
+#![feature(repr_simd)]
+#![feature(const_simd)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+struct u32x4(u32, u32, u32, u32);
+
+const V: u32x4 = u32x4(1, 2, 3, 4);
+const SUM: u32 = V.0 + V.1 + V.2 + V.3;
+
+const fn swap(v: u32x4) -> u32x4 {
+    u32x4(v.3, v.2, v.1, v.0)
+}
+
+const fn last(v: u32x4) -> u32 {
+    v.3
+}
+
+const SWAPPED: u32x4 = swap(V);
+const LAST: u32 = last(SWAPPED);
+
+fn main() {
+    assert_eq!(SUM, 10);
+    assert_eq!(SWAPPED.0, 4);
+    assert_eq!(LAST, 1);
+    assert_eq!(last(swap(u32x4(5, 6, 7, 8))), 5);
+}
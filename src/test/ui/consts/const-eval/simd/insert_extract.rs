@@ -2,6 +2,7 @@
 #![feature(const_fn)]
 #![feature(repr_simd)]
 #![feature(platform_intrinsics)]
+#![feature(const_simd)]
 #![allow(non_camel_case_types)]
 
 #[repr(simd)] struct i8x1(i8);
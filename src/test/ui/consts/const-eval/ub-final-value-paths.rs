@@ -0,0 +1,25 @@
+// Invalid values in the final value of a const or static are reported with the path to them.
+
+#![feature(const_transmute)]
+
+use std::mem::transmute;
+
+#[derive(Copy, Clone)]
+#[repr(C)]
+struct Flags {
+    count: u8,
+    enabled: bool,
+}
+
+const OK: Flags = Flags { count: 1, enabled: true };
+
+const BAD_FIELD: Flags = unsafe { transmute::<[u8; 2], Flags>([1, 3]) };
+//~^ ERROR it is undefined behavior to use this value
+
+static BAD_BEHIND_REF: &Flags = unsafe { &transmute::<[u8; 2], Flags>([1, 3]) };
+//~^ ERROR it is undefined behavior to use this value
+
+static BAD_IN_ARRAY: [Flags; 2] = [OK, unsafe { transmute::<[u8; 2], Flags>([1, 3]) }];
+//~^ ERROR it is undefined behavior to use this value
+
+fn main() {}
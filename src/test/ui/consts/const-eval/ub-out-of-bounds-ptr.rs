@@ -0,0 +1,16 @@
+// Pointers in the final value of a constant must point into their allocation, or one past its
+// end, even where validation does not see them at a pointer type.
+
+#![feature(const_ptr_offset)]
+
+const DATA: &[u8; 4] = &[1, 2, 3, 4];
+
+const ONE_PAST_END: *const u8 = DATA.as_ptr().wrapping_offset(4);
+
+const OUT_OF_BOUNDS: *const u8 = DATA.as_ptr().wrapping_offset(5);
+//~^ ERROR it is undefined behavior to use this value
+
+const BEHIND_REF: &*const u8 = &DATA.as_ptr().wrapping_offset(6);
+//~^ ERROR it is undefined behavior to use this value
+
+fn main() {}
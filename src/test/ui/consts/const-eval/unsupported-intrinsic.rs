@@ -0,0 +1,11 @@
+#![feature(core_intrinsics)]
+
+use std::intrinsics;
+
+const SUM: u32 = unsafe { intrinsics::unchecked_add(1, 2) };
+//~^ ERROR any use of this value will cause an error
+
+const ASSUMED: () = unsafe { intrinsics::assume(true) };
+//~^ ERROR any use of this value will cause an error
+
+fn main() {}
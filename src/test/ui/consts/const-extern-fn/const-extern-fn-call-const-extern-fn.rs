@@ -0,0 +1,25 @@
+// run-pass
+#![feature(const_extern_fn)]
+
+const extern "C" fn double(x: u32) -> u32 {
+    x * 2
+}
+
+const unsafe extern "C" fn quadruple(x: u32) -> u32 {
+    double(double(x))
+}
+
+const fn octuple(x: u32) -> u32 {
+    double(unsafe { quadruple(x) })
+}
+
+const extern fn sixteen_times(x: u32) -> u32 {
+    double(octuple(x))
+}
+
+const A: u32 = sixteen_times(1);
+
+fn main() {
+    assert_eq!(A, 16);
+    assert_eq!(octuple(2), 16);
+}
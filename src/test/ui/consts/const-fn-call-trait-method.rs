@@ -0,0 +1,18 @@
+// Check that calls to trait methods, including overloaded operators, explain that const trait
+// impls are not supported.
+
+#![feature(const_fn)]
+
+use std::ops::Add;
+
+const fn add<T: Add<Output = T>>(a: T, b: T) -> T {
+    a + b
+    //~^ ERROR calls in constant functions are limited to constant functions
+}
+
+const fn duplicate<T: Clone>(x: &T) -> T {
+    x.clone()
+    //~^ ERROR calls in constant functions are limited to constant functions
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+// check-pass
+// aux-build:const_fn_qualifs.rs
+
+// The qualifs in the return place of a `const fn` from another crate are used instead of
+// assuming that any value of the return type may contain interior mutability.
+
+extern crate const_fn_qualifs;
+
+use const_fn_qualifs::no_cell;
+
+const X: i32 = {
+    let x = no_cell();
+    let _r = &x;
+    0
+};
+
+fn main() {
+    assert_eq!(X, 0);
+}
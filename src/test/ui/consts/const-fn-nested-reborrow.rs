@@ -0,0 +1,31 @@
+// check-pass
+
+// Reborrowing through several references at once, like `&mut **r`, is treated just like a
+// reborrow through a single reference.
+
+#![feature(const_fn)]
+
+use std::cell::Cell;
+
+const fn reborrow_mut<'a>(r: &'a mut &mut i32) -> &'a mut i32 {
+    &mut **r
+}
+
+const fn reborrow_mut_3<'a>(r: &'a mut &mut &mut i32) -> &'a mut i32 {
+    &mut ***r
+}
+
+const fn reborrow_cell<'a>(r: &'a &Cell<i32>) -> &'a Cell<i32> {
+    &**r
+}
+
+fn main() {
+    let mut x = 0;
+    let mut r = &mut x;
+    *reborrow_mut(&mut r) += 1;
+
+    let cell = Cell::new(0);
+    reborrow_cell(&&cell).set(1);
+
+    let _ = reborrow_mut_3;
+}
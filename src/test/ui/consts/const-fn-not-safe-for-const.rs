@@ -3,6 +3,7 @@
 #![feature(const_fn, const_transmute)]
 
 use std::mem::transmute;
+use std::sync::atomic::AtomicUsize;
 
 fn random() -> u32 { 0 }
 
@@ -16,14 +17,20 @@ const fn sub1() -> u32 {
 
 static Y: u32 = 0;
 
+// Reading an immutable `static` without interior mutability is deterministic.
 const fn get_Y() -> u32 {
     Y
-        //~^ ERROR E0013
 }
 
 const fn get_Y_addr() -> &'static u32 {
     &Y
-        //~^ ERROR E0013
+}
+
+static Z: AtomicUsize = AtomicUsize::new(0);
+
+const fn get_Z_addr() -> &'static AtomicUsize {
+    &Z
+        //~^ ERROR E0753
 }
 
 const fn get() -> u32 {
@@ -0,0 +1,15 @@
+// Thread-local statics may be accessed in a `const fn`, but not when it is evaluated at
+// compile-time.
+
+#![feature(const_fn, const_fn_thread_local, thread_local)]
+
+#[thread_local]
+static A: u32 = 7;
+
+const fn get() -> u32 {
+    A //~ ERROR any use of this value will cause an error
+}
+
+const X: u32 = get();
+
+fn main() {}
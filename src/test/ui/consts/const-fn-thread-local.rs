@@ -0,0 +1,16 @@
+// run-pass
+
+// Thread-local statics may be accessed in a `const fn` that is only called at runtime.
+
+#![feature(const_fn, const_fn_thread_local, thread_local)]
+
+#[thread_local]
+static A: u32 = 7;
+
+const fn get() -> u32 {
+    A
+}
+
+fn main() {
+    assert_eq!(get(), 7);
+}
@@ -0,0 +1,30 @@
+// check-pass
+
+// The autoref of a method receiver that is reached through a `&mut` is a two-phase borrow. It only
+// reborrows data that can already be mutated through the reference, so it is not rejected as a
+// new mutable borrow.
+
+#![feature(const_fn)]
+
+struct Inner;
+
+impl Inner {
+    const fn touch(&mut self) {}
+}
+
+struct Outer {
+    inner: Inner,
+}
+
+impl Outer {
+    const fn touch_inner(&mut self) {
+        self.inner.touch();
+    }
+}
+
+const fn touch_nested(outer: &mut &mut Outer) {
+    outer.inner.touch();
+    outer.touch_inner();
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#![feature(const_fn)]
+
+// Unsafe operations outside of `unsafe` code in a `const fn` are only reported by the unsafety
+// checker, not a second time by const-checking.
+
+const fn deref(x: *const u32) -> u32 {
+    *x
+    //~^ ERROR dereference of raw pointer is unsafe and requires unsafe function or block
+}
+
+union Foo {
+    u: u32,
+    i: i32,
+}
+
+const fn read_union(u: u32) -> i32 {
+    Foo { u }.i
+    //~^ ERROR access to union field is unsafe and requires unsafe function or block
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+// Const-checking errors record which operation was rejected in their JSON output.
+
+// compile-flags: --json=diagnostic-short --error-format=json
+
+const PID: u32 = std::process::id();
+
+fn main() {}
@@ -0,0 +1,92 @@
+// run-pass
+
+// Check that `?` can be used in a const fn when the `Try` and `From` impls involved are const.
+
+#![feature(const_fn, const_if_match, const_trait_impl, try_trait)]
+#![allow(incomplete_features)]
+
+use std::ops::Try;
+
+pub struct DigitError;
+
+#[derive(Debug, PartialEq)]
+pub struct ParseError;
+
+impl From<DigitError> for ParseError {
+    const fn from(_: DigitError) -> Self {
+        ParseError
+    }
+}
+
+pub enum Digit {
+    Ok(u32),
+    Err(DigitError),
+}
+
+impl Try for Digit {
+    type Ok = u32;
+    type Error = DigitError;
+
+    const fn into_result(self) -> Result<u32, DigitError> {
+        match self {
+            Digit::Ok(d) => Ok(d),
+            Digit::Err(e) => Err(e),
+        }
+    }
+
+    const fn from_error(e: DigitError) -> Self {
+        Digit::Err(e)
+    }
+
+    const fn from_ok(d: u32) -> Self {
+        Digit::Ok(d)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Parsed {
+    Ok(u32),
+    Err(ParseError),
+}
+
+impl Try for Parsed {
+    type Ok = u32;
+    type Error = ParseError;
+
+    const fn into_result(self) -> Result<u32, ParseError> {
+        match self {
+            Parsed::Ok(n) => Ok(n),
+            Parsed::Err(e) => Err(e),
+        }
+    }
+
+    const fn from_error(e: ParseError) -> Self {
+        Parsed::Err(e)
+    }
+
+    const fn from_ok(n: u32) -> Self {
+        Parsed::Ok(n)
+    }
+}
+
+const fn digit(b: u8) -> Digit {
+    if b >= b'0' && b <= b'9' {
+        Digit::Ok((b - b'0') as u32)
+    } else {
+        Digit::Err(DigitError)
+    }
+}
+
+const fn parse_two(hi: u8, lo: u8) -> Parsed {
+    let hi = digit(hi)?;
+    let lo = digit(lo)?;
+    Parsed::Ok(hi * 10 + lo)
+}
+
+const GOOD: Parsed = parse_two(b'4', b'2');
+const BAD: Parsed = parse_two(b'4', b'x');
+
+fn main() {
+    assert_eq!(GOOD, Parsed::Ok(42));
+    assert_eq!(BAD, Parsed::Err(ParseError));
+}
@@ -0,0 +1,49 @@
+// run-pass
+
+#![feature(const_fn)]
+
+use std::fmt::Debug;
+
+trait Trait {
+    fn value(&self) -> u32;
+}
+
+impl Trait for u32 {
+    fn value(&self) -> u32 { *self }
+}
+
+impl Trait for [u8; 2] {
+    fn value(&self) -> u32 { self[0] as u32 + self[1] as u32 }
+}
+
+struct Wrapper<'a> {
+    inner: &'a dyn Trait,
+    pair: (&'a dyn Trait, &'a [u8]),
+}
+
+const SIMPLE: &dyn Trait = &3u32;
+
+const NESTED: Wrapper<'static> = Wrapper {
+    inner: &[1, 2],
+    pair: (&4u32, &[5, 6, 7]),
+};
+
+const ARRAY: [&dyn Trait; 2] = [&10u32, &[20, 30]];
+
+static DEBUG: &(dyn Debug + Sync) = &Some(1u8);
+
+const fn as_dyn(x: &u32) -> &dyn Trait {
+    x
+}
+
+const FROM_FN: &dyn Trait = as_dyn(&8);
+
+fn main() {
+    assert_eq!(SIMPLE.value(), 3);
+    assert_eq!(NESTED.inner.value(), 3);
+    assert_eq!(NESTED.pair.0.value(), 4);
+    assert_eq!(NESTED.pair.1, &[5, 6, 7]);
+    assert_eq!(ARRAY[0].value() + ARRAY[1].value(), 60);
+    assert_eq!(format!("{:?}", DEBUG), "Some(1)");
+    assert_eq!(FROM_FN.value(), 8);
+}
@@ -0,0 +1,8 @@
+// run-rustfix
+
+use std::any::TypeId;
+
+pub const A_ID: TypeId = TypeId::of::<u8>();
+//~^ ERROR `std::any::TypeId::of` is not yet stable as a const fn
+
+fn main() {}
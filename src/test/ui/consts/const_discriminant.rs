@@ -0,0 +1,43 @@
+// run-pass
+#![feature(const_discriminant)]
+
+use std::mem::{discriminant, Discriminant};
+
+enum Test {
+    A(u8),
+    B,
+    C { a: u8, b: u8 },
+}
+
+#[repr(i8)]
+enum Signed {
+    Negative = -1,
+    Zero = 0,
+}
+
+const TEST_A: Discriminant<Test> = discriminant(&Test::A(5));
+const TEST_A_OTHER: Discriminant<Test> = discriminant(&Test::A(17));
+const TEST_B: Discriminant<Test> = discriminant(&Test::B);
+const TEST_C: Discriminant<Test> = discriminant(&Test::C { a: 1, b: 2 });
+
+const NEGATIVE: Discriminant<Signed> = discriminant(&Signed::Negative);
+const ZERO: Discriminant<Signed> = discriminant(&Signed::Zero);
+
+const fn same_variant(a: &Test, b: &Test) -> Discriminant<Test> {
+    let _ = discriminant(b);
+    discriminant(a)
+}
+
+fn main() {
+    assert_eq!(TEST_A, TEST_A_OTHER);
+    assert_ne!(TEST_A, TEST_B);
+    assert_ne!(TEST_B, TEST_C);
+    assert_eq!(TEST_A, discriminant(&Test::A(0)));
+    assert_eq!(TEST_B, discriminant(&Test::B));
+    assert_eq!(TEST_C, discriminant(&Test::C { a: 3, b: 4 }));
+
+    assert_eq!(NEGATIVE, discriminant(&Signed::Negative));
+    assert_eq!(ZERO, discriminant(&Signed::Zero));
+
+    assert_eq!(same_variant(&Test::B, &Test::A(1)), TEST_B);
+}
@@ -7,7 +7,7 @@ struct S {
 impl S {
     const fn foo(&mut self, x: u32) {
         self.state = x;
-        //~^ contains unimplemented expression
+        //~^ mutation through a reference is not allowed
     }
 }
 
@@ -22,7 +22,7 @@ type Array = [u32; {
     let y = &mut x;
 //~^ ERROR references in constants may only refer to immutable values
     *y = 42;
-//~^ ERROR constant contains unimplemented expression type
+//~^ ERROR mutation through a reference is not allowed in constants
     *y
 }];
 
@@ -1,7 +1,7 @@
 fn main() {}
 
 const fn slice([a, b]: &[i32]) -> i32 { //~ ERROR refutable pattern in function argument
-    a + b //~ ERROR can only call other `const fn` within a `const fn`
+    a + b //~ ERROR calls in constant functions are limited to constant functions
     //~^ ERROR use of possibly-uninitialized variable: `a`
     //~| ERROR use of possibly-uninitialized variable: `b`
 }
@@ -12,6 +12,6 @@ const _: () = assert!(true);
 const _: () = assert!(false);
 //[stock,panic]~^ ERROR `if` is not allowed in a `const`
 //[if_match]~^^ ERROR panicking in constants is unstable
-//[both]~^^^ ERROR any use of this value will cause an error
+//[both]~^^^ ERROR the evaluated program panicked
 
 fn main() {}
@@ -0,0 +1,78 @@
+// Test `loop`, `while`, `while let` and `for` in a const context.
+
+// run-pass
+
+#![feature(const_fn, const_if_match, const_loop, const_trait_impl)]
+#![allow(incomplete_features)]
+
+const fn fib(n: u32) -> u32 {
+    let mut a = 0;
+    let mut b = 1;
+    let mut i = 0;
+    while i < n {
+        let t = a + b;
+        a = b;
+        b = t;
+        i += 1;
+    }
+    a
+}
+
+const FIB: u32 = fib(10);
+
+const LOOP: u32 = {
+    let mut x = 0;
+    loop {
+        x += 1;
+        if x == 4 {
+            break x * 2;
+        }
+    }
+};
+
+const WHILE_LET: u32 = {
+    let mut x = Some(3);
+    let mut n = 0;
+    while let Some(y) = x {
+        n += y;
+        x = if y > 0 { Some(y - 1) } else { None };
+    }
+    n
+};
+
+// `Iterator::next` cannot mutate the iterator in a const context yet, so this one never yields
+// anything. What matters is that the `into_iter` and `next` calls resolve to `const` impls.
+pub struct Empty;
+pub struct EmptyIter;
+
+impl IntoIterator for Empty {
+    type Item = u32;
+    type IntoIter = EmptyIter;
+
+    const fn into_iter(self) -> EmptyIter {
+        EmptyIter
+    }
+}
+
+impl Iterator for EmptyIter {
+    type Item = u32;
+
+    const fn next(&mut self) -> Option<u32> {
+        None
+    }
+}
+
+const FOR: u32 = {
+    let mut n = 1;
+    for i in Empty {
+        n += i;
+    }
+    n
+};
+
+fn main() {
+    assert_eq!(FIB, 55);
+    assert_eq!(LOOP, 8);
+    assert_eq!(WHILE_LET, 6);
+    assert_eq!(FOR, 1);
+}
@@ -0,0 +1,37 @@
+// Branches on a condition that is not a literal are always checked, so here `x` is *not* always
+// moved into the final value and may be dropped inside the initializer.
+
+#![feature(const_if_match)]
+
+const fn cond(b: bool) -> bool {
+    b
+}
+
+const _: Option<Vec<i32>> = {
+    let y: Option<Vec<i32>> = None;
+    let x = Some(Vec::new());
+    //~^ ERROR destructors cannot be evaluated at compile-time
+
+    if cond(true) {
+        x
+    } else {
+        y
+    }
+};
+
+// The same goes for a named constant, which is not evaluated to find out which branch is taken.
+const TRUE: bool = true;
+
+const _: Option<Vec<i32>> = {
+    let y: Option<Vec<i32>> = None;
+    let x = Some(Vec::new());
+    //~^ ERROR destructors cannot be evaluated at compile-time
+
+    if TRUE {
+        x
+    } else {
+        y
+    }
+};
+
+fn main() {}
@@ -1,10 +1,10 @@
 #![feature(const_if_match)]
 
-// `x` is *not* always moved into the final value may be dropped inside the initializer.
+// `x` is moved into the final value on the only path that can be taken, so it is never dropped
+// inside the initializer.
 const _: Option<Vec<i32>> = {
     let y: Option<Vec<i32>> = None;
     let x = Some(Vec::new());
-    //~^ ERROR destructors cannot be evaluated at compile-time
 
     if true {
         x
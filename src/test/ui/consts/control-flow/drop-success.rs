@@ -21,4 +21,18 @@ const _: Option<Vec<i32>> = {
     }
 };
 
+pub enum Droppy {
+    Unit,
+    Int(i32),
+    Vec(Vec<i32>),
+}
+
+// Only the fields of the enum variant being constructed can be dropped.
+const _: usize = {
+    let _x: Option<String> = None;
+    let _y = Droppy::Unit;
+    let _z = Droppy::Int(42);
+    0
+};
+
 fn main() {}
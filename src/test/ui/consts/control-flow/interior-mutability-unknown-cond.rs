@@ -0,0 +1,33 @@
+// Ensure that *any* assignment to the return place of a value with interior mutability
+// disqualifies it from promotion, unless it is behind a branch on a literal that is never taken.
+
+#![feature(const_if_match)]
+
+use std::cell::Cell;
+
+const fn cond(b: bool) -> bool {
+    b
+}
+
+const X: Option<Cell<i32>> = {
+    let mut x = None;
+    if cond(false) {
+        x = Some(Cell::new(4));
+    }
+    x
+};
+
+const FALSE: bool = false;
+
+const Y: Option<Cell<i32>> = {
+    let mut y = None;
+    if FALSE {
+        y = Some(Cell::new(4));
+    }
+    y
+};
+
+fn main() {
+    let x: &'static _ = &X; //~ ERROR temporary value dropped while borrowed
+    let y: &'static _ = &Y; //~ ERROR temporary value dropped while borrowed
+}
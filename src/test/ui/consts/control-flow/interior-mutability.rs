@@ -1,5 +1,7 @@
-// Ensure that *any* assignment to the return place of a value with interior mutability
-// disqualifies it from promotion.
+// Ensure that an assignment of a value with interior mutability to the return place only
+// disqualifies it from promotion if it can actually happen.
+
+// check-pass
 
 #![feature(const_if_match)]
 
@@ -22,6 +24,6 @@ const Y: Option<Cell<i32>> = {
 };
 
 fn main() {
-    let x: &'static _ = &X; //~ ERROR temporary value dropped while borrowed
-    let y: &'static _ = &Y; //~ ERROR temporary value dropped while borrowed
+    let x: &'static _ = &X;
+    let y: &'static _ = &Y;
 }
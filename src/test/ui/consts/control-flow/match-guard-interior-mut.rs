@@ -0,0 +1,25 @@
+// Ensure that the shallow borrow of the scrutinee that is created for a match guard is not
+// rejected when the scrutinee has interior mutability.
+
+// check-pass
+
+#![feature(const_if_match)]
+
+use std::cell::Cell;
+
+const fn is_some(x: Option<Cell<i32>>) -> bool {
+    match x {
+        Some(_) if true => true,
+        _ => false,
+    }
+}
+
+const X: bool = match Some(Cell::new(5)) {
+    Some(_) if true => true,
+    _ => false,
+};
+
+fn main() {
+    assert!(is_some(Some(Cell::new(0))));
+    assert!(X);
+}
@@ -0,0 +1,17 @@
+// `&&` and `||` also short-circuit alongside let bindings when only `#![feature(const_loop)]` is
+// enabled.
+
+// run-pass
+
+#![feature(const_loop)]
+
+const X: i32 = {
+    let mut x = 0;
+    let _ = false || { x += 1; true };
+    let _ = false && { x += 10; true };
+    x
+};
+
+fn main() {
+    assert_eq!(X, 1);
+}
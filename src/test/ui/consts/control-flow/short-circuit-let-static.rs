@@ -0,0 +1,17 @@
+// `&&` and `||` actually short-circuit in a `static` alongside let bindings when
+// `#![feature(const_if_match)]` is enabled.
+
+// run-pass
+
+#![feature(const_if_match)]
+
+static X: i32 = {
+    let mut x = 0;
+    let _ = false || { x += 1; true };
+    let _ = false && { x += 10; true };
+    x
+};
+
+fn main() {
+    assert_eq!(X, 1);
+}
@@ -7,8 +7,8 @@
 #![feature(const_panic)]
 #![cfg_attr(if_match, feature(const_if_match))]
 
-const _: bool = true || panic!();  //[stock]~ ERROR any use of this value will cause an error
-const _: bool = false && panic!(); //[stock]~ ERROR any use of this value will cause an error
+const _: bool = true || panic!();  //[stock]~ ERROR the evaluated program panicked
+const _: bool = false && panic!(); //[stock]~ ERROR the evaluated program panicked
 
 #[rustc_error]
 fn main() {} //[if_match]~ ERROR fatal error triggered by #[rustc_error]
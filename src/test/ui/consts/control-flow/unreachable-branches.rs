@@ -0,0 +1,60 @@
+// Operations in branches that can never be taken are not const-checked, and neither the values
+// assigned in them nor the paths through them affect the qualifs of a local.
+
+// check-pass
+
+#![feature(const_if_match)]
+
+use std::cell::Cell;
+
+pub fn not_const() -> i32 {
+    0
+}
+
+pub const DEAD_CALL: i32 = if false { not_const() } else { 1 };
+
+// `cfg!` expands to a literal.
+pub const DEAD_CFG: i32 = if cfg!(FALSE) { not_const() } else { 1 };
+
+pub const fn dead_in_const_fn(x: i32) -> i32 {
+    if false {
+        not_const()
+    } else if true {
+        x
+    } else {
+        not_const()
+    }
+}
+
+// `x` is moved into the final value on every path that can be taken.
+pub const DEAD_DROP: Option<Vec<i32>> = {
+    let y: Option<Vec<i32>> = None;
+    let x = Some(Vec::new());
+
+    if true {
+        x
+    } else {
+        y
+    }
+};
+
+const X: Option<Cell<i32>> = {
+    let mut x = None;
+    if false {
+        x = Some(Cell::new(4));
+    }
+    x
+};
+
+const Y: Option<Cell<i32>> = {
+    let mut y = Some(Cell::new(4));
+    if true {
+        y = None;
+    }
+    y
+};
+
+fn main() {
+    let _: &'static _ = &X;
+    let _: &'static _ = &Y;
+}
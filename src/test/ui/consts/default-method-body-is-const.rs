@@ -0,0 +1,33 @@
+// run-pass
+
+#![feature(const_trait_impl)]
+#![allow(incomplete_features)]
+
+trait Shape {
+    fn sides(&self) -> u32;
+
+    #[default_method_body_is_const]
+    fn corners(&self) -> u32 {
+        4
+    }
+}
+
+struct Square;
+
+impl Shape for Square {
+    const fn sides(&self) -> u32 {
+        4
+    }
+}
+
+const CORNERS: u32 = Square.corners();
+
+const fn corners_of(square: &Square) -> u32 {
+    square.corners()
+}
+
+fn main() {
+    assert_eq!(CORNERS, 4);
+    assert_eq!(corners_of(&Square), 4);
+    assert_eq!(Square.sides(), 4);
+}
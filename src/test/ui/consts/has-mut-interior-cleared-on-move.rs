@@ -0,0 +1,16 @@
+// A local that held a value with interior mutability can be borrowed once that value has been
+// moved out and the local has been reinitialized with one that does not.
+
+// check-pass
+
+use std::cell::Cell;
+
+pub const REUSED: usize = {
+    let mut x = Some(Cell::new(0));
+    let _y = x;
+    x = None;
+    let _r = &x;
+    0
+};
+
+fn main() {}
@@ -0,0 +1,15 @@
+// run-rustfix
+// Check that we suggest replacing a boxed literal or array with a reference to `'static` data.
+
+#![feature(box_syntax)]
+#![allow(dead_code)]
+
+const ARRAY: Box<[u8; 3]> = box [1, 2, 3];
+//~^ ERROR allocations are not allowed in constants
+//~| ERROR mutation through a reference is not allowed in constants
+
+static SLICE: Box<[u8]> = box [0; 4];
+//~^ ERROR allocations are not allowed in statics
+//~| ERROR mutation through a reference is not allowed in statics
+
+fn main() {}
@@ -0,0 +1,13 @@
+// ignore-emscripten no asm! support
+
+#![feature(asm, const_fn)]
+
+const fn foo() {
+    unsafe { asm!("nop") }
+    //~^ ERROR inline assembly is not allowed in constant functions
+}
+
+const BAR: () = unsafe { asm!("nop") };
+//~^ ERROR inline assembly is not allowed in constants
+
+fn main() {}
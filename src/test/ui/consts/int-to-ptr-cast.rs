@@ -0,0 +1,28 @@
+// run-pass
+
+// Check that integers can be cast to raw pointers in a const context, e.g. to describe a map of
+// memory-mapped registers.
+
+pub const GPIO: *mut u32 = 0x4800_0000 as *mut u32;
+
+pub struct Registers {
+    pub mode: *mut u32,
+    pub output: *mut u32,
+}
+
+pub const fn registers(base: usize) -> Registers {
+    Registers {
+        mode: base as *mut u32,
+        output: (base + 0x14) as *mut u32,
+    }
+}
+
+pub const GPIOA: Registers = registers(0x4800_0000);
+pub static GPIOB: usize = 0x4800_0400;
+
+fn main() {
+    assert_eq!(GPIO as usize, 0x4800_0000);
+    assert_eq!(GPIOA.mode, GPIO);
+    assert_eq!(GPIOA.output as usize, 0x4800_0014);
+    assert_eq!(registers(GPIOB).output as usize, 0x4800_0414);
+}
@@ -0,0 +1,26 @@
+// Check that we point out the type that makes a borrowed constant interior mutable.
+
+use std::cell::Cell;
+
+struct Inner {
+    flag: bool,
+    counter: Cell<u8>,
+}
+
+struct Outer {
+    name: &'static str,
+    inner: Inner,
+}
+
+const OUTER: Outer = Outer {
+    name: "outer",
+    inner: Inner { flag: true, counter: Cell::new(0) },
+};
+
+const REF: &Outer = &OUTER;
+//~^ ERROR cannot borrow a constant which may contain interior mutability
+
+const OPT: &Option<[Cell<u8>; 2]> = &Some([Cell::new(0), Cell::new(1)]);
+//~^ ERROR cannot borrow a constant which may contain interior mutability
+
+fn main() {}
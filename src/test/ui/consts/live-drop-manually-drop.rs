@@ -0,0 +1,18 @@
+// run-rustfix
+// Check that we suggest wrapping a variable that is never used again in a `ManuallyDrop`.
+
+#![allow(dead_code, unused_variables)]
+
+struct HasDrop;
+
+impl Drop for HasDrop {
+    fn drop(&mut self) {}
+}
+
+const X: u32 = {
+    let guard = HasDrop;
+    //~^ ERROR destructors cannot be evaluated at compile-time
+    42
+};
+
+fn main() {}
@@ -1,5 +1,7 @@
 const fn foo(a: i32) -> Vec<i32> {
-    vec![1, 2, 3] //~ ERROR heap allocations are not allowed in const fn
+    vec![1, 2, 3] //~ ERROR allocations are not allowed in constant functions
+    //~| ERROR unsizing casts are not allowed in const fn
+    //~| ERROR calls in constant functions are limited to constant functions
 }
 
 fn main() {}
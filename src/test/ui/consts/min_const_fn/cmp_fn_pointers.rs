@@ -1,5 +1,5 @@
 const fn cmp(x: fn(), y: fn()) -> bool { //~ ERROR function pointers in const fn are unstable
-    unsafe { x == y }
+    unsafe { x == y } //~ ERROR only int, `bool` and `char` operations are stable in const fn
 }
 
 fn main() {}
@@ -11,5 +11,6 @@ const fn no_inner_dyn_trait2(x: Hide) {
 }
 const fn no_inner_dyn_trait_ret() -> Hide { Hide(HasDyn { field: &0 }) }
 //~^ ERROR trait bounds other than `Sized`
+//~| ERROR unsizing casts are not allowed in const fn
 
 fn main() {}
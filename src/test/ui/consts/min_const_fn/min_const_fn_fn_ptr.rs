@@ -13,5 +13,6 @@ const fn no_inner_dyn_trait2(x: Hide) {
 }
 const fn no_inner_dyn_trait_ret() -> Hide { Hide(HasPtr { field }) }
 //~^ ERROR function pointers in const fn
+//~| ERROR function pointer casts are not allowed in const fn
 
 fn main() {}
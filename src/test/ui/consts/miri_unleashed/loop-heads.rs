@@ -0,0 +1,25 @@
+// compile-flags: -Zunleash-the-miri-inside-of-you
+// check-pass
+
+// Check that each loop in a const context is reported separately, pointing at its head.
+
+#![feature(const_if_match)]
+
+const fn loops(n: u32) -> u32 {
+    let mut i = 0;
+    let mut sum = 0;
+    while i < n {
+        //~^ WARN skipping const checks
+        sum += i;
+        i += 1;
+    }
+
+    loop {
+        if sum > 100 {
+            break sum;
+        }
+        sum += 1;
+    }
+}
+
+fn main() {}
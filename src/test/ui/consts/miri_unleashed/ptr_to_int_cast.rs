@@ -0,0 +1,23 @@
+// compile-flags: -Zunleash-the-miri-inside-of-you
+
+#![feature(const_raw_ptr_to_usize_cast)]
+#![deny(const_err)]
+
+// Pointers cast to integers are kept symbolic, so evaluation only fails once the actual address
+// is needed.
+
+const ROUNDTRIP: *const u8 = &42u8 as *const u8 as usize as *const u8;
+
+const COPIED: (*const u8, *const u8) = {
+    let addr = &42u8 as *const u8 as usize;
+    let copy = addr;
+    (addr as *const u8, copy as *const u8)
+};
+
+const ARITHMETIC: usize = (&42u8 as *const u8 as usize) / 2;
+//~^ ERROR any use of this value will cause an error
+
+fn main() {
+    assert_eq!(unsafe { *ROUNDTRIP }, 42);
+    assert_eq!(COPIED.0, COPIED.1);
+}
@@ -0,0 +1,19 @@
+// compile-flags: -Zunleash-the-miri-inside-of-you
+// check-pass
+
+// Check that all the checks skipped in an item are reported in a single warning.
+
+#![allow(const_err)]
+
+fn foo() -> u32 { 0 }
+
+static S: u32 = 1;
+
+const C: u32 = {
+    let a = foo();
+    //~^ WARN skipping const checks
+    let b = S;
+    a + b
+};
+
+fn main() {}
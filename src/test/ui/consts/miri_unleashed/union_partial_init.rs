@@ -0,0 +1,27 @@
+// compile-flags: -Zunleash-the-miri-inside-of-you
+// run-pass
+
+#![feature(const_raw_ptr_deref)]
+
+// Unions are copied byte by byte, so copying a partially initialized union keeps its initialized
+// bytes, even if the union has a scalar layout.
+
+#[derive(Copy, Clone)]
+union MaybeUninit {
+    uninit: (),
+    init: u32,
+}
+
+const FIRST_BYTE: u8 = {
+    let mut x = MaybeUninit { uninit: () };
+    let ptr = &mut x as *mut MaybeUninit as *mut u8;
+    //~^ WARN skipping const checks
+    unsafe { *ptr = 42; }
+    //~^ WARN skipping const checks
+    let y = x;
+    unsafe { *(&y as *const MaybeUninit as *const u8) }
+};
+
+fn main() {
+    assert_eq!(FIRST_BYTE, 42);
+}
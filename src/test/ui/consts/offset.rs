@@ -0,0 +1,28 @@
+// run-pass
+
+#![feature(const_ptr_offset)]
+#![feature(const_raw_ptr_deref)]
+
+const DATA: [u32; 4] = [1, 2, 3, 4];
+
+const SECOND: u32 = unsafe { *(&DATA as *const u32).offset(1) };
+const LAST: u32 = unsafe { *(&DATA as *const u32).offset(3) };
+
+// Offsetting to one past the end and back is fine.
+const BACK_FROM_END: u32 = unsafe {
+    let end = (&DATA as *const u32).offset(4);
+    *end.offset(-2)
+};
+
+// `wrapping_offset` may leave the allocation, as long as the result is not dereferenced.
+const WRAPPED: u32 = unsafe {
+    let ptr = (&DATA as *const u32).wrapping_offset(10);
+    *ptr.wrapping_offset(-8)
+};
+
+fn main() {
+    assert_eq!(SECOND, 2);
+    assert_eq!(LAST, 4);
+    assert_eq!(BACK_FROM_END, 3);
+    assert_eq!(WRAPPED, 3);
+}
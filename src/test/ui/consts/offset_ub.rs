@@ -0,0 +1,33 @@
+// ignore-x86 FIXME: missing sysroot spans (#53081)
+
+#![feature(const_ptr_offset)]
+
+pub const AFTER_END: *const u8 = {
+    //~^ NOTE
+    let data = [5u8, 6, 7];
+    unsafe { data.as_ptr().offset(4) }
+};
+
+pub const BEFORE_START: *const u8 = {
+    //~^ NOTE
+    let data = [5u8, 6, 7];
+    unsafe { data.as_ptr().offset(-1) }
+};
+
+pub const OVERFLOW: *const u16 = {
+    //~^ NOTE
+    let data = [5u16, 6, 7];
+    unsafe { data.as_ptr().offset(isize::max_value()) }
+};
+
+pub const NULL_PTR: *const u8 = {
+    //~^ NOTE
+    unsafe { (0 as *const u8).offset(1) }
+};
+
+pub const DANGLING: *const u8 = {
+    //~^ NOTE
+    unsafe { (8 as *const u8).offset(1) }
+};
+
+fn main() {}
@@ -5,7 +5,7 @@ const FOO: &u32 = {
     {
         let b: *mut u32 = &mut a; //~ ERROR may only refer to immutable values
         unsafe { *b = 5; } //~ ERROR dereferencing raw pointers in constants
-        //~^ contains unimplemented expression
+        //~^ mutation through a reference is not allowed
     }
     &{a}
 };
@@ -0,0 +1,21 @@
+// build-pass
+
+// Borrows of expressions that always fail to evaluate are still promoted, but linted against.
+
+#![allow(const_err)]
+
+fn main() {
+    let _div = &(1 / 0);
+    //~^ WARN reaching this expression at runtime will panic or abort
+    //~| WARN this was previously accepted by the compiler but is being phased out
+    let _rem = &(-128i8 % -1);
+    //~^ WARN reaching this expression at runtime will panic or abort
+    //~| WARN this was previously accepted by the compiler but is being phased out
+    let _index = &[1, 2, 3][4];
+    //~^ WARN reaching this expression at runtime will panic or abort
+    //~| WARN this was previously accepted by the compiler but is being phased out
+
+    // The value is computed at runtime, so this is not linted.
+    let x = 0;
+    let _runtime = &(1 / x);
+}
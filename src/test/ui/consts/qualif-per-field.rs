@@ -0,0 +1,27 @@
+// check-pass
+
+// Assigning a value with interior mutability to one field of a local does not prevent the other
+// fields from being borrowed.
+
+use std::cell::Cell;
+
+pub struct Pair {
+    a: Option<Cell<i32>>,
+    b: Option<Cell<i32>>,
+}
+
+pub const STRUCT: Option<Cell<i32>> = {
+    let mut pair = Pair { a: None, b: None };
+    pair.a = Some(Cell::new(1));
+    let _b = &pair.b;
+    pair.a
+};
+
+pub const TUPLE: Option<Cell<i32>> = {
+    let mut pair: (Option<Cell<i32>>, Option<Cell<i32>>) = (None, None);
+    pair.1 = Some(Cell::new(1));
+    let _a = &pair.0;
+    pair.1
+};
+
+fn main() {}
@@ -0,0 +1,9 @@
+// Check that we suggest comparing raw pointers directly instead of casting them to integers.
+
+fn main() {
+    const A: u8 = 0;
+    const B: u8 = 1;
+    const SAME: bool = unsafe { &A as *const u8 as usize == &B as *const u8 as usize };
+    //~^ ERROR casting pointers to integers in constants is unstable
+    println!("{}", SAME);
+}
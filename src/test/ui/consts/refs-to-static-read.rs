@@ -0,0 +1,16 @@
+// Reading through a reference to a `static` in a `const` still reads from the `static`.
+
+#![feature(const_refs_to_static)]
+
+static S: u32 = 5;
+
+const C: &u32 = &S;
+
+const READ_LOCAL: u32 = {
+    let r = &S;
+    *r //~ ERROR constants cannot refer to statics
+};
+
+const READ_CONST: u32 = *C; //~ ERROR constants cannot refer to statics
+
+fn main() {}
@@ -0,0 +1,19 @@
+// run-pass
+
+#![feature(const_refs_to_static)]
+
+pub struct Registers {
+    pub control: u32,
+    pub status: u32,
+}
+
+pub static REGISTERS: Registers = Registers { control: 1, status: 2 };
+
+pub const CONTROL: &u32 = &REGISTERS.control;
+pub const ALL: &Registers = &REGISTERS;
+
+fn main() {
+    assert_eq!(*CONTROL, 1);
+    assert_eq!(ALL.status, 2);
+    assert!(std::ptr::eq(ALL, &REGISTERS));
+}
@@ -0,0 +1,19 @@
+#![feature(const_in_array_repeat_expressions)]
+
+struct Bar(u32);
+
+// `x` is not a temporary, so it cannot be promoted and has to be `Copy` to be repeated.
+const ARR: [Option<Bar>; 3] = {
+    let x = Some(Bar(1));
+    [x; 3]
+    //~^ ERROR the trait bound `std::option::Option<Bar>: std::marker::Copy` is not satisfied
+};
+
+// Duplicating this element bitwise could create aliasing mutable references.
+static mut REFS: [Option<&'static mut u8>; 2] = {
+    let x: Option<&'static mut u8> = None;
+    [x; 2]
+    //~^ ERROR the trait bound `std::option::Option<&mut u8>: std::marker::Copy` is not satisfied
+};
+
+fn main() {}
@@ -0,0 +1,27 @@
+// run-pass
+
+// Mutable borrows of values without drop glue are allowed in a `static mut` initializer.
+
+struct Descriptor {
+    addr: usize,
+    len: u32,
+}
+
+static mut DESC: &mut Descriptor = &mut Descriptor { addr: 0, len: 0 };
+static mut COUNT: &mut u32 = &mut 0;
+static mut RING: &mut ([u8; 4], usize) = &mut ([0; 4], 0);
+
+fn main() {
+    unsafe {
+        DESC.len = 16;
+        *COUNT += 1;
+        RING.0[RING.1] = 7;
+        RING.1 += 1;
+
+        assert_eq!(DESC.addr, 0);
+        assert_eq!(DESC.len, 16);
+        assert_eq!(*COUNT, 1);
+        assert_eq!(RING.0, [7, 0, 0, 0]);
+        assert_eq!(RING.1, 1);
+    }
+}
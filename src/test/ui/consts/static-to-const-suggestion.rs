@@ -0,0 +1,23 @@
+// Check that we suggest turning a `static` that is read in a constant into a `const`, but only if
+// its type has neither interior mutability nor drop glue.
+
+use std::sync::atomic::AtomicU32;
+
+static PLAIN: u32 = 1;
+pub(crate) static PUBLIC: (u8, bool) = (2, true);
+static INTERIOR_MUT: (u32, AtomicU32) = (3, AtomicU32::new(4));
+static DROP_GLUE: (u32, Vec<u32>) = (5, Vec::new());
+
+const A: u32 = PLAIN;
+//~^ ERROR constants cannot refer to statics
+
+const B: u8 = PUBLIC.0;
+//~^ ERROR constants cannot refer to statics
+
+const C: u32 = INTERIOR_MUT.0;
+//~^ ERROR constants cannot refer to statics
+
+const D: u32 = DROP_GLUE.0;
+//~^ ERROR constants cannot refer to statics
+
+fn main() {}
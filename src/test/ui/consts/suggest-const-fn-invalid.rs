@@ -0,0 +1,24 @@
+// Check that we don't suggest making a function `const` when that would not be enough to call it in
+// a constant.
+
+#![allow(const_err)]
+
+fn count() -> u32 {
+    let mut x = 0;
+    while x < 10 {
+        x += 1;
+    }
+    x
+}
+
+fn generic<T: Default>() -> T {
+    T::default()
+}
+
+const COUNT: u32 = count();
+//~^ ERROR calls in constants are limited to constant functions
+
+const GENERIC: u32 = generic::<u32>();
+//~^ ERROR calls in constants are limited to constant functions
+
+fn main() {}
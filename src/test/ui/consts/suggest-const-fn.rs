@@ -0,0 +1,24 @@
+// run-rustfix
+// Check that we suggest making a local function `const` when its body would pass const checking.
+
+#![allow(const_err, dead_code)]
+
+fn double(x: u32) -> u32 {
+    x * 2
+}
+
+struct Foo;
+
+impl Foo {
+    pub fn new() -> Foo {
+        Foo
+    }
+}
+
+const X: u32 = double(21);
+//~^ ERROR calls in constants are limited to constant functions
+
+const FOO: Foo = Foo::new();
+//~^ ERROR calls in constants are limited to constant functions
+
+fn main() {}
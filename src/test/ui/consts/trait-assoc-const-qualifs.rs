@@ -0,0 +1,22 @@
+// Uses of trait associated constants are qualified based on the value in the impl when the impl
+// is known, instead of conservatively based on their type.
+
+// run-pass
+
+use std::cell::Cell;
+
+trait Zero {
+    const ZERO: Self;
+}
+
+impl Zero for Option<Cell<i32>> {
+    const ZERO: Self = None;
+}
+
+const C: &Option<Cell<i32>> = &<Option<Cell<i32>>>::ZERO;
+
+fn main() {
+    let x: &'static Option<Cell<i32>> = &<Option<Cell<i32>>>::ZERO;
+    assert!(x.is_none());
+    assert!(C.is_none());
+}
@@ -0,0 +1,10 @@
+// check-pass
+
+// A zero-length repeat expression has no elements, so it does not inherit the qualifs of its
+// operand.
+
+use std::cell::Cell;
+
+pub const EMPTY: &[Cell<i32>; 0] = &[Cell::new(0); 0];
+
+fn main() {}
@@ -2,6 +2,6 @@
 #![allow(warnings)]
 
 const CON : Box<i32> = box 0; //~ ERROR E0010
-//~^ ERROR constant contains unimplemented expression type
+//~^ ERROR mutation through a reference is not allowed in constants
 
 fn main() {}
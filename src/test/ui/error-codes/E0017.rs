@@ -4,7 +4,7 @@ static mut M: i32 = 3;
 
 const CR: &'static mut i32 = &mut C; //~ ERROR E0017
 static STATIC_REF: &'static mut i32 = &mut X; //~ ERROR E0017
-                                              //~| ERROR E0019
+                                              //~| ERROR E0751
                                               //~| ERROR cannot borrow
 static CONST_REF: &'static mut i32 = &mut C; //~ ERROR E0017
 static STATIC_MUT_REF: &'static mut i32 = unsafe { &mut M }; //~ ERROR E0017
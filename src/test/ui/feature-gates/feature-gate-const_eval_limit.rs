@@ -0,0 +1,4 @@
+#![const_eval_limit = "1000"]
+//~^ ERROR the `#[const_eval_limit]` attribute is an experimental feature
+
+fn main() {}
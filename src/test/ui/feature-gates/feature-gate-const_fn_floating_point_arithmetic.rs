@@ -0,0 +1,21 @@
+#![feature(const_fn)]
+
+const fn add(a: f32, b: f32) -> f32 {
+    a + b
+    //~^ ERROR floating point arithmetic is not allowed in constant functions
+}
+
+const fn neg(a: f64) -> f64 {
+    -a
+    //~^ ERROR floating point arithmetic is not allowed in constant functions
+}
+
+const fn to_int(a: f32) -> u32 {
+    a as u32
+    //~^ ERROR floating point arithmetic is not allowed in constant functions
+}
+
+// Floating-point arithmetic is always allowed outside of `const fn`.
+const SUM: f32 = 1.0 + 2.0;
+
+fn main() {}
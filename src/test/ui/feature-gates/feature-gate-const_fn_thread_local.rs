@@ -0,0 +1,11 @@
+#![feature(const_fn, thread_local)]
+
+#[thread_local]
+static A: u32 = 1;
+
+const fn f() -> u32 {
+    A
+    //~^ ERROR thread-local statics in constant functions are unstable
+}
+
+fn main() {}
@@ -15,4 +15,8 @@ fn bar() {
     //~^ ERROR the trait bound `std::option::Option<std::string::String>: std::marker::Copy` is not satisfied [E0277]
 }
 
+// In a `const`, a promotable element is only rejected by the feature gate.
+const BAZ: [Option<Bar>; 2] = [None; 2];
+//~^ ERROR repeat expressions with non-`Copy` elements in constants are unstable
+
 fn main() {}
@@ -0,0 +1,13 @@
+#![feature(const_if_match)]
+
+const ZERO: u32 = loop { break 0; }; //~ ERROR `loop` is not allowed in a `const`
+
+const FOUR: u32 = {
+    let mut x = 0;
+    while x < 4 { //~ ERROR `while` is not allowed in a `const`
+        x += 1;
+    }
+    x
+};
+
+fn main() {}
@@ -0,0 +1,8 @@
+#![feature(raw_ref_op)]
+
+const fn foo() {
+    let mut x = 0;
+    let _y = &raw mut x; //~ ERROR `&raw mut` in constant functions is unstable
+}
+
+fn main() {}
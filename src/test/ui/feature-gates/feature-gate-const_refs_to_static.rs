@@ -0,0 +1,5 @@
+static S: u32 = 0;
+
+const C: &u32 = &S; //~ ERROR constants cannot refer to statics
+
+fn main() {}
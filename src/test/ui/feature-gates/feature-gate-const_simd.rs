@@ -0,0 +1,17 @@
+#![feature(repr_simd)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+struct u8x2(u8, u8);
+
+const V: u8x2 = u8x2(1, 2);
+//~^ ERROR SIMD vectors in constants are unstable
+
+const fn first(v: u8x2) -> u8 {
+    v.0
+    //~^ ERROR SIMD vectors in constant functions are unstable
+}
+
+fn main() {
+    let _ = first(V);
+}
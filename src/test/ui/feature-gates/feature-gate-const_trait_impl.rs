@@ -0,0 +1,16 @@
+#![feature(const_fn)]
+
+trait Answer { fn answer(&self) -> u32; }
+
+const fn dispatch(x: &dyn Answer) -> u32 {
+    x.answer()
+    //~^ ERROR calling methods on trait objects in constant functions is unstable
+}
+
+trait Corners {
+    #[default_method_body_is_const]
+    //~^ ERROR `default_method_body_is_const` is a temporary placeholder
+    fn corners(&self) -> u32 { 4 }
+}
+
+fn main() {}
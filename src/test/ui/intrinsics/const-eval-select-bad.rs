@@ -0,0 +1,14 @@
+#![feature(const_fn, core_intrinsics)]
+
+use std::intrinsics::const_eval_select;
+
+fn not_const(x: u32) -> u32 {
+    x
+}
+
+const fn select(x: u32) -> u32 {
+    unsafe { const_eval_select((x,), not_const, not_const) }
+    //~^ ERROR calls in constant functions are limited to constant functions
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#![feature(core_intrinsics)]
+
+use std::intrinsics::const_eval_select;
+
+const fn in_const(x: u32) -> u32 {
+    x
+}
+
+fn main() {
+    let _ = unsafe { const_eval_select((1,), in_const, |x: u32| x + 1) };
+    //~^ ERROR this argument to `const_eval_select` must be a function item
+    let at_rt: fn(u32) -> u32 = |x| x;
+    let _ = unsafe { const_eval_select((1,), in_const, at_rt) };
+    //~^ ERROR this argument to `const_eval_select` must be a function item
+}
@@ -0,0 +1,24 @@
+// run-pass
+
+#![feature(const_fn, core_intrinsics)]
+
+use std::intrinsics::const_eval_select;
+
+const fn in_const(x: u32, y: u32) -> u32 {
+    x + y
+}
+
+fn at_rt(x: u32, y: u32) -> u32 {
+    x * y
+}
+
+const fn select(x: u32, y: u32) -> u32 {
+    unsafe { const_eval_select((x, y), in_const, at_rt) }
+}
+
+const COMPILE_TIME: u32 = select(3, 4);
+
+fn main() {
+    assert_eq!(COMPILE_TIME, 7);
+    assert_eq!(select(3, 4), 12);
+}
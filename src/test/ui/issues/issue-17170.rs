@@ -1,5 +1,5 @@
 // run-pass
-#![feature(repr_simd)]
+#![feature(repr_simd, const_simd)]
 
 #[repr(simd)]
 struct T(f64, f64, f64);
@@ -0,0 +1,32 @@
+// check-pass
+
+struct MyStruct {
+    field: bool,
+    inner_array: [char; 1],
+}
+
+impl MyStruct {
+    const fn new() -> Self {
+        MyStruct { field: true, inner_array: ['a'] }
+    }
+
+    fn use_mut(&mut self) {}
+}
+
+const ARRAY: [u8; 1] = [25];
+const MY_STRUCT: MyStruct = MyStruct::new();
+const PTR: *mut u8 = 0 as *mut u8;
+
+fn main() {
+    ARRAY[0] = 5; //~ WARN attempting to modify a `const` item
+    MY_STRUCT.field = false; //~ WARN attempting to modify a `const` item
+    MY_STRUCT.inner_array[0] = 'b'; //~ WARN attempting to modify a `const` item
+    MY_STRUCT.use_mut(); //~ WARN taking a mutable reference to a `const` item
+    let _ = &mut MY_STRUCT; //~ WARN taking a mutable reference to a `const` item
+
+    // Neither of these modify the temporary created for the `const` item.
+    let _ = MY_STRUCT.field;
+    if false {
+        unsafe { *PTR = 1; }
+    }
+}
@@ -0,0 +1,12 @@
+// Checks the output of `-Zdataflow-stats`, which is sorted by body, then by analysis.
+
+// check-pass
+// compile-flags: -Zdataflow-stats
+// normalize-stdout-test "\d+ us" -> "N us"
+
+const B: usize = 0;
+const A: usize = B;
+
+fn main() {
+    let _ = A;
+}
@@ -0,0 +1,19 @@
+// compile-flags: -Zunleash-the-miri-inside-of-you
+
+#![feature(core_intrinsics, rustc_attrs)]
+
+use std::cell::Cell;
+use std::intrinsics::rustc_peek;
+
+#[rustc_mir(rustc_peek_has_mut_interior, stop_after_dataflow)]
+const BOO: i32 = {
+    let cell = Cell::new(0);
+    let none: Option<Cell<i32>> = None;
+
+    unsafe { rustc_peek(&cell) };
+    unsafe { rustc_peek(&none) }; //~ ERROR rustc_peek: bit not set
+
+    0
+};
+
+fn main() {}
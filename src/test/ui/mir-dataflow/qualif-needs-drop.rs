@@ -0,0 +1,18 @@
+// compile-flags: -Zunleash-the-miri-inside-of-you
+
+#![feature(core_intrinsics, rustc_attrs)]
+
+use std::intrinsics::rustc_peek;
+
+#[rustc_mir(rustc_peek_needs_drop, stop_after_dataflow)]
+const BOO: usize = {
+    let v = Vec::<i32>::new();
+    let none: Option<Vec<i32>> = None;
+
+    unsafe { rustc_peek(&v) };
+    unsafe { rustc_peek(&none) }; //~ ERROR rustc_peek: bit not set
+
+    0
+};
+
+fn main() {}
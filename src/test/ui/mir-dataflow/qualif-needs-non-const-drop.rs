@@ -0,0 +1,28 @@
+// compile-flags: -Zunleash-the-miri-inside-of-you
+
+#![feature(core_intrinsics, rustc_attrs)]
+
+use std::intrinsics::rustc_peek;
+
+struct NonConstDrop;
+
+impl Drop for NonConstDrop {
+    fn drop(&mut self) {}
+}
+
+#[rustc_mir(rustc_peek_needs_non_const_drop, stop_after_dataflow)]
+const BOO: usize = {
+    let x = NonConstDrop;
+    let none: Option<NonConstDrop> = None;
+
+    unsafe { rustc_peek(&x) };
+    unsafe { rustc_peek(&none) }; //~ ERROR rustc_peek: bit not set
+
+    let y = if true { x } else { NonConstDrop };
+    unsafe { rustc_peek(&y) };
+    std::mem::forget(y);
+
+    0
+};
+
+fn main() {}
@@ -0,0 +1,20 @@
+// compile-flags: -Zunleash-the-miri-inside-of-you
+
+#![feature(core_intrinsics, rustc_attrs)]
+
+use std::intrinsics::rustc_peek;
+
+static FOO: i32 = 0;
+
+#[rustc_mir(rustc_peek_ref_to_static, stop_after_dataflow)]
+const BOO: usize = {
+    let r = &FOO;
+    let none: Option<&i32> = None;
+
+    unsafe { rustc_peek(&r) };
+    unsafe { rustc_peek(&none) }; //~ ERROR rustc_peek: bit not set
+
+    0
+};
+
+fn main() {}
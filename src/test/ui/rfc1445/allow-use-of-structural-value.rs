@@ -0,0 +1,47 @@
+// The `indirect_structural_match` lint only fires for constants whose value may contain a type
+// with a custom `PartialEq` impl, not for every constant whose type mentions one.
+
+// run-pass
+
+#![deny(indirect_structural_match)]
+
+struct CustomEq;
+
+impl PartialEq for CustomEq {
+    fn eq(&self, _: &Self) -> bool { false }
+}
+
+impl Eq for CustomEq { }
+
+#[derive(PartialEq, Eq)]
+enum Foo {
+    Bar,
+    Baz(CustomEq),
+}
+
+const BAR: Foo = Foo::Bar;
+const BAR_REF: &Foo = &Foo::Bar;
+const BAR_REF_VIA_CONST: &Foo = &BAR;
+const NONE_REF: &Option<CustomEq> = &None;
+
+fn main() {
+    match &Foo::Bar {
+        BAR_REF => {}
+        _ => panic!(),
+    }
+
+    match &Foo::Bar {
+        BAR_REF_VIA_CONST => {}
+        _ => panic!(),
+    }
+
+    match &None {
+        NONE_REF => {}
+        _ => panic!(),
+    }
+
+    match &Foo::Baz(CustomEq) {
+        BAR_REF => panic!(),
+        _ => {}
+    }
+}
@@ -0,0 +1,38 @@
+// Constants whose value contains a function pointer or a raw pointer can be used as patterns, but
+// pointer addresses are not guaranteed to be unique or stable, so `pointer_structural_match` can
+// be enabled to lint against them.
+
+#![deny(pointer_structural_match)]
+
+fn foo() {}
+
+const FN: fn() = foo;
+const PTR: *const u8 = &0;
+
+// The type contains a function pointer, but the value does not.
+const NO_FN: Option<fn()> = None;
+const NOT_PTR: usize = 0;
+
+fn main() {
+    match foo as fn() {
+        FN => {}
+        //~^ ERROR constant containing a function pointer or raw pointer used in a pattern
+        _ => {}
+    }
+
+    match &1 as *const u8 {
+        PTR => {}
+        //~^ ERROR constant containing a function pointer or raw pointer used in a pattern
+        _ => {}
+    }
+
+    match Some(foo as fn()) {
+        NO_FN => {}
+        _ => {}
+    }
+
+    match 0 {
+        NOT_PTR => {}
+        _ => {}
+    }
+}
@@ -2,6 +2,6 @@
 
 static mut a: Box<isize> = box 3;
 //~^ ERROR allocations are not allowed in statics
-//~| ERROR static contains unimplemented expression type
+//~| ERROR mutation through a reference is not allowed in statics
 
 fn main() {}
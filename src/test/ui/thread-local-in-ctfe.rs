@@ -17,7 +17,7 @@ const E: &u32 = &A;
 
 const fn f() -> u32 {
     A
-    //~^ ERROR thread-local statics cannot be accessed at compile-time
+    //~^ ERROR thread-local statics in constant functions are unstable
 }
 
 fn main() {}
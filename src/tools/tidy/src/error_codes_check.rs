@@ -49,6 +49,7 @@ const WHITELIST: &[&str] = &[
     "E0717",
     "E0727",
     "E0729",
+    "E0747",
 ];
 
 fn check_error_code_explanation(